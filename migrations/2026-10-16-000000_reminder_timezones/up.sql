@@ -0,0 +1,2 @@
+-- IANA time zone in which scheduled times are interpreted
+ALTER TABLE reminders ADD COLUMN timezone TEXT NOT NULL DEFAULT 'UTC'
@@ -0,0 +1,2 @@
+-- Number of weeks between each week in which a weekly schedule occurs
+ALTER TABLE reminders ADD COLUMN week_interval INTEGER NOT NULL DEFAULT 1
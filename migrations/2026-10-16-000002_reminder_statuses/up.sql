@@ -0,0 +1,2 @@
+-- Whether the reminder is active or finished
+ALTER TABLE reminders ADD COLUMN status TEXT NOT NULL DEFAULT 'active'
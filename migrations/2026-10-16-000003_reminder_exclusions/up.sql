@@ -0,0 +1,2 @@
+-- JSON object of excluded dates and whether excluded times advance the rotation
+ALTER TABLE reminders ADD COLUMN exclusions TEXT NOT NULL DEFAULT '{"dates":[],"advance_rotation":false}'
@@ -0,0 +1,2 @@
+ALTER TABLE reminders DROP COLUMN occurrence_limit;
+ALTER TABLE reminders DROP COLUMN until
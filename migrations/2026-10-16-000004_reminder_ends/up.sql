@@ -0,0 +1,3 @@
+-- Last date and maximum number of times a schedule occurs, if the schedule ends
+ALTER TABLE reminders ADD COLUMN until TEXT;
+ALTER TABLE reminders ADD COLUMN occurrence_limit INTEGER
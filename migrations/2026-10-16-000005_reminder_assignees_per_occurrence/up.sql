@@ -0,0 +1,2 @@
+-- Number of assignees for each occurrence of a schedule
+ALTER TABLE reminders ADD COLUMN assignees_per_occurrence INTEGER NOT NULL DEFAULT 1
@@ -0,0 +1,2 @@
+-- Strategy for rotating through the assignees of a schedule
+ALTER TABLE reminders ADD COLUMN rotation TEXT NOT NULL DEFAULT 'round-robin'
@@ -0,0 +1,10 @@
+-- Dates on which users are away from their assignments
+CREATE TABLE absences (
+  uid INTEGER PRIMARY KEY NOT NULL,
+  -- Uid of the user who is away
+  user_uid INTEGER NOT NULL,
+  -- Date or inclusive date range of the absence, such as 2026-08-10..2026-08-16
+  dates TEXT NOT NULL,
+  -- Whether the user keeps their turn until they return, instead of losing it
+  defer_turn BOOLEAN NOT NULL DEFAULT 0
+)
@@ -0,0 +1,9 @@
+-- Assignees of past occurrences of reminders, for rotations based on history
+CREATE TABLE assignment_history (
+  reminder_uid INTEGER NOT NULL,
+  -- RFC 3339 datetime of the occurrence in UTC
+  occurrence TEXT NOT NULL,
+  -- JSON array of integer user uids, in order of assignment
+  assignees TEXT NOT NULL,
+  PRIMARY KEY (reminder_uid, occurrence)
+)
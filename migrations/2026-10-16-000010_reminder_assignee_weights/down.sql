@@ -0,0 +1,4 @@
+UPDATE reminders SET assignees = (
+  SELECT json_group_array(json_extract(value, '$.uid'))
+  FROM (SELECT value FROM json_each(reminders.assignees) ORDER BY key)
+)
@@ -0,0 +1,5 @@
+-- Assignees of a schedule as objects with a uid and weight, instead of plain uids
+UPDATE reminders SET assignees = (
+  SELECT json_group_array(json_object('uid', value, 'weight', 1))
+  FROM (SELECT value FROM json_each(reminders.assignees) ORDER BY key)
+)
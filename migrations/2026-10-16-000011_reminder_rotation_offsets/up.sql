@@ -0,0 +1,2 @@
+-- Number of turns the rotation is moved along by, to keep it in place when a schedule is edited
+ALTER TABLE reminders ADD COLUMN rotation_offset INTEGER NOT NULL DEFAULT 0
@@ -0,0 +1 @@
+ALTER TABLE reminders DROP COLUMN handoff_notice
\ No newline at end of file
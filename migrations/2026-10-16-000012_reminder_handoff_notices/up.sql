@@ -0,0 +1,2 @@
+-- Minutes before a change of assignees that it is announced, or null when it isn't announced
+ALTER TABLE reminders ADD COLUMN handoff_notice INTEGER
\ No newline at end of file
@@ -0,0 +1,4 @@
+DROP TRIGGER reminders_delete_changes;
+DROP TRIGGER reminders_update_changes;
+DROP TRIGGER reminders_insert_changes;
+DROP TABLE reminder_changes
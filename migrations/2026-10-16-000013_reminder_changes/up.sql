@@ -0,0 +1,22 @@
+-- Number of times reminders were added, updated or removed, so running schedulers can pick up changes
+CREATE TABLE reminder_changes (
+  id INTEGER PRIMARY KEY NOT NULL CHECK (id = 0),
+  changes INTEGER NOT NULL
+);
+
+INSERT INTO reminder_changes (id, changes) VALUES (0, 0);
+
+CREATE TRIGGER reminders_insert_changes AFTER INSERT ON reminders
+BEGIN
+  UPDATE reminder_changes SET changes = changes + 1;
+END;
+
+CREATE TRIGGER reminders_update_changes AFTER UPDATE ON reminders
+BEGIN
+  UPDATE reminder_changes SET changes = changes + 1;
+END;
+
+CREATE TRIGGER reminders_delete_changes AFTER DELETE ON reminders
+BEGIN
+  UPDATE reminder_changes SET changes = changes + 1;
+END
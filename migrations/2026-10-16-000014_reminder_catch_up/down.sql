@@ -0,0 +1,3 @@
+DROP TABLE last_occurrences;
+
+ALTER TABLE reminders DROP COLUMN catch_up
\ No newline at end of file
@@ -0,0 +1,9 @@
+-- How occurrences missed while the scheduler wasn't running are handled, as all, latest or skip
+ALTER TABLE reminders ADD COLUMN catch_up TEXT NOT NULL DEFAULT 'skip';
+
+-- Most recent occurrence of each reminder which the scheduler fired
+CREATE TABLE last_occurrences (
+  reminder_uid INTEGER PRIMARY KEY NOT NULL,
+  -- RFC 3339 datetime of the occurrence in UTC
+  occurrence TEXT NOT NULL
+)
\ No newline at end of file
@@ -0,0 +1 @@
+ALTER TABLE reminders DROP COLUMN created
\ No newline at end of file
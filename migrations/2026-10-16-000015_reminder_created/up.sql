@@ -0,0 +1,2 @@
+-- RFC 3339 datetime in UTC when the reminder was created, before which it never occurred
+ALTER TABLE reminders ADD COLUMN created TEXT;
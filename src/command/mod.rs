@@ -3,8 +3,15 @@
 mod reminder;
 mod user;
 
-use crate::{Integrations, Providers, Scheduler};
+use crate::schedule::exclusions::DATE_FORMAT;
+use crate::{config, Config, Integrations, Providers, Scheduler};
+use std::time::Duration;
 use structopt::StructOpt;
+use time::Date;
+use tokio::signal::unix::{signal, Signal, SignalKind};
+
+/// Interval at which a running scheduler checks for changes to reminders
+const RELOAD_INTERVAL: Duration = Duration::from_secs(1);
 
 /// Interface for executable CLI commands
 pub trait Command {
@@ -28,7 +35,15 @@ pub enum Global {
     #[structopt(external_subcommand)]
     Integration(Vec<String>),
     /// Start the scheduler
-    Start,
+    ///
+    /// The scheduler stops on SIGINT or SIGTERM once any notifications being sent are finished,
+    /// and loads the config and reminders again on SIGHUP
+    Start {
+        /// Loads the reminders once instead of picking up changes to them, stopping when no
+        /// reminders are left to schedule
+        #[structopt(long)]
+        no_reload: bool,
+    },
 }
 
 impl Command for Global {
@@ -37,16 +52,60 @@ impl Command for Global {
             Self::User(command) => command.execute(providers),
             Self::Reminder(command) => command.execute(providers),
             // These commands are handled by the async `execute` function
-            Self::Start | Self::Integration(_) => Ok(String::from("")),
+            Self::Start { .. } | Self::Integration(_) => Ok(String::from("")),
+        }
+    }
+}
+
+/// Signals handled while the scheduler is running
+enum Signalled {
+    /// The config and reminders should be loaded again
+    Reload,
+    /// The scheduler should stop
+    Shutdown,
+}
+
+/// Signals which the scheduler listens for while running
+struct Signals {
+    /// SIGHUP, for loading the config and reminders again
+    hangup: Signal,
+    /// SIGINT, for stopping
+    interrupt: Signal,
+    /// SIGTERM, for stopping
+    terminate: Signal,
+}
+
+impl Signals {
+    /// Starts listening for signals
+    fn new() -> std::io::Result<Self> {
+        Ok(Self {
+            hangup: signal(SignalKind::hangup())?,
+            interrupt: signal(SignalKind::interrupt())?,
+            terminate: signal(SignalKind::terminate())?,
+        })
+    }
+
+    /// Waits for the next signal
+    async fn recv(&mut self) -> Signalled {
+        tokio::select! {
+            _ = self.hangup.recv() => Signalled::Reload,
+            _ = self.interrupt.recv() => Signalled::Shutdown,
+            _ = self.terminate.recv() => Signalled::Shutdown,
         }
     }
 }
 
+/// Parses a date such as 2026-10-31
+fn parse_date(value: &str) -> Result<Date, time::error::Parse> {
+    Date::parse(value, DATE_FORMAT)
+}
+
 /// Executes the given global command
 ///
 /// # Errors
 ///
-/// If the scheduler is started and a reminder is triggered when the queue is empty
+/// If the scheduler is started and a reminder is triggered when the queue is empty,
+/// or signals can't be listened for
 pub async fn execute(
     command: Global,
     providers: Providers<'_>,
@@ -54,11 +113,26 @@ pub async fn execute(
 ) -> Result<String, Box<dyn std::error::Error>> {
     match command {
         // Start the scheduler if requested
-        Global::Start => {
+        Global::Start { no_reload } => {
             let mut scheduler =
                 Scheduler::new(providers.reminder.get_all()?, providers, integrations);
-            scheduler.run().await?;
-            Ok(String::from("Scheduler queue is empty"))
+
+            if !no_reload {
+                scheduler = scheduler.with_reload_interval(RELOAD_INTERVAL);
+            }
+
+            let mut signals = Signals::new()?;
+
+            loop {
+                match scheduler.run_until(signals.recv()).await? {
+                    Some(Signalled::Reload) => match Config::load(config::NAME) {
+                        Ok(config) => scheduler.reload_with(Integrations::new(&config))?,
+                        Err(error) => eprintln!("Config could not be reloaded: {}", error),
+                    },
+                    Some(Signalled::Shutdown) => return Ok(String::from("Scheduler stopped")),
+                    None => return Ok(String::from("Scheduler queue is empty")),
+                }
+            }
         }
         // Find the specific integration if possible, and pass execution on to it
         Global::Integration(mut arguments) => {
@@ -86,7 +160,10 @@ mod tests {
             reminder: &crate::reminder::provider::MockProvidable::new(),
             integration: &crate::integration::provider::MockProvidable::new(),
         };
-        assert_eq!(String::from(""), Global::Start.execute(providers)?);
+        assert_eq!(
+            String::from(""),
+            Global::Start { no_reload: false }.execute(providers)?
+        );
 
         Ok(())
     }
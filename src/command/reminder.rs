@@ -1,97 +1,263 @@
 //! CLI interface commands for reminder management
 
-use super::{Command, Providers};
-use crate::reminder::model::{NewReminder, UpdatedReminder};
+use super::{parse_date, Command, Providers};
+use crate::reminder::model::{AssignmentOverride, NewReminder, Status, UpdatedReminder};
+use crate::schedule::availability::Substitution;
+use crate::schedule::model::{History, Overrides, Recurrence, Weights};
+use crate::schedule::rotation_strategy::SeededRandom;
+use crate::schedule::{
+    shorthand::parse_weekly_times, Assignee, Availability, CatchUp, CronExpression, DateRange,
+    Exclusions, Notice, RecurrenceRule, Rotation, Timezone,
+};
 use crate::Schedule;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::num::NonZeroU32;
 use structopt::StructOpt;
-use time::{OffsetDateTime, Weekday};
+use time::format_description::{self, well_known::Rfc3339};
+use time::{Date, Duration, OffsetDateTime, Weekday};
 
 #[derive(StructOpt)]
 /// Commands for reminder management
 pub enum Reminder {
     /// Adds a new reminder
-    Add {
-        /// Name for the reminder
-        name: String,
-        /// Schedule for the reminder, as a JSON object of weekday to times.
-        ///
-        /// For example, a schedule of Monday at 10:30 and 22:30, and Wednesday at 12:30 would be:
-        ///     {"mon":["10:30:00","22:30:00"],"wed":["12:30:00"]}
-        schedule: String,
-        /// List of assigned user uids, in order of assignment
-        assignees: Vec<i32>,
-    },
+    Add(Add),
     /// Updates an existing reminder
-    Update {
-        /// Uid of the reminder to update
-        uid: i32,
-        /// Updated name for the reminder
-        #[structopt(short, long)]
-        name: Option<String>,
-        /// Updated schedule for the reminder
-        ///
-        /// For example, a schedule of Monday at 10:30 and 22:30, and Wednesday at 12:30 would be:
-        ///     {"mon":["10:30:00","22:30:00"],"wed":["12:30:00"]}
-        #[structopt(short, long)]
-        schedule: Option<String>,
-        /// Updated list of assigned user uids, in order of assignment
-        #[structopt(short, long)]
-        assignees: Option<Vec<i32>>,
-    },
-    /// Lists all reminders as a JSON array
+    Update(Update),
+    /// Assigns a user to one occurrence of a reminder in place of its assignee, optionally
+    /// trading it for one of the user's own occurrences
+    Swap(Swap),
+    /// Lists all reminders as a JSON array, including whether they're active or completed
     List,
     /// Removes a reminder by its uid
     Remove {
         /// Uid of the reminder to remove
         uid: i32,
     },
+    /// Lists the upcoming occurrences of one or all reminders with the names of their assignees
+    /// and who is covering for assignees who are away, as a JSON array or a table
+    Upcoming(Upcoming),
+}
+
+#[derive(StructOpt)]
+/// Options for swapping the assignee of an occurrence
+pub struct Swap {
+    /// Uid of the reminder
+    uid: i32,
+    /// Occurrence to reassign, as an RFC 3339 datetime such as 2026-10-20T19:00:00+02:00,
+    /// or as a date such as 2026-10-20 for the first occurrence on that date
+    when: String,
+    /// Uid of the user taking the occurrence
+    user: i32,
+    /// Uid of the assignee being replaced, when the occurrence has several assignees
+    #[structopt(short, long)]
+    replacing: Option<i32>,
+    /// Occurrence assigned to the user which the replaced assignee takes in return,
+    /// written in the same way as the occurrence being reassigned
+    #[structopt(long)]
+    trade: Option<String>,
+}
+
+#[derive(StructOpt)]
+/// Options for listing upcoming occurrences
+pub struct Upcoming {
+    /// Uid of the reminder to list, or all active reminders when omitted
+    uid: Option<i32>,
+    /// Number of days ahead to list occurrences for, which is 14 unless a count is given
+    #[structopt(short, long)]
+    days: Option<u32>,
+    /// Maximum number of occurrences to list
+    #[structopt(short, long)]
+    count: Option<usize>,
+    /// Prints the occurrences as a table instead of JSON
+    #[structopt(long)]
+    table: bool,
+}
+
+/// Upcoming occurrence of a reminder with the names of its assignees
+#[derive(Serialize)]
+struct UpcomingOccurrence {
+    /// Uid of the reminder
+    reminder_uid: i32,
+    /// Name of the reminder
+    reminder: String,
+    /// Scheduled time, in the reminder's time zone
+    #[serde(with = "crate::schedule::rfc3339")]
+    time: OffsetDateTime,
+    /// Assignees for the occurrence, including anyone covering for an assignee who is away
+    assignees: Vec<NamedAssignee>,
+    /// Scheduled assignees who are away, and who is covering for them
+    substitutions: Vec<Substitution>,
+    /// Whether the assignees were chosen by hand instead of by the rotation
+    #[serde(rename = "override")]
+    is_override: bool,
+}
+
+/// Assignee of an upcoming occurrence
+#[derive(Serialize)]
+struct NamedAssignee {
+    /// Uid of the user
+    uid: i32,
+    /// Name of the user, if they still exist
+    name: Option<String>,
+}
+
+#[derive(StructOpt)]
+/// Options for adding a new reminder
+pub struct Add {
+    /// Name for the reminder
+    name: String,
+    /// Schedule for the reminder, as a JSON object of weekday or day of the month to times.
+    ///
+    /// For example, a schedule of Monday at 10:30 and 22:30, and Wednesday at 12:30 would be:
+    ///     {"mon":["10:30:00","22:30:00"],"wed":["12:30:00"]}
+    ///
+    /// Weekly schedules may also be written as shorthand, so the same schedule would be:
+    ///     mon 10:30 and 22:30; wed 12:30
+    ///
+    /// Shorthand days may be weekday names, ranges such as mon-fri, weekdays, weekends or daily,
+    /// such as "weekdays at 08:00" or "daily 9pm"
+    ///
+    /// A schedule of the 1st of the month and the last Friday of the month at 09:00 would be:
+    ///     {"1":["09:00:00"],"last friday":["09:00:00"]}
+    ///
+    /// An iCalendar recurrence rule may be given instead, such as every other Monday and
+    /// Thursday at 09:00:
+    ///     FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH;BYHOUR=9
+    ///
+    /// A 5 or 6-field cron expression may also be given, such as weekdays at 07:30:
+    ///     30 7 * * 1-5
+    schedule: String,
+    /// List of assigned user uids, in order of assignment. Uids may be given a weight for a
+    /// larger share of the occurrences, such as 1:2 for twice as many turns as everyone else
+    assignees: Vec<Assignee>,
+    /// IANA time zone for the scheduled times, such as Europe/Berlin
+    #[structopt(short, long, default_value = "UTC")]
+    timezone: String,
+    /// Number of weeks between each week the schedule occurs, such as 2 for every other week
+    #[structopt(short, long, default_value = "1")]
+    week_interval: NonZeroU32,
+    /// Date or date range to skip, such as 2026-12-25 or 2026-08-10..2026-08-16
+    #[structopt(short, long = "exclude", number_of_values = 1)]
+    exclusions: Vec<DateRange>,
+    /// Counts skipped times towards the assignee rotation instead of keeping the assignee
+    #[structopt(long)]
+    advance_rotation: bool,
+    /// Last date on which the reminder occurs, such as 2026-10-31
+    #[structopt(short, long, parse(try_from_str = parse_date))]
+    until: Option<Date>,
+    /// Number of times the reminder occurs before it ends
+    #[structopt(short, long = "count")]
+    occurrence_limit: Option<NonZeroU32>,
+    /// Number of assignees for each occurrence, such as 2 for chores needing two people
+    #[structopt(short = "k", long, default_value = "1")]
+    assignees_per_occurrence: NonZeroU32,
+    /// How the assignees rotate: round-robin on every occurrence, per-week, per-day, fixed,
+    /// random for a shuffled order which can be repeated with random:<seed>, or fair for
+    /// whoever was assigned least in the last 28 days, or in the last <days> with fair:<days>
+    #[structopt(short, long, default_value = "round-robin", parse(try_from_str = parse_rotation))]
+    rotation: Rotation,
+    /// Announces each change of assignees this long before their first occurrence, such as 12h
+    /// or 2d, so the incoming assignees get a heads-up
+    #[structopt(long)]
+    handoff_notice: Option<Notice>,
+    /// How occurrences missed while the scheduler wasn't running are notified once it starts
+    /// again: all of them, only the latest, or skip them
+    #[structopt(long, default_value = "skip")]
+    catch_up: CatchUp,
+}
+
+#[derive(StructOpt)]
+#[allow(clippy::struct_excessive_bools)] // Each flag is an independent command-line switch
+/// Options for updating an existing reminder
+pub struct Update {
+    /// Uid of the reminder to update
+    uid: i32,
+    /// Updated name for the reminder
+    #[structopt(short, long)]
+    name: Option<String>,
+    /// Updated schedule for the reminder
+    ///
+    /// For example, a schedule of Monday at 10:30 and 22:30, and Wednesday at 12:30 would be:
+    ///     {"mon":["10:30:00","22:30:00"],"wed":["12:30:00"]}
+    ///
+    /// Shorthand such as "mon 10:30 and 22:30; wed 12:30", an iCalendar recurrence rule such as
+    /// FREQ=DAILY;BYHOUR=9, or a cron expression such as 30 7 * * 1-5, may also be given
+    #[structopt(short, long)]
+    schedule: Option<String>,
+    /// Updated list of assigned user uids, in order of assignment, with optional weights
+    #[structopt(short, long)]
+    assignees: Option<Vec<Assignee>>,
+    /// Updated IANA time zone for the scheduled times, such as Europe/Berlin
+    #[structopt(short, long)]
+    timezone: Option<String>,
+    /// Updated number of weeks between each week the schedule occurs
+    #[structopt(short, long)]
+    week_interval: Option<NonZeroU32>,
+    /// Updated dates or date ranges to skip, replacing any existing ones
+    #[structopt(short, long = "exclude", number_of_values = 1)]
+    exclusions: Option<Vec<DateRange>>,
+    /// Removes all dates and date ranges to skip
+    #[structopt(long, conflicts_with = "exclusions")]
+    clear_exclusions: bool,
+    /// Updated setting for whether skipped times count towards the assignee rotation
+    #[structopt(long)]
+    advance_rotation: Option<bool>,
+    /// Updated last date on which the reminder occurs
+    #[structopt(short, long, parse(try_from_str = parse_date))]
+    until: Option<Date>,
+    /// Updated number of times the reminder occurs before it ends
+    #[structopt(short, long = "count")]
+    occurrence_limit: Option<NonZeroU32>,
+    /// Removes the end date and occurrence limit, so the reminder occurs indefinitely
+    #[structopt(long, conflicts_with_all = &["until", "occurrence-limit"])]
+    clear_end: bool,
+    /// Updated number of assignees for each occurrence
+    #[structopt(short = "k", long)]
+    assignees_per_occurrence: Option<NonZeroU32>,
+    /// Updated rotation of the assignees, such as per-week or random
+    #[structopt(short, long, parse(try_from_str = parse_rotation))]
+    rotation: Option<Rotation>,
+    /// Restarts the rotation from this week, instead of keeping whoever is up next
+    #[structopt(long)]
+    reset_rotation: bool,
+    /// Updated length of time before a change of assignees that it is announced
+    #[structopt(long)]
+    handoff_notice: Option<Notice>,
+    /// Stops announcing changes of assignees
+    #[structopt(long, conflicts_with = "handoff-notice")]
+    clear_handoff_notice: bool,
+    /// Updated handling of occurrences missed while the scheduler wasn't running, such as latest
+    #[structopt(long)]
+    catch_up: Option<CatchUp>,
 }
 
 impl Command for Reminder {
     fn execute(self, providers: Providers) -> Result<String, Box<dyn std::error::Error>> {
         match self {
-            Self::Add {
-                name,
-                schedule,
-                assignees,
-            } => {
-                let schedule = Schedule::new(
-                    serde_json::from_str(&schedule)?,
-                    get_start_of_this_week(),
-                    assignees,
-                );
-                let new_reminder = providers.reminder.add(NewReminder { name, schedule })?;
-                Ok(serde_json::to_string_pretty(&new_reminder)?)
+            Self::Add(command) => command.execute(providers),
+            Self::Update(command) => command.execute(providers),
+            Self::Swap(command) => command.execute(providers),
+            Self::Upcoming(command) => command.execute(providers),
+            Self::List => {
+                let now = OffsetDateTime::now_utc();
+
+                // Reminders which ended while the scheduler wasn't running are also listed as ended
+                let reminders: Vec<_> = providers
+                    .reminder
+                    .get_all()?
+                    .into_iter()
+                    .map(|mut reminder| {
+                        if reminder.status == Status::Active {
+                            reminder.status = Status::at(&reminder.schedule, now);
+                        }
+                        reminder
+                    })
+                    .collect();
+
+                Ok(serde_json::to_string_pretty(&reminders)?)
             }
-            Self::List => Ok(serde_json::to_string_pretty(
-                &providers.reminder.get_all()?,
-            )?),
-            Self::Update {
-                uid,
-                name,
-                schedule,
-                assignees,
-            } => match providers.reminder.get_by_uid(uid) {
-                Ok(reminder) => {
-                    let schedule = Schedule::new(
-                        schedule.map_or(Ok(reminder.schedule.weekly_times), |schedule| {
-                            serde_json::from_str(&schedule)
-                        })?,
-                        get_start_of_this_week(),
-                        assignees.unwrap_or(reminder.schedule.assignees),
-                    );
-
-                    let updated_reminder = UpdatedReminder {
-                        uid,
-                        schedule,
-                        name: name.unwrap_or(reminder.name),
-                    };
-
-                    let reminder = providers.reminder.update(updated_reminder)?;
-                    Ok(serde_json::to_string_pretty(&reminder)?)
-                }
-                Err(_) => Err(format!("Invalid uid {}", uid).into()),
-            },
             Self::Remove { uid } => match providers.reminder.get_by_uid(uid) {
                 Ok(reminder) => {
                     providers.reminder.remove(uid)?;
@@ -103,6 +269,460 @@ impl Command for Reminder {
     }
 }
 
+impl Command for Add {
+    fn execute(self, providers: Providers) -> Result<String, Box<dyn std::error::Error>> {
+        let (assignees, weights) = split_weights(&self.assignees);
+        let mut schedule = Schedule::from_recurrence(
+            parse_recurrence(&self.schedule)?,
+            get_start_of_this_week(),
+            assignees,
+        )
+        .with_created(OffsetDateTime::now_utc())
+        .with_weights(weights)
+        .with_timezone(Timezone::new(&self.timezone)?)
+        .with_week_interval(self.week_interval)
+        .with_exclusions(Exclusions {
+            dates: self.exclusions,
+            advance_rotation: self.advance_rotation,
+        })
+        .with_assignees_per_occurrence(self.assignees_per_occurrence)
+        .with_rotation(self.rotation)
+        .with_catch_up(self.catch_up);
+
+        if let Some(until) = self.until {
+            schedule = schedule.with_until(until);
+        }
+
+        if let Some(occurrence_limit) = self.occurrence_limit {
+            schedule = schedule.with_occurrence_limit(occurrence_limit);
+        }
+
+        if let Some(handoff_notice) = self.handoff_notice {
+            schedule = schedule.with_handoff_notice(handoff_notice);
+        }
+
+        let new_reminder = providers.reminder.add(NewReminder {
+            name: self.name,
+            schedule,
+        })?;
+
+        Ok(serde_json::to_string_pretty(&new_reminder)?)
+    }
+}
+
+impl Command for Update {
+    fn execute(self, providers: Providers) -> Result<String, Box<dyn std::error::Error>> {
+        let reminder = match providers.reminder.get_by_uid(self.uid) {
+            Ok(reminder) => reminder,
+            Err(_) => return Err(format!("Invalid uid {}", self.uid).into()),
+        };
+
+        let (assignees, weights) = split_weights(
+            &self
+                .assignees
+                .unwrap_or_else(|| reminder.schedule.weighted_assignees()),
+        );
+        let mut schedule = Schedule::from_recurrence(
+            self.schedule
+                .map_or(Ok(reminder.schedule.recurrence.clone()), |schedule| {
+                    parse_recurrence(&schedule)
+                })?,
+            if self.reset_rotation {
+                get_start_of_this_week()
+            } else {
+                reminder.schedule.start_date
+            },
+            assignees,
+        )
+        .with_weights(weights)
+        .with_timezone(match self.timezone {
+            Some(timezone) => Timezone::new(&timezone)?,
+            None => reminder.schedule.timezone.clone(),
+        })
+        .with_exclusions(Exclusions {
+            dates: match self.exclusions {
+                _ if self.clear_exclusions => Vec::new(),
+                Some(exclusions) => exclusions,
+                None => reminder.schedule.exclusions.dates.clone(),
+            },
+            advance_rotation: self
+                .advance_rotation
+                .unwrap_or(reminder.schedule.exclusions.advance_rotation),
+        });
+
+        schedule.week_interval = self
+            .week_interval
+            .map_or(reminder.schedule.week_interval, NonZeroU32::get);
+        schedule.assignees_per_occurrence = self
+            .assignees_per_occurrence
+            .map_or(reminder.schedule.assignees_per_occurrence, NonZeroU32::get);
+        schedule.rotation = self.rotation.unwrap_or(reminder.schedule.rotation);
+        schedule.catch_up = self.catch_up.unwrap_or(reminder.schedule.catch_up);
+        schedule.created = reminder.schedule.created;
+
+        if !self.clear_handoff_notice {
+            schedule.handoff_notice = self.handoff_notice.or(reminder.schedule.handoff_notice);
+        }
+
+        if !self.clear_end {
+            schedule.until = self.until.or(reminder.schedule.until);
+            schedule.occurrence_limit = self
+                .occurrence_limit
+                .map(NonZeroU32::get)
+                .or(reminder.schedule.occurrence_limit);
+        }
+
+        // Keep whoever is up next, unless the rotation is being restarted
+        let now = OffsetDateTime::now_utc();
+        if !self.reset_rotation {
+            schedule = schedule.with_rotation_continued_from(&reminder.schedule, now);
+        }
+
+        // Reminders which ended become active again when they have occurrences left
+        let updated_reminder = UpdatedReminder {
+            uid: self.uid,
+            status: Status::at(&schedule, now),
+            schedule,
+            name: self.name.unwrap_or(reminder.name),
+        };
+
+        let reminder = providers.reminder.update(updated_reminder)?;
+        Ok(serde_json::to_string_pretty(&reminder)?)
+    }
+}
+
+impl Command for Swap {
+    fn execute(self, providers: Providers) -> Result<String, Box<dyn std::error::Error>> {
+        let reminder = match providers.reminder.get_by_uid(self.uid) {
+            Ok(reminder) => reminder,
+            Err(_) => return Err(format!("Invalid uid {}", self.uid).into()),
+        };
+
+        if providers.user.get_by_uid(self.user).is_err() {
+            return Err(format!("Invalid user uid {}", self.user).into());
+        }
+
+        let (availability, overrides, history) = get_adjustments(&providers, self.uid)?;
+        let schedule = &reminder.schedule;
+        let now = OffsetDateTime::now_utc();
+
+        // Upcoming occurrences are assigned in turn, so fair rotations account for the
+        // occurrences leading up to the swapped one
+        let get_assignees = |time: OffsetDateTime| {
+            schedule
+                .assignments(time.min(now), &availability, &overrides, history.clone())
+                .find(|(next_time, _)| *next_time == time)
+                .map(|(_, assignment)| assignment.assignees)
+                .unwrap_or_default()
+        };
+
+        let time = find_occurrence(schedule, &self.when)?;
+        let mut assignees = get_assignees(time);
+
+        if assignees.contains(&self.user) {
+            return Err(format!("User {} is already assigned to {}", self.user, self.when).into());
+        }
+
+        let replaced = self
+            .replacing
+            .or_else(|| assignees.first().copied())
+            .ok_or_else(|| format!("Occurrence {} has no assignee to replace", self.when))?;
+        let position = assignees
+            .iter()
+            .position(|assignee| *assignee == replaced)
+            .ok_or_else(|| format!("User {} is not assigned to {}", replaced, self.when))?;
+        assignees[position] = self.user;
+
+        let mut assignment_overrides = vec![AssignmentOverride {
+            reminder_uid: self.uid,
+            occurrence: time,
+            assignees,
+        }];
+
+        // The replaced assignee takes one of the user's occurrences in return
+        if let Some(trade) = self.trade {
+            let time = find_occurrence(schedule, &trade)?;
+            let mut assignees = get_assignees(time);
+
+            if assignees.contains(&replaced) {
+                return Err(format!("User {} is already assigned to {}", replaced, trade).into());
+            }
+
+            let position = assignees
+                .iter()
+                .position(|assignee| *assignee == self.user)
+                .ok_or_else(|| format!("User {} is not assigned to {}", self.user, trade))?;
+            assignees[position] = replaced;
+
+            assignment_overrides.push(AssignmentOverride {
+                reminder_uid: self.uid,
+                occurrence: time,
+                assignees,
+            });
+        }
+
+        providers
+            .reminder
+            .set_overrides(assignment_overrides.clone())?;
+
+        Ok(serde_json::to_string_pretty(&assignment_overrides)?)
+    }
+}
+
+impl Command for Upcoming {
+    fn execute(self, providers: Providers) -> Result<String, Box<dyn std::error::Error>> {
+        let reminders = match self.uid {
+            Some(uid) => match providers.reminder.get_by_uid(uid) {
+                Ok(reminder) => vec![reminder],
+                Err(_) => return Err(format!("Invalid uid {}", uid).into()),
+            },
+            None => providers
+                .reminder
+                .get_all()?
+                .into_iter()
+                .filter(|reminder| reminder.status == Status::Active)
+                .collect(),
+        };
+
+        let names: HashMap<i32, String> = providers
+            .user
+            .get_all()?
+            .into_iter()
+            .map(|user| (user.uid, user.name))
+            .collect();
+
+        let now = OffsetDateTime::now_utc();
+        let days = match (self.days, self.count) {
+            (None, None) => Some(14),
+            (days, _) => days,
+        };
+        let end = days.map(|days| now + Duration::days(i64::from(days)));
+        let count = self.count.unwrap_or(usize::MAX);
+
+        let mut occurrences = Vec::new();
+
+        for reminder in reminders {
+            let (availability, overrides, history) = get_adjustments(&providers, reminder.uid)?;
+            let schedule = &reminder.schedule;
+
+            occurrences.extend(
+                schedule
+                    .assignments(now, &availability, &overrides, history)
+                    .take_while(|(time, _)| end.map_or(true, |end| *time < end))
+                    .take(count)
+                    .map(|(time, assignment)| UpcomingOccurrence {
+                        reminder_uid: reminder.uid,
+                        reminder: reminder.name.clone(),
+                        time: schedule.timezone.to_local(time),
+                        assignees: assignment
+                            .assignees
+                            .into_iter()
+                            .map(|uid| NamedAssignee {
+                                uid,
+                                name: names.get(&uid).cloned(),
+                            })
+                            .collect(),
+                        substitutions: assignment.substitutions,
+                        is_override: assignment.is_override,
+                    }),
+            );
+        }
+
+        // Occurrences of all reminders are listed in order, earliest first
+        occurrences.sort_by_key(|occurrence| (occurrence.time, occurrence.reminder_uid));
+        occurrences.truncate(count);
+
+        if self.table {
+            Ok(format_upcoming_table(&occurrences, &names)?)
+        } else {
+            Ok(serde_json::to_string_pretty(&occurrences)?)
+        }
+    }
+}
+
+/// Formats upcoming occurrences as a table of times, reminders and assignee names, noting who is
+/// covering for an assignee who is away and which assignees were chosen by hand
+fn format_upcoming_table(
+    occurrences: &[UpcomingOccurrence],
+    names: &HashMap<i32, String>,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let time_format = format_description::parse(
+        "[year]-[month]-[day] [hour]:[minute] [offset_hour sign:mandatory]:[offset_minute]",
+    )?;
+
+    let mut rows = vec![[
+        String::from("Time"),
+        String::from("Reminder"),
+        String::from("Assignees"),
+    ]];
+
+    for occurrence in occurrences {
+        let assignees: Vec<_> = occurrence
+            .assignees
+            .iter()
+            .map(|assignee| {
+                let name = get_name(names, assignee.uid);
+                match occurrence
+                    .substitutions
+                    .iter()
+                    .find(|substitution| substitution.substitute == assignee.uid)
+                {
+                    Some(substitution) => format!(
+                        "{} (covering for {})",
+                        name,
+                        get_name(names, substitution.absent)
+                    ),
+                    None => name,
+                }
+            })
+            .collect();
+
+        let mut assignees = assignees.join(", ");
+        if occurrence.is_override {
+            assignees.push_str(" (swapped)");
+        }
+
+        rows.push([
+            occurrence.time.format(&time_format)?,
+            occurrence.reminder.clone(),
+            assignees,
+        ]);
+    }
+
+    let widths: Vec<_> = (0..2)
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    Ok(rows
+        .iter()
+        .map(|row| {
+            format!(
+                "{:time$}  {:reminder$}  {}",
+                row[0],
+                row[1],
+                row[2],
+                time = widths[0],
+                reminder = widths[1]
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+/// Gets the name of a user, or their uid if they no longer exist
+fn get_name(names: &HashMap<i32, String>, uid: i32) -> String {
+    names
+        .get(&uid)
+        .cloned()
+        .unwrap_or_else(|| format!("#{}", uid))
+}
+
+/// Retrieves the absences of users and the overrides and history for a reminder, which adjust
+/// who is assigned to its occurrences
+fn get_adjustments(
+    providers: &Providers,
+    uid: i32,
+) -> Result<(Availability, Overrides, History), Box<dyn std::error::Error>> {
+    let availability = providers.user.get_absences()?.into_iter().collect();
+    let overrides = providers
+        .reminder
+        .get_overrides(uid)?
+        .into_iter()
+        .map(|assignment_override| {
+            (
+                assignment_override.occurrence,
+                assignment_override.assignees,
+            )
+        })
+        .collect();
+    let history = providers
+        .reminder
+        .get_history(uid)?
+        .into_iter()
+        .map(|past_assignment| (past_assignment.occurrence, past_assignment.assignees))
+        .collect();
+
+    Ok((availability, overrides, history))
+}
+
+/// Splits weighted assignees into their uids in order of assignment, and their weights
+fn split_weights(assignees: &[Assignee]) -> (Vec<i32>, Weights) {
+    (
+        assignees.iter().map(|assignee| assignee.uid).collect(),
+        assignees
+            .iter()
+            .map(|assignee| (assignee.uid, assignee.weight))
+            .collect(),
+    )
+}
+
+/// Finds the occurrence of a schedule at an RFC 3339 datetime, or the first occurrence on a
+/// date in the schedule's time zone
+fn find_occurrence(schedule: &Schedule, when: &str) -> Result<OffsetDateTime, String> {
+    let no_occurrence = || format!("The reminder does not occur at {}", when);
+
+    // Occurrences are found without their assignees, which the schedule may not have
+    let get_next_time = |time: OffsetDateTime| {
+        schedule
+            .get_next_duration(time)
+            .map(|duration| time + duration)
+    };
+
+    if let Ok(time) = OffsetDateTime::parse(when, &Rfc3339) {
+        return get_next_time(time)
+            .filter(|next_time| *next_time == time)
+            .ok_or_else(no_occurrence);
+    }
+
+    let date = parse_date(when).map_err(|_| {
+        format!(
+            "Invalid occurrence `{}`, should be a date or an RFC 3339 datetime",
+            when
+        )
+    })?;
+
+    get_next_time(schedule.timezone.from_local(date.midnight()))
+        .filter(|next_time| schedule.timezone.to_local(*next_time).date() == date)
+        .ok_or_else(no_occurrence)
+}
+
+/// Parses a rotation, seeding `random` rotations from the current time
+fn parse_rotation(value: &str) -> Result<Rotation, String> {
+    if value.trim().eq_ignore_ascii_case("random") {
+        let seed = u64::try_from(OffsetDateTime::now_utc().unix_timestamp_nanos()).unwrap_or(0);
+        return Ok(Rotation::SeededRandom(SeededRandom { seed }));
+    }
+
+    value.parse()
+}
+
+/// Parses a schedule as a JSON object, an iCalendar recurrence rule, a cron expression, or
+/// shorthand such as `weekdays at 08:00`
+fn parse_recurrence(value: &str) -> Result<Recurrence, Box<dyn std::error::Error>> {
+    let value = value.trim();
+
+    if value.starts_with('{') {
+        Ok(serde_json::from_str(value)?)
+    } else if value.contains('=') {
+        Ok(Recurrence::Rule {
+            rule: RecurrenceRule::try_from(value.to_string())?,
+        })
+    } else if value.starts_with(|character: char| {
+        character.is_ascii_digit() || character == '*' || character == '@'
+    }) {
+        Ok(Recurrence::Cron {
+            cron: CronExpression::try_from(value.to_string())?,
+        })
+    } else {
+        Ok(Recurrence::Weekly(parse_weekly_times(value)?))
+    }
+}
+
 /// Returns the start of the current week
 fn get_start_of_this_week() -> OffsetDateTime {
     let mut today = OffsetDateTime::now_utc().date();
@@ -122,6 +742,7 @@ mod tests {
     use super::*;
     use crate::reminder::{model, provider::MockProvidable};
     use mockall::predicate::eq;
+    use time::macros::{date, datetime};
 
     const SCHEDULE_ROADHOUSE: &str = r#"{"Monday":["21:00:00.0"]}"#;
     const SCHEDULE_253: &str = r#"{"Wednesday":["14:53:00.0"]}"#;
@@ -175,25 +796,46 @@ mod tests {
             uid: 1,
             name: String::from("Meet at Roadhouse"),
             schedule: get_roadhouse_schedule()?,
+            status: Status::Active,
         };
 
         let expected_output = serde_json::to_string_pretty(&reminder)?;
+        let expected_schedule = get_roadhouse_schedule()?;
+        let added = OffsetDateTime::now_utc();
 
+        // New reminders are created when they're added
         mock_reminder_provider
             .expect_add()
-            .with(eq(NewReminder {
-                name: String::from("Meet at Roadhouse"),
-                schedule: get_roadhouse_schedule()?,
-            }))
+            .withf(move |new_reminder| {
+                new_reminder.schedule.created.map_or(false, |created| {
+                    created >= added
+                        && *new_reminder
+                            == NewReminder {
+                                name: String::from("Meet at Roadhouse"),
+                                schedule: expected_schedule.clone().with_created(created),
+                            }
+                })
+            })
             .times(1)
             .return_once(|_| Ok(reminder));
 
         let output = execute(
-            Reminder::Add {
+            Reminder::Add(Add {
                 name: String::from("Meet at Roadhouse"),
                 schedule: SCHEDULE_ROADHOUSE.to_string(),
-                assignees: ASSIGNEES_ROADHOUSE.to_vec(),
-            },
+                assignees: ASSIGNEES_ROADHOUSE.map(Assignee::from).to_vec(),
+                timezone: String::from("UTC"),
+                week_interval: NonZeroU32::new(1).ok_or("Invalid week interval")?,
+                exclusions: Vec::new(),
+                advance_rotation: false,
+                until: None,
+                occurrence_limit: None,
+                assignees_per_occurrence: NonZeroU32::new(1)
+                    .ok_or("Invalid assignees per occurrence")?,
+                rotation: Rotation::default(),
+                handoff_notice: None,
+                catch_up: CatchUp::Skip,
+            }),
             &mock_reminder_provider,
         )?;
 
@@ -210,11 +852,13 @@ mod tests {
                 uid: 1,
                 name: String::from("Meet at Roadhouse"),
                 schedule: get_roadhouse_schedule()?,
+                status: Status::Active,
             },
             model::Reminder {
                 uid: 2,
                 name: String::from("2:53"),
                 schedule: get_253_schedule()?,
+                status: Status::Active,
             },
         ];
 
@@ -240,11 +884,13 @@ mod tests {
             uid: 1,
             name: String::from("2:53"),
             schedule: get_253_schedule()?,
+            status: Status::Active,
         };
         let reminder = model::Reminder {
             uid: 1,
             name: String::from("2:54"),
             schedule: get_254_schedule()?,
+            status: Status::Active,
         };
 
         let expected_output = serde_json::to_string_pretty(&reminder)?;
@@ -261,17 +907,32 @@ mod tests {
                 uid: 1,
                 name: String::from("2:54"),
                 schedule: get_254_schedule()?,
+                status: Status::Active,
             }))
             .times(1)
             .return_once(|_| Ok(reminder));
 
         let output = execute(
-            Reminder::Update {
+            Reminder::Update(Update {
                 uid: 1,
                 name: Some(String::from("2:54")),
                 schedule: Some(SCHEDULE_254.to_string()),
-                assignees: Some(ASSIGNEES_254.to_vec()),
-            },
+                assignees: Some(ASSIGNEES_254.map(Assignee::from).to_vec()),
+                timezone: None,
+                week_interval: None,
+                exclusions: None,
+                clear_exclusions: false,
+                advance_rotation: None,
+                until: None,
+                occurrence_limit: None,
+                clear_end: false,
+                assignees_per_occurrence: None,
+                rotation: None,
+                reset_rotation: false,
+                handoff_notice: None,
+                clear_handoff_notice: false,
+                catch_up: None,
+            }),
             &mock_reminder_provider,
         )?;
 
@@ -280,6 +941,116 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn it_reactivates_finished_reminders_updated_to_occur_again(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut mock_reminder_provider = MockProvidable::new();
+
+        let finished_reminder = model::Reminder {
+            uid: 1,
+            name: String::from("Meet at Roadhouse"),
+            schedule: Schedule::from_recurrence(
+                Recurrence::Once {
+                    time: datetime!(2020-01-06 21:00 UTC),
+                },
+                get_start_of_this_week(),
+                ASSIGNEES_ROADHOUSE.to_vec(),
+            ),
+            status: Status::Finished,
+        };
+        let reminder = finished_reminder.clone();
+
+        mock_reminder_provider
+            .expect_get_by_uid()
+            .with(eq(1))
+            .times(1)
+            .return_once(|_| Ok(finished_reminder));
+
+        mock_reminder_provider
+            .expect_update()
+            .withf(|updated| updated.status == Status::Active)
+            .times(1)
+            .return_once(|_| Ok(reminder));
+
+        execute(
+            Reminder::Update(Update {
+                uid: 1,
+                name: None,
+                schedule: Some(String::from(r#"{"once":"2099-01-05T21:00:00Z"}"#)),
+                assignees: None,
+                timezone: None,
+                week_interval: None,
+                exclusions: None,
+                clear_exclusions: false,
+                advance_rotation: None,
+                until: None,
+                occurrence_limit: None,
+                clear_end: false,
+                assignees_per_occurrence: None,
+                rotation: None,
+                reset_rotation: false,
+                handoff_notice: None,
+                clear_handoff_notice: false,
+                catch_up: None,
+            }),
+            &mock_reminder_provider,
+        )?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_reactivates_completed_reminders_when_their_end_is_cleared(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut mock_reminder_provider = MockProvidable::new();
+
+        let completed_reminder = model::Reminder {
+            uid: 1,
+            name: String::from("Meet at Roadhouse"),
+            schedule: get_roadhouse_schedule()?.with_until(date!(2020 - 01 - 31)),
+            status: Status::Completed,
+        };
+        let reminder = completed_reminder.clone();
+
+        mock_reminder_provider
+            .expect_get_by_uid()
+            .with(eq(1))
+            .times(1)
+            .return_once(|_| Ok(completed_reminder));
+
+        mock_reminder_provider
+            .expect_update()
+            .withf(|updated| updated.status == Status::Active && updated.schedule.until.is_none())
+            .times(1)
+            .return_once(|_| Ok(reminder));
+
+        execute(
+            Reminder::Update(Update {
+                uid: 1,
+                name: None,
+                schedule: None,
+                assignees: None,
+                timezone: None,
+                week_interval: None,
+                exclusions: None,
+                clear_exclusions: false,
+                advance_rotation: None,
+                until: None,
+                occurrence_limit: None,
+                clear_end: true,
+                assignees_per_occurrence: None,
+                rotation: None,
+                reset_rotation: false,
+                handoff_notice: None,
+                clear_handoff_notice: false,
+                catch_up: None,
+            }),
+            &mock_reminder_provider,
+        )?;
+
+        Ok(())
+    }
+
     #[test]
     fn it_outputs_an_error_for_invalid_update_uid() -> Result<(), Box<dyn std::error::Error>> {
         let mut mock_reminder_provider = MockProvidable::new();
@@ -295,12 +1066,26 @@ mod tests {
             });
 
         let output = execute(
-            Reminder::Update {
+            Reminder::Update(Update {
                 uid: 1,
                 name: Some(String::from("2:53")),
                 schedule: Some(SCHEDULE_253.to_string()),
-                assignees: Some(ASSIGNEES_253.to_vec()),
-            },
+                assignees: Some(ASSIGNEES_253.map(Assignee::from).to_vec()),
+                timezone: None,
+                week_interval: None,
+                exclusions: None,
+                clear_exclusions: false,
+                advance_rotation: None,
+                until: None,
+                occurrence_limit: None,
+                clear_end: false,
+                assignees_per_occurrence: None,
+                rotation: None,
+                reset_rotation: false,
+                handoff_notice: None,
+                clear_handoff_notice: false,
+                catch_up: None,
+            }),
             &mock_reminder_provider,
         );
 
@@ -320,6 +1105,7 @@ mod tests {
             uid: 1,
             name: String::from("Meet at Roadhouse"),
             schedule: get_roadhouse_schedule()?,
+            status: Status::Active,
         };
 
         let expected_output = serde_json::to_string_pretty(&existing_reminder)?;
@@ -1,8 +1,11 @@
 //! CLI interface commands for user management
 
-use super::{Command, Providers};
-use crate::user::model::{NewUser, UpdatedUser};
+use super::{parse_date, Command, Providers};
+use crate::reminder::model::UpdatedReminder;
+use crate::schedule::DateRange;
+use crate::user::model::{NewAbsence, NewUser, UpdatedUser};
 use structopt::StructOpt;
+use time::{Date, OffsetDateTime};
 
 #[derive(StructOpt)]
 /// Commands for user management
@@ -23,9 +26,32 @@ pub enum User {
     /// Lists all users as a JSON array
     List,
     /// Removes a user by their uid
+    ///
+    /// Users who are assigned to reminders are only removed when they are reassigned or dropped
+    /// from those reminders
     Remove {
         /// Uid of the user to remove
         uid: i32,
+        /// Uid of the user taking over the removed user's turns
+        #[structopt(long)]
+        reassign: Option<i32>,
+        /// Removes the user from the assignees of their reminders
+        #[structopt(long, conflicts_with = "reassign")]
+        drop_from_reminders: bool,
+    },
+    /// Marks a user as away, so their turns are covered by the next available assignee
+    Away {
+        /// Uid of the user who is away
+        uid: i32,
+        /// First date on which the user is away, such as 2026-08-10
+        #[structopt(parse(try_from_str = parse_date))]
+        from: Date,
+        /// Last date on which the user is away, such as 2026-08-16
+        #[structopt(parse(try_from_str = parse_date))]
+        to: Date,
+        /// Keeps the user's turn until they return, instead of passing it on
+        #[structopt(long)]
+        defer: bool,
     },
 }
 
@@ -50,17 +76,108 @@ impl Command for User {
                 }
                 Err(_) => Err(format!("Invalid uid {}", uid).into()),
             },
-            Self::Remove { uid } => match providers.user.get_by_uid(uid) {
+            Self::Remove {
+                uid,
+                reassign,
+                drop_from_reminders,
+            } => match providers.user.get_by_uid(uid) {
                 Ok(user) => {
-                    providers.user.remove(uid)?;
+                    let unassigned = unassign(&providers, uid, reassign, drop_from_reminders)?;
+                    providers.user.remove(uid, unassigned)?;
                     Ok(serde_json::to_string_pretty(&user)?)
                 }
                 Err(_) => Err(format!("Invalid uid {}", uid).into()),
             },
+            Self::Away {
+                uid,
+                from,
+                to,
+                defer,
+            } => {
+                if providers.user.get_by_uid(uid).is_err() {
+                    return Err(format!("Invalid uid {}", uid).into());
+                }
+
+                if from > to {
+                    return Err(format!("Invalid dates, {} is after {}", from, to).into());
+                }
+
+                let absence = providers.user.add_absence(NewAbsence {
+                    user_uid: uid,
+                    dates: DateRange { from, to },
+                    defer_turn: defer,
+                })?;
+
+                Ok(serde_json::to_string_pretty(&absence)?)
+            }
         }
     }
 }
 
+/// Reassigns a user's turns to another user, or drops them from the assignees, for every
+/// reminder they're assigned to. The updated reminders are saved along with removing the user
+///
+/// Fails without a replacement or permission to drop them, listing the affected reminders
+fn unassign(
+    providers: &Providers,
+    uid: i32,
+    replacement: Option<i32>,
+    drop_from_reminders: bool,
+) -> Result<Vec<UpdatedReminder>, Box<dyn std::error::Error>> {
+    let reminders: Vec<_> = providers
+        .reminder
+        .get_all()?
+        .into_iter()
+        .filter(|reminder| reminder.schedule.assignees.contains(&uid))
+        .collect();
+
+    if reminders.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    if let Some(replacement) = replacement {
+        if replacement == uid || providers.user.get_by_uid(replacement).is_err() {
+            return Err(format!("Invalid uid {} to reassign to", replacement).into());
+        }
+    } else if !drop_from_reminders {
+        let names: Vec<_> = reminders
+            .iter()
+            .map(|reminder| format!("{} ({})", reminder.uid, reminder.name))
+            .collect();
+
+        return Err(format!(
+            "User {} is assigned to reminders {}, remove them with --reassign <uid> or --drop-from-reminders",
+            uid,
+            names.join(", ")
+        )
+        .into());
+    }
+
+    let now = OffsetDateTime::now_utc();
+    Ok(reminders
+        .into_iter()
+        .map(|reminder| {
+            let schedule = reminder
+                .schedule
+                .with_assignee_replaced(uid, replacement, now);
+
+            if schedule.assignees.is_empty() {
+                return Err(format!(
+                    "Reminder {} ({}) would have no assignees left, use --reassign <uid> instead",
+                    reminder.uid, reminder.name
+                ));
+            }
+
+            Ok(UpdatedReminder {
+                uid: reminder.uid,
+                name: reminder.name,
+                schedule,
+                status: reminder.status,
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -71,9 +188,14 @@ mod tests {
         command: User,
         user_provider: &MockProvidable,
     ) -> Result<String, Box<dyn std::error::Error>> {
+        let mut mock_reminder_provider = crate::reminder::provider::MockProvidable::new();
+        mock_reminder_provider
+            .expect_get_all()
+            .returning(|| Ok(Vec::new()));
+
         let providers = Providers {
             user: user_provider,
-            reminder: &crate::reminder::provider::MockProvidable::new(),
+            reminder: &mock_reminder_provider,
             integration: &crate::integration::provider::MockProvidable::new(),
         };
 
@@ -226,11 +348,67 @@ mod tests {
 
         mock_user_provider
             .expect_remove()
+            .withf(|uid, unassigned| *uid == 1 && unassigned.is_empty())
+            .times(1)
+            .return_once(|_, _| Ok(()));
+
+        let output = execute(
+            User::Remove {
+                uid: 1,
+                reassign: None,
+                drop_from_reminders: false,
+            },
+            &mock_user_provider,
+        )?;
+
+        assert_eq!(expected_output, output);
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_marks_existing_users_as_away() -> Result<(), Box<dyn std::error::Error>> {
+        let mut mock_user_provider = MockProvidable::new();
+
+        let absence = model::Absence {
+            uid: 1,
+            user_uid: 1,
+            dates: "2026-08-10..2026-08-16".parse()?,
+            defer_turn: false,
+        };
+
+        let expected_output = serde_json::to_string_pretty(&absence)?;
+
+        mock_user_provider
+            .expect_get_by_uid()
             .with(eq(1))
             .times(1)
-            .return_once(|_| Ok(()));
+            .return_once(|_| {
+                Ok(model::User {
+                    uid: 1,
+                    name: String::from("Leland"),
+                })
+            });
+
+        mock_user_provider
+            .expect_add_absence()
+            .with(eq(NewAbsence {
+                user_uid: 1,
+                dates: "2026-08-10..2026-08-16".parse()?,
+                defer_turn: false,
+            }))
+            .times(1)
+            .return_once(|_| Ok(absence));
 
-        let output = execute(User::Remove { uid: 1 }, &mock_user_provider)?;
+        let output = execute(
+            User::Away {
+                uid: 1,
+                from: Date::from_calendar_date(2026, time::Month::August, 10)?,
+                to: Date::from_calendar_date(2026, time::Month::August, 16)?,
+                defer: false,
+            },
+            &mock_user_provider,
+        )?;
 
         assert_eq!(expected_output, output);
 
@@ -251,7 +429,14 @@ mod tests {
                 })
             });
 
-        let output = execute(User::Remove { uid: 1 }, &mock_user_provider);
+        let output = execute(
+            User::Remove {
+                uid: 1,
+                reassign: None,
+                drop_from_reminders: false,
+            },
+            &mock_user_provider,
+        );
 
         match output {
             Ok(_) => panic!("Error was not propagated"),
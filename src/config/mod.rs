@@ -7,6 +7,9 @@ use error::Error;
 use serde::Deserialize;
 use std::collections::BTreeMap;
 
+/// Name of the config file loaded at startup, and again when the scheduler is signalled to reload
+pub const NAME: &str = "remembear";
+
 /// All configurable properties of the app
 #[derive(Debug, Deserialize, Eq, PartialEq)]
 pub struct Config {
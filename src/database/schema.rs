@@ -1,5 +1,43 @@
 //! Diesel-generated schemas for database tables
 
+table! {
+    /// Records for dates on which users are away
+    absences (uid) {
+        /// Unique identifier for the absence record
+        uid -> Integer,
+        /// Uid of the user who is away
+        user_uid -> Integer,
+        /// Date or inclusive date range of the absence, such as `2026-08-10..2026-08-16`
+        dates -> Text,
+        /// Whether the user keeps their turn until they return, instead of losing it
+        defer_turn -> Bool,
+    }
+}
+
+table! {
+    /// Records for the assignees of past occurrences of reminders
+    assignment_history (reminder_uid, occurrence) {
+        /// Uid of the reminder
+        reminder_uid -> Integer,
+        /// RFC 3339 datetime of the occurrence in UTC
+        occurrence -> Text,
+        /// JSON array of integer user uids, in order of assignment
+        assignees -> Text,
+    }
+}
+
+table! {
+    /// Records for the assignees of single occurrences of reminders, replacing the rotation
+    assignment_overrides (reminder_uid, occurrence) {
+        /// Uid of the reminder
+        reminder_uid -> Integer,
+        /// RFC 3339 datetime of the occurrence in UTC
+        occurrence -> Text,
+        /// JSON array of integer user uids, in order of assignment
+        assignees -> Text,
+    }
+}
+
 table! {
     /// Records for external service integrations
     integrations (uid, uid_type, name) {
@@ -14,6 +52,26 @@ table! {
     }
 }
 
+table! {
+    /// Records for the most recent occurrence of each reminder fired by the scheduler
+    last_occurrences (reminder_uid) {
+        /// Uid of the reminder
+        reminder_uid -> Integer,
+        /// RFC 3339 datetime of the occurrence in UTC
+        occurrence -> Text,
+    }
+}
+
+table! {
+    /// Single record counting the changes to reminders
+    reminder_changes (id) {
+        /// Unique identifier for the record, which is always 0
+        id -> Integer,
+        /// Number of times reminders were added, updated or removed
+        changes -> Integer,
+    }
+}
+
 table! {
     /// Records for active reminders
     reminders (uid) {
@@ -21,13 +79,38 @@ table! {
         uid -> Integer,
         /// Name of the reminder
         name -> Text,
-        /// JSON object of day name to an array of the times of day
+        /// JSON object of weekday or day of the month to an array of the times of day,
+        /// a one-off datetime, a recurrence rule, or a cron expression
         schedule -> Text,
         /// Beginning of the week in which the schedule started
         #[sql_name = "startweek"]
         start_week -> Integer,
-        /// JSON array of integer user uids, in order of assignment
+        /// JSON array of objects with the uid and weight of each assignee, in order of assignment
         assignees -> Text,
+        /// IANA time zone in which scheduled times are interpreted
+        timezone -> Text,
+        /// Number of weeks between each week in which a weekly schedule occurs
+        week_interval -> Integer,
+        /// JSON object of excluded dates and whether excluded times advance the rotation
+        exclusions -> Text,
+        /// Last date on which the schedule occurs, if the schedule ends
+        until -> Nullable<Text>,
+        /// Maximum number of times the schedule occurs, if the schedule ends
+        occurrence_limit -> Nullable<Integer>,
+        /// Number of assignees for each occurrence of the schedule
+        assignees_per_occurrence -> Integer,
+        /// Strategy for rotating through the assignees, such as `round-robin` or `per-week`
+        rotation -> Text,
+        /// Number of turns the rotation is moved along by, to keep it in place after edits
+        rotation_offset -> Integer,
+        /// Minutes before a change of assignees that it is announced, if it is announced
+        handoff_notice -> Nullable<Integer>,
+        /// How occurrences missed while the scheduler wasn't running are handled
+        catch_up -> Text,
+        /// RFC 3339 datetime in UTC when the reminder was created, if it was recorded
+        created -> Nullable<Text>,
+        /// Whether the reminder is active or finished
+        status -> Text,
     }
 }
 
@@ -41,4 +124,12 @@ table! {
     }
 }
 
-allow_tables_to_appear_in_same_query!(reminders, users);
+allow_tables_to_appear_in_same_query!(
+    absences,
+    assignment_history,
+    assignment_overrides,
+    last_occurrences,
+    reminder_changes,
+    reminders,
+    users
+);
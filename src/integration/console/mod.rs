@@ -1,8 +1,9 @@
 //! Integration for displaying reminders on the console
 
-use super::{model::Uid, Integration};
+use super::{model::Uid, Delivery, Integration};
 use crate::{Providers, Reminder, User};
 use colored::Colorize;
+use std::future;
 use std::io::Write;
 use time::{OffsetDateTime, UtcOffset};
 
@@ -35,22 +36,62 @@ impl<'a> Integration for Console<'a> {
         reminder: &Reminder,
         assignees: &[User],
         timestamp: &OffsetDateTime,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+        late: bool,
+    ) -> Delivery {
         // Convert the UTC timestamp to the local timezone
-        let local_timestamp = format_date(
-            timestamp.to_offset(UtcOffset::current_local_offset().unwrap_or(UtcOffset::UTC)),
-        );
+        let local_timestamp = format_local_date(*timestamp);
+        let assignee_names = self.format_names(providers, assignees);
+        let label = if late { " (late)" } else { "" };
+
+        // Write to the output buffer, which is done by the time the delivery runs
+        let result = self.0.write_fmt(format_args!(
+            "[{}] {}{}: {}",
+            local_timestamp, reminder.name, label, assignee_names,
+        ));
 
-        // Concatenate the assignees' names for human-readable display
-        let assignee_names = assignees
+        Box::pin(future::ready(result.map_err(Into::into)))
+    }
+
+    fn handoff(
+        &mut self,
+        providers: &Providers,
+        reminder: &Reminder,
+        incoming: &[User],
+        outgoing: &[User],
+        timestamp: &OffsetDateTime,
+    ) -> Delivery {
+        let local_timestamp = format_local_date(*timestamp);
+        let incoming_names = self.format_names(providers, incoming);
+
+        let result = if outgoing.is_empty() {
+            self.0.write_fmt(format_args!(
+                "{}: {} starting {}",
+                reminder.name, incoming_names, local_timestamp,
+            ))
+        } else {
+            let outgoing_names = self.format_names(providers, outgoing);
+            self.0.write_fmt(format_args!(
+                "{}: {} starting {}, taking over from {}",
+                reminder.name, incoming_names, local_timestamp, outgoing_names,
+            ))
+        };
+
+        Box::pin(future::ready(result.map_err(Into::into)))
+    }
+}
+
+impl<'a> Console<'a> {
+    /// Concatenates the names of users for human-readable display, in their configured colors
+    fn format_names(&self, providers: &Providers, users: &[User]) -> String {
+        users
             .iter()
-            .map(|assignee| {
+            .map(|user| {
                 let user_configuration = providers
                     .integration
-                    .get(self, Uid::User(assignee.uid))
+                    .get(self, Uid::User(user.uid))
                     .unwrap_or_default();
 
-                let name = assignee.name.clone();
+                let name = user.name.clone();
 
                 // Apply color to the name if one is set for the user
                 match user_configuration.get("color") {
@@ -62,18 +103,15 @@ impl<'a> Integration for Console<'a> {
                 }
             })
             .collect::<Vec<String>>()
-            .join(", ");
-
-        // Write to the output buffer
-        self.0.write_fmt(format_args!(
-            "[{}] {}: {}",
-            local_timestamp, reminder.name, assignee_names,
-        ))?;
-
-        Ok(())
+            .join(", ")
     }
 }
 
+/// Formats a UTC date for human-readable output in the local timezone
+fn format_local_date(datetime: OffsetDateTime) -> String {
+    format_date(datetime.to_offset(UtcOffset::current_local_offset().unwrap_or(UtcOffset::UTC)))
+}
+
 /// Formats a date for human-readable output
 fn format_date(datetime: OffsetDateTime) -> String {
     let timezone = datetime.offset();
@@ -96,6 +134,7 @@ fn format_date(datetime: OffsetDateTime) -> String {
 mod tests {
     use super::*;
     use crate::integration::Error;
+    use crate::reminder::model::Status;
     use crate::schedule::model::WeeklyTimes;
     use crate::{Reminder, Schedule, User};
     use mockall::predicate::*;
@@ -108,8 +147,8 @@ mod tests {
         assert_eq!("console", console.name());
     }
 
-    #[test]
-    fn it_outputs_uncolored_names_on_notify() -> Result<(), Box<dyn std::error::Error>> {
+    #[tokio::test]
+    async fn it_outputs_uncolored_names_on_notify() -> Result<(), Box<dyn std::error::Error>> {
         let timestamp = datetime!(2020-01-01 00:01:02 UTC);
         let local_timestamp = format_date(
             timestamp.to_offset(UtcOffset::current_local_offset().unwrap_or(UtcOffset::UTC)),
@@ -120,15 +159,15 @@ mod tests {
 
         // Expect the UTC timestamp to be in the correct timezone
         let expected_output = format!("[{}] Reminder: Laura, Donna", local_timestamp);
-        let actual_output = get_console_output(timestamp, config_1, config_2)?;
+        let actual_output = get_console_output(timestamp, config_1, config_2, false).await?;
 
         assert_eq!(expected_output, actual_output);
 
         Ok(())
     }
 
-    #[test]
-    fn it_outputs_colored_names_on_notify() -> Result<(), Box<dyn std::error::Error>> {
+    #[tokio::test]
+    async fn it_outputs_colored_names_on_notify() -> Result<(), Box<dyn std::error::Error>> {
         let timestamp = datetime!(2020-01-01 00:01:02 UTC);
         let local_timestamp = format_date(
             timestamp.to_offset(UtcOffset::current_local_offset().unwrap_or(UtcOffset::UTC)),
@@ -141,15 +180,16 @@ mod tests {
 
         // Expect the UTC timestamp to be in the correct timezone
         let expected_output = format!("[{}] Reminder: {}", local_timestamp, assignees);
-        let actual_output = get_console_output(timestamp, config_1, config_2)?;
+        let actual_output = get_console_output(timestamp, config_1, config_2, false).await?;
 
         assert_eq!(expected_output, actual_output);
 
         Ok(())
     }
 
-    #[test]
-    fn it_outputs_despite_provider_failures_on_notify() -> Result<(), Box<dyn std::error::Error>> {
+    #[tokio::test]
+    async fn it_outputs_despite_provider_failures_on_notify(
+    ) -> Result<(), Box<dyn std::error::Error>> {
         let timestamp = datetime!(2020-01-01 00:01:02 UTC);
         let local_timestamp = format_date(
             timestamp.to_offset(UtcOffset::current_local_offset().unwrap_or(UtcOffset::UTC)),
@@ -160,28 +200,99 @@ mod tests {
 
         // Expect the UTC timestamp to be in the correct timezone
         let expected_output = format!("[{}] Reminder: Laura, Donna", local_timestamp);
-        let actual_output = get_console_output(timestamp, config_1, config_2)?;
+        let actual_output = get_console_output(timestamp, config_1, config_2, false).await?;
+
+        assert_eq!(expected_output, actual_output);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_labels_late_notifications() -> Result<(), Box<dyn std::error::Error>> {
+        let timestamp = datetime!(2020-01-01 00:01:02 UTC);
+
+        let config_1 = Ok(serde_json::json!({}));
+        let config_2 = Ok(serde_json::json!({}));
+
+        let expected_output = format!(
+            "[{}] Reminder (late): Laura, Donna",
+            format_local_date(timestamp)
+        );
+        let actual_output = get_console_output(timestamp, config_1, config_2, true).await?;
 
         assert_eq!(expected_output, actual_output);
 
         Ok(())
     }
 
+    #[tokio::test]
+    async fn it_outputs_incoming_and_outgoing_names_on_handoff(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let timestamp = datetime!(2020-01-06 09:00:00 UTC);
+
+        let mut integration_provider = crate::integration::provider::MockProvidable::new();
+        integration_provider
+            .expect_get()
+            .returning(|_, _| Ok(serde_json::json!({})));
+
+        let providers = Providers {
+            user: &crate::user::provider::MockProvidable::new(),
+            reminder: &crate::reminder::provider::MockProvidable::new(),
+            integration: &integration_provider,
+        };
+
+        let reminder = Reminder {
+            uid: 1,
+            name: String::from("Trash"),
+            schedule: Schedule::new(WeeklyTimes::default(), timestamp, vec![1, 2]),
+            status: Status::Active,
+        };
+
+        let incoming = vec![User {
+            uid: 1,
+            name: String::from("Laura"),
+        }];
+        let outgoing = vec![User {
+            uid: 2,
+            name: String::from("Donna"),
+        }];
+
+        let mut output_buffer = Vec::<u8>::new();
+
+        {
+            let mut integration = Console(Box::new(&mut output_buffer));
+            integration
+                .handoff(&providers, &reminder, &incoming, &outgoing, &timestamp)
+                .await?;
+        }
+
+        let expected_output = format!(
+            "Trash: Laura starting {}, taking over from Donna",
+            format_local_date(timestamp)
+        );
+
+        assert_eq!(expected_output, String::from_utf8(output_buffer)?);
+
+        Ok(())
+    }
+
     /// Runs the console integration and returns the output to its buffer.
     /// There will be 2 users for this integration:
     ///
     /// 1. Laura
     /// 2. Donna
     ///
-    /// There is one reminder set called "Reminder" which all users are assigned to.
+    /// There is one reminder set called "Reminder" which all users are assigned to, notified late
+    /// if `late` is set.
     ///
     /// # Errors
     ///
     /// When the console integration fails to run or the output is not UTF-8.
-    fn get_console_output(
+    async fn get_console_output(
         timestamp: OffsetDateTime,
         config_1: Result<serde_json::Value, Error>,
         config_2: Result<serde_json::Value, Error>,
+        late: bool,
     ) -> Result<String, Box<dyn std::error::Error>> {
         let mut integration_provider = crate::integration::provider::MockProvidable::new();
 
@@ -207,6 +318,7 @@ mod tests {
             uid: 1,
             name: String::from("Reminder"),
             schedule: Schedule::new(WeeklyTimes::default(), timestamp, vec![1, 2]),
+            status: Status::Active,
         };
 
         let assignees = vec![
@@ -224,7 +336,9 @@ mod tests {
 
         {
             let mut integration = Console(Box::new(&mut output_buffer));
-            integration.notify(&providers, &reminder, &assignees, &timestamp)?;
+            integration
+                .notify(&providers, &reminder, &assignees, &timestamp, late)
+                .await?;
         }
 
         Ok(String::from_utf8(output_buffer)?)
@@ -12,12 +12,37 @@ mod error;
 
 use crate::{config, Config, Providers, Reminder, User};
 use std::collections::BTreeMap;
+use std::future::Future;
 use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
+use std::task::Poll;
+use std::time::Duration;
 use time::OffsetDateTime;
 
 #[cfg(test)]
 use mockall::automock;
 
+/// Time an integration has to deliver a notification, unless its config sets a `timeout`
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Delivery of a notification to an integrated service, which completes once the service has
+/// been notified
+pub type Delivery = Pin<Box<dyn Future<Output = Result<(), Box<dyn std::error::Error>>>>>;
+
+/// Outcome of delivering a notification to an integrated service
+#[derive(Debug)]
+pub enum Outcome {
+    /// The notification was delivered
+    Delivered,
+    /// The notification failed with the given error
+    Failed(Box<dyn std::error::Error>),
+    /// The notification wasn't delivered within the integration's timeout
+    TimedOut(Duration),
+}
+
+/// Outcomes of delivering a notification, by integration name
+pub type Outcomes = BTreeMap<&'static str, Outcome>;
+
 /// Provides an interface for integration with external services
 #[cfg_attr(test, automock)]
 pub trait Integration {
@@ -38,21 +63,46 @@ pub trait Integration {
 
     /// Notifies the integrated service of a triggered reminder
     ///
-    /// # Errors
+    /// Late notifications are for occurrences which were missed while the scheduler wasn't
+    /// running, and are sent once it starts again
     ///
-    /// If integration with the external service fails
+    /// The returned delivery runs alongside the deliveries of other integrations, and fails if
+    /// integration with the external service fails
     fn notify<'a>(
         &mut self,
         providers: &Providers<'a>,
         reminder: &Reminder,
         assignees: &[User],
         timestamp: &OffsetDateTime,
-    ) -> Result<(), Box<dyn std::error::Error>>;
+        late: bool,
+    ) -> Delivery;
+
+    /// Notifies the integrated service that a reminder is being handed off to new assignees,
+    /// ahead of the first occurrence they're assigned to
+    ///
+    /// The incoming assignees take over from the given occurrence, and the outgoing assignees
+    /// are released from it
+    ///
+    /// The returned delivery runs alongside the deliveries of other integrations, and fails if
+    /// integration with the external service fails
+    fn handoff<'a>(
+        &mut self,
+        providers: &Providers<'a>,
+        reminder: &Reminder,
+        incoming: &[User],
+        outgoing: &[User],
+        timestamp: &OffsetDateTime,
+    ) -> Delivery;
 }
 
 /// All configured integrations for the service
 #[derive(Default)]
-pub struct Integrations(BTreeMap<&'static str, Box<dyn Integration>>);
+pub struct Integrations {
+    /// Integrations by name
+    integrations: BTreeMap<&'static str, Box<dyn Integration>>,
+    /// Configured time each integration has to deliver a notification, by integration name
+    timeouts: BTreeMap<&'static str, Duration>,
+}
 
 impl Integrations {
     /// Initializes all configured integrations
@@ -62,16 +112,71 @@ impl Integrations {
     /// When there is an error with the config or any of the integrations
     #[must_use]
     pub fn new(config: &Config) -> Self {
-        let mut integrations: BTreeMap<&'static str, Box<dyn Integration>> = BTreeMap::new();
+        let mut integrations = Self::default();
 
         if let Some(configs) = &config.integrations {
-            if Self::get_enabled_config(configs, "console").is_some() {
+            if let Some(config) = Self::get_enabled_config(configs, "console") {
                 let integration = Console(Box::new(std::io::stdout()));
+                integrations = integrations.with_timeout(integration.name(), get_timeout(config));
                 integrations.insert(integration.name(), Box::new(integration));
             }
         }
 
-        Self(integrations)
+        integrations
+    }
+
+    /// Sets the time the named integration has to deliver a notification
+    #[must_use]
+    pub fn with_timeout(mut self, name: &'static str, timeout: Duration) -> Self {
+        self.timeouts.insert(name, timeout);
+        self
+    }
+
+    /// Returns the time the named integration has to deliver a notification
+    #[must_use]
+    pub fn timeout(&self, name: &str) -> Duration {
+        self.timeouts.get(name).copied().unwrap_or(DEFAULT_TIMEOUT)
+    }
+
+    /// Runs the deliveries of a notification to each named integration at the same time,
+    /// waiting for all of them to succeed, fail, or run out of time
+    pub async fn deliver(&self, deliveries: Vec<(&'static str, Delivery)>) -> Outcomes {
+        let mut pending: Vec<_> = deliveries
+            .into_iter()
+            .map(|(name, delivery)| {
+                let timeout = self.timeout(name);
+                let outcome = async move {
+                    match tokio::time::timeout(timeout, delivery).await {
+                        Ok(Ok(())) => Outcome::Delivered,
+                        Ok(Err(error)) => Outcome::Failed(error),
+                        Err(_) => Outcome::TimedOut(timeout),
+                    }
+                };
+
+                (name, Some(Box::pin(outcome)))
+            })
+            .collect();
+
+        let mut outcomes = Outcomes::new();
+        std::future::poll_fn(|context| {
+            for (name, delivery) in &mut pending {
+                if let Some(outcome) = delivery.as_mut() {
+                    if let Poll::Ready(outcome) = outcome.as_mut().poll(context) {
+                        outcomes.insert(*name, outcome);
+                        *delivery = None;
+                    }
+                }
+            }
+
+            if outcomes.len() == pending.len() {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        })
+        .await;
+
+        outcomes
     }
 
     /// Returns an integration configuration if the integration is enabled
@@ -89,12 +194,74 @@ impl Deref for Integrations {
     type Target = BTreeMap<&'static str, Box<dyn Integration>>;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.integrations
     }
 }
 
 impl DerefMut for Integrations {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+        &mut self.integrations
+    }
+}
+
+/// Reads the time an integration has to deliver a notification from its config, as a number of
+/// seconds
+fn get_timeout(config: &config::Integration) -> Duration {
+    match config.get("timeout").map(|timeout| timeout.parse()) {
+        Some(Ok(seconds)) => Duration::from_secs(seconds),
+        Some(Err(error)) => {
+            eprintln!("Invalid integration timeout, using the default: {}", error);
+            DEFAULT_TIMEOUT
+        }
+        None => DEFAULT_TIMEOUT,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns a delivery which completes with the given result after the given delay
+    fn delivery(delay: Duration, result: Result<(), String>) -> Delivery {
+        Box::pin(async move {
+            tokio::time::sleep(delay).await;
+            result.map_err(Into::into)
+        })
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn it_delivers_to_integrations_at_the_same_time_within_their_timeouts() {
+        let integrations = Integrations::default().with_timeout("slow", Duration::from_secs(5));
+        let started = tokio::time::Instant::now();
+
+        let outcomes = integrations
+            .deliver(vec![
+                ("fast", delivery(Duration::from_secs(8), Ok(()))),
+                ("slow", delivery(Duration::from_secs(60), Ok(()))),
+                (
+                    "broken",
+                    delivery(Duration::from_secs(8), Err(String::from("down"))),
+                ),
+            ])
+            .await;
+
+        assert!(matches!(outcomes["fast"], Outcome::Delivered));
+        assert!(matches!(outcomes["slow"], Outcome::TimedOut(timeout) if timeout.as_secs() == 5));
+        assert!(
+            matches!(&outcomes["broken"], Outcome::Failed(error) if error.to_string() == "down")
+        );
+
+        // Deliveries don't wait on each other
+        assert!(started.elapsed() < Duration::from_secs(9));
+    }
+
+    #[test]
+    fn it_reads_integration_timeouts_from_config() {
+        let config: config::Integration = vec![(String::from("timeout"), String::from("30"))]
+            .into_iter()
+            .collect();
+
+        assert_eq!(Duration::from_secs(30), get_timeout(&config));
+        assert_eq!(DEFAULT_TIMEOUT, get_timeout(&config::Integration::new()));
     }
 }
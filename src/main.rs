@@ -1,4 +1,4 @@
-use remembear::{command, command::execute, integration, reminder, user};
+use remembear::{command, command::execute, config, integration, reminder, user};
 use remembear::{Config, Dependencies, Integrations, Providers};
 use std::error::Error;
 use std::sync::Arc;
@@ -8,7 +8,7 @@ use structopt::StructOpt;
 async fn main() -> Result<(), Box<dyn Error>> {
     let command = command::Global::from_args();
 
-    let config = Config::load("remembear")?;
+    let config = Config::load(config::NAME)?;
     let dependencies = Dependencies::new(&config)?;
     let integrations = Integrations::new(&config);
 
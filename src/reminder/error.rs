@@ -13,4 +13,13 @@ pub enum Error {
         #[from]
         source: DieselError,
     },
+    /// An assignment override could not be converted for storage
+    #[error("Failed to store assignment override: {0}")]
+    Override(String),
+    /// A past assignment could not be converted for storage
+    #[error("Failed to store past assignment: {0}")]
+    History(String),
+    /// The last occurrence of a reminder could not be converted for storage or retrieval
+    #[error("Failed to store last occurrence: {0}")]
+    LastOccurrence(String),
 }
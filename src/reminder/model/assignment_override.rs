@@ -0,0 +1,79 @@
+//! Data model for an assignment which overrides the rotation for one occurrence
+
+use crate::database::schema::assignment_overrides;
+use crate::reminder::Error;
+use diesel::backend::Backend;
+use diesel::deserialize::{FromSqlRow, Queryable, Result as FromSqlResult};
+use diesel::sql_types::{Integer, Text};
+use serde::Serialize;
+use std::convert::TryFrom;
+use time::format_description::well_known::Rfc3339;
+use time::{OffsetDateTime, UtcOffset};
+
+/// Record for the assignees of one occurrence of a reminder, replacing the rotation
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct AssignmentOverride {
+    /// Uid of the reminder
+    pub reminder_uid: i32,
+    /// Time of the occurrence
+    #[serde(with = "crate::schedule::rfc3339")]
+    pub occurrence: OffsetDateTime,
+    /// Uids of the assignees for the occurrence, in order of assignment
+    pub assignees: Vec<i32>,
+}
+
+impl<TDatabase> FromSqlRow<(Integer, Text, Text), TDatabase> for AssignmentOverride
+where
+    TDatabase: Backend,
+    i32: FromSqlRow<Integer, TDatabase>,
+    String: FromSqlRow<Text, TDatabase>,
+{
+    const FIELDS_NEEDED: usize = 3;
+
+    fn build_from_row<TRow: diesel::row::Row<TDatabase>>(row: &mut TRow) -> FromSqlResult<Self> {
+        Ok(Self {
+            reminder_uid: i32::build_from_row(row)?,
+            occurrence: OffsetDateTime::parse(&String::build_from_row(row)?, &Rfc3339)?,
+            assignees: serde_json::from_str(&String::build_from_row(row)?)?,
+        })
+    }
+}
+
+impl Queryable<assignment_overrides::SqlType, diesel::sqlite::Sqlite> for AssignmentOverride {
+    type Row = AssignmentOverride;
+
+    fn build(row: Self::Row) -> Self {
+        row
+    }
+}
+
+/// Insertable `AssignmentOverride` for use with `diesel`
+#[derive(Debug, Insertable, Eq, PartialEq)]
+#[table_name = "assignment_overrides"]
+pub(crate) struct InsertableAssignmentOverride {
+    /// Uid of the reminder
+    pub reminder_uid: i32,
+    /// Time of the occurrence as an RFC 3339 datetime in UTC
+    pub occurrence: String,
+    /// JSON array of the uids of the assignees for the occurrence
+    pub assignees: String,
+}
+
+impl TryFrom<AssignmentOverride> for InsertableAssignmentOverride {
+    type Error = Error;
+
+    fn try_from(assignment_override: AssignmentOverride) -> Result<Self, Self::Error> {
+        let to_error = |error: &dyn std::error::Error| Error::Override(error.to_string());
+
+        Ok(Self {
+            reminder_uid: assignment_override.reminder_uid,
+            occurrence: assignment_override
+                .occurrence
+                .to_offset(UtcOffset::UTC)
+                .format(&Rfc3339)
+                .map_err(|error| to_error(&error))?,
+            assignees: serde_json::to_string(&assignment_override.assignees)
+                .map_err(|error| to_error(&error))?,
+        })
+    }
+}
@@ -1,10 +1,18 @@
 //! Data models for reminders
 
+mod assignment_override;
 mod new_reminder;
+mod past_assignment;
 mod reminder;
+mod status;
 mod updated_reminder;
 
+pub use assignment_override::AssignmentOverride;
+pub(crate) use assignment_override::InsertableAssignmentOverride;
 pub(crate) use new_reminder::InsertableNewReminder;
 pub use new_reminder::NewReminder;
+pub(crate) use past_assignment::InsertablePastAssignment;
+pub use past_assignment::PastAssignment;
 pub use reminder::Reminder;
+pub use status::Status;
 pub use updated_reminder::UpdatedReminder;
@@ -0,0 +1,79 @@
+//! Data model for the assignees of an occurrence which has already happened
+
+use crate::database::schema::assignment_history;
+use crate::reminder::Error;
+use diesel::backend::Backend;
+use diesel::deserialize::{FromSqlRow, Queryable, Result as FromSqlResult};
+use diesel::sql_types::{Integer, Text};
+use serde::Serialize;
+use std::convert::TryFrom;
+use time::format_description::well_known::Rfc3339;
+use time::{OffsetDateTime, UtcOffset};
+
+/// Record for the assignees who were assigned to one past occurrence of a reminder
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct PastAssignment {
+    /// Uid of the reminder
+    pub reminder_uid: i32,
+    /// Time of the occurrence
+    #[serde(with = "crate::schedule::rfc3339")]
+    pub occurrence: OffsetDateTime,
+    /// Uids of the assignees for the occurrence, in order of assignment
+    pub assignees: Vec<i32>,
+}
+
+impl<TDatabase> FromSqlRow<(Integer, Text, Text), TDatabase> for PastAssignment
+where
+    TDatabase: Backend,
+    i32: FromSqlRow<Integer, TDatabase>,
+    String: FromSqlRow<Text, TDatabase>,
+{
+    const FIELDS_NEEDED: usize = 3;
+
+    fn build_from_row<TRow: diesel::row::Row<TDatabase>>(row: &mut TRow) -> FromSqlResult<Self> {
+        Ok(Self {
+            reminder_uid: i32::build_from_row(row)?,
+            occurrence: OffsetDateTime::parse(&String::build_from_row(row)?, &Rfc3339)?,
+            assignees: serde_json::from_str(&String::build_from_row(row)?)?,
+        })
+    }
+}
+
+impl Queryable<assignment_history::SqlType, diesel::sqlite::Sqlite> for PastAssignment {
+    type Row = PastAssignment;
+
+    fn build(row: Self::Row) -> Self {
+        row
+    }
+}
+
+/// Insertable `PastAssignment` for use with `diesel`
+#[derive(Debug, Insertable, Eq, PartialEq)]
+#[table_name = "assignment_history"]
+pub(crate) struct InsertablePastAssignment {
+    /// Uid of the reminder
+    pub reminder_uid: i32,
+    /// Time of the occurrence as an RFC 3339 datetime in UTC
+    pub occurrence: String,
+    /// JSON array of the uids of the assignees for the occurrence
+    pub assignees: String,
+}
+
+impl TryFrom<PastAssignment> for InsertablePastAssignment {
+    type Error = Error;
+
+    fn try_from(past_assignment: PastAssignment) -> Result<Self, Self::Error> {
+        let to_error = |error: &dyn std::error::Error| Error::History(error.to_string());
+
+        Ok(Self {
+            reminder_uid: past_assignment.reminder_uid,
+            occurrence: past_assignment
+                .occurrence
+                .to_offset(UtcOffset::UTC)
+                .format(&Rfc3339)
+                .map_err(|error| to_error(&error))?,
+            assignees: serde_json::to_string(&past_assignment.assignees)
+                .map_err(|error| to_error(&error))?,
+        })
+    }
+}
@@ -1,15 +1,16 @@
 //! Data model for a reminder
 
+use super::Status;
 use crate::database::schema::reminders;
 use crate::{schedule, Schedule};
 use diesel::backend::Backend;
 use diesel::deserialize::{FromSqlRow, Queryable, Result as FromSqlResult};
-use diesel::sql_types::{Integer, Text};
+use diesel::sql_types::{Integer, Nullable, Text};
 use serde::Serialize;
 use std::convert::TryInto;
 
 /// Record for an individual reminder
-#[derive(Debug, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 pub struct Reminder {
     /// Unique identifier for the reminder record
     pub uid: i32,
@@ -18,16 +19,59 @@ pub struct Reminder {
     /// Schedule for the reminder
     #[serde(flatten)]
     pub schedule: Schedule,
+    /// Whether the reminder is still being scheduled
+    pub status: Status,
 }
 
-impl<TDatabase> FromSqlRow<(Integer, Text, Text, Integer, Text), TDatabase> for Reminder
+impl<TDatabase>
+    FromSqlRow<
+        (
+            Integer,
+            Text,
+            Text,
+            Integer,
+            Text,
+            Text,
+            Integer,
+            Text,
+            Nullable<Text>,
+            Nullable<Integer>,
+            Integer,
+            Text,
+            Integer,
+            Nullable<Integer>,
+            Text,
+            Nullable<Text>,
+            Text,
+        ),
+        TDatabase,
+    > for Reminder
 where
     TDatabase: Backend,
     i32: FromSqlRow<Integer, TDatabase>,
     String: FromSqlRow<Text, TDatabase>,
-    schedule::Provider: FromSqlRow<(Text, Integer, Text), TDatabase>,
+    Status: FromSqlRow<Text, TDatabase>,
+    schedule::Provider: FromSqlRow<
+        (
+            Text,
+            Integer,
+            Text,
+            Text,
+            Integer,
+            Text,
+            Nullable<Text>,
+            Nullable<Integer>,
+            Integer,
+            Text,
+            Integer,
+            Nullable<Integer>,
+            Text,
+            Nullable<Text>,
+        ),
+        TDatabase,
+    >,
 {
-    const FIELDS_NEEDED: usize = 5;
+    const FIELDS_NEEDED: usize = 17;
 
     /// Converts a `SQLite` row to a `Reminder` using `schedule::Provider`
     fn build_from_row<TRow: diesel::row::Row<TDatabase>>(row: &mut TRow) -> FromSqlResult<Self> {
@@ -35,6 +79,7 @@ where
             uid: i32::build_from_row(row)?,
             name: String::build_from_row(row)?,
             schedule: schedule::Provider::build_from_row(row)?.try_into()?,
+            status: Status::build_from_row(row)?,
         })
     }
 }
@@ -0,0 +1,72 @@
+//! Data model for the status of a reminder
+
+use crate::Schedule;
+use diesel::deserialize::{FromSql, Result as FromSqlResult};
+use diesel::serialize::{Output, Result as ToSqlResult, ToSql};
+use diesel::{backend::Backend, sql_types::Text};
+use serde::Serialize;
+use std::io::Write;
+use time::OffsetDateTime;
+
+/// Whether a reminder is still being scheduled
+#[derive(AsExpression, Clone, Copy, Debug, Eq, FromSqlRow, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+#[sql_type = "Text"]
+pub enum Status {
+    /// The reminder has future occurrences and will be scheduled
+    Active,
+    /// The one-off reminder has occurred and will not be scheduled again
+    Finished,
+    /// The recurring reminder has reached its end and will not be scheduled again
+    Completed,
+}
+
+impl Status {
+    /// Determines the status of a reminder with the given schedule at the given datetime
+    #[must_use]
+    pub fn at(schedule: &Schedule, current_time: OffsetDateTime) -> Self {
+        if !schedule.is_exhausted(current_time) {
+            Self::Active
+        } else if schedule.recurrence.is_once() {
+            Self::Finished
+        } else {
+            Self::Completed
+        }
+    }
+}
+
+impl Default for Status {
+    fn default() -> Self {
+        Self::Active
+    }
+}
+
+impl<TDatabase: Backend> ToSql<Text, TDatabase> for Status
+where
+    str: ToSql<Text, TDatabase>,
+{
+    /// Converts this model to a SQL type
+    /// Data is serialized as the lowercase name of the status
+    fn to_sql<W: Write>(&self, out: &mut Output<W, TDatabase>) -> ToSqlResult {
+        match self {
+            Self::Active => "active".to_sql(out),
+            Self::Finished => "finished".to_sql(out),
+            Self::Completed => "completed".to_sql(out),
+        }
+    }
+}
+
+impl<TDatabase: Backend> FromSql<Text, TDatabase> for Status
+where
+    String: FromSql<Text, TDatabase>,
+{
+    /// Creates this model from a SQL type
+    fn from_sql(bytes: Option<&TDatabase::RawValue>) -> FromSqlResult<Self> {
+        match String::from_sql(bytes)?.as_str() {
+            "active" => Ok(Self::Active),
+            "finished" => Ok(Self::Finished),
+            "completed" => Ok(Self::Completed),
+            status => Err(format!("Invalid reminder status {}", status).into()),
+        }
+    }
+}
@@ -1,5 +1,6 @@
 //! Data model for an updated reminder
 
+use super::Status;
 use crate::Schedule;
 
 /// Necessary data to update an existing reminder
@@ -11,4 +12,6 @@ pub struct UpdatedReminder {
     pub name: String,
     /// Updated schedule for the reminder
     pub schedule: Schedule,
+    /// Updated status of the reminder
+    pub status: Status,
 }
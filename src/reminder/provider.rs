@@ -1,11 +1,20 @@
 //! Provider for reminder data
 
-use super::model::{InsertableNewReminder, NewReminder, Reminder, UpdatedReminder};
+use super::model::{
+    AssignmentOverride, InsertableAssignmentOverride, InsertableNewReminder,
+    InsertablePastAssignment, NewReminder, PastAssignment, Reminder, Status, UpdatedReminder,
+};
 use super::Error;
-use crate::database::{schema::reminders, Database};
+use crate::database::schema::{
+    assignment_history, assignment_overrides, last_occurrences, reminder_changes, reminders,
+};
+use crate::database::Database;
 use crate::schedule;
 use diesel::prelude::*;
+use std::convert::TryFrom;
 use std::sync::Arc;
+use time::format_description::well_known::Rfc3339;
+use time::{OffsetDateTime, UtcOffset};
 
 #[cfg(test)]
 use mockall::automock;
@@ -27,6 +36,20 @@ pub trait Providable {
     /// When the update fails
     fn update(&self, reminder: UpdatedReminder) -> Result<Reminder, Error>;
 
+    /// Updates several existing reminders in the database, either updating all of them or none
+    ///
+    /// # Errors
+    ///
+    /// When any of the updates fail
+    fn update_all(&self, reminders: Vec<UpdatedReminder>) -> Result<Vec<Reminder>, Error>;
+
+    /// Sets the status of an existing reminder in the database
+    ///
+    /// # Errors
+    ///
+    /// When the update fails
+    fn set_status(&self, uid: i32, status: Status) -> Result<(), Error>;
+
     /// Removes an existing reminder from the database
     ///
     /// # Errors
@@ -47,6 +70,64 @@ pub trait Providable {
     ///
     /// When user retrieval fails
     fn get_by_uid(&self, uid: i32) -> Result<Reminder, Error>;
+
+    /// Assigns an occurrence of a reminder, replacing any existing override for it
+    ///
+    /// # Errors
+    ///
+    /// When the insertion fails
+    fn set_override(&self, assignment_override: AssignmentOverride) -> Result<(), Error>;
+
+    /// Assigns several occurrences of reminders, either setting all of the overrides or none
+    ///
+    /// # Errors
+    ///
+    /// When any of the insertions fail
+    fn set_overrides(&self, assignment_overrides: Vec<AssignmentOverride>) -> Result<(), Error>;
+
+    /// Retrieves the assignment overrides for a reminder from the database
+    ///
+    /// # Errors
+    ///
+    /// When override retrieval fails
+    fn get_overrides(&self, uid: i32) -> Result<Vec<AssignmentOverride>, Error>;
+
+    /// Records who was assigned to an occurrence of a reminder, replacing any existing record
+    ///
+    /// # Errors
+    ///
+    /// When the insertion fails
+    fn add_history(&self, past_assignment: PastAssignment) -> Result<(), Error>;
+
+    /// Retrieves the recorded assignments for past occurrences of a reminder from the database
+    ///
+    /// # Errors
+    ///
+    /// When history retrieval fails
+    fn get_history(&self, uid: i32) -> Result<Vec<PastAssignment>, Error>;
+
+    /// Records the most recent occurrence of a reminder which was notified, replacing the
+    /// previous one
+    ///
+    /// # Errors
+    ///
+    /// When the insertion fails
+    fn set_last_occurrence(&self, uid: i32, occurrence: OffsetDateTime) -> Result<(), Error>;
+
+    /// Retrieves the most recent occurrence of a reminder which was notified, if any
+    ///
+    /// # Errors
+    ///
+    /// When retrieval fails
+    fn get_last_occurrence(&self, uid: i32) -> Result<Option<OffsetDateTime>, Error>;
+
+    /// Retrieves the number of times reminders were added, updated or removed in the database,
+    /// which changes whenever the stored reminders change
+    ///
+    /// # Errors
+    ///
+    /// When retrieval fails
+    fn get_change_count(&self) -> Result<i32, Error>;
 }
 
 /// Provides access to reminder data in persistent storage
@@ -82,21 +163,65 @@ impl Providable for Provider {
         diesel::update(reminders::table.find(reminder.uid))
             .set((
                 reminders::columns::name.eq(reminder.name),
-                reminders::columns::schedule.eq(schedule.weekly_times),
+                reminders::columns::schedule.eq(schedule.recurrence),
                 reminders::columns::start_week.eq(schedule.start_week),
                 reminders::columns::assignees.eq(schedule.assignees),
+                reminders::columns::timezone.eq(schedule.timezone),
+                reminders::columns::week_interval.eq(schedule.week_interval),
+                reminders::columns::exclusions.eq(schedule.exclusions),
+                reminders::columns::until.eq(schedule.until),
+                reminders::columns::occurrence_limit.eq(schedule.occurrence_limit),
+                reminders::columns::assignees_per_occurrence.eq(schedule.assignees_per_occurrence),
+                reminders::columns::rotation.eq(schedule.rotation),
+                reminders::columns::rotation_offset.eq(schedule.rotation_offset),
+                reminders::columns::handoff_notice.eq(schedule.handoff_notice),
+                reminders::columns::catch_up.eq(schedule.catch_up),
+                reminders::columns::created.eq(schedule.created),
+                reminders::columns::status.eq(reminder.status),
             ))
             .execute(self.database.connection())?;
 
         self.get_by_uid(uid)
     }
 
-    fn remove(&self, uid: i32) -> Result<(), Error> {
-        diesel::delete(reminders::table.find(uid)).execute(self.database.connection())?;
+    fn update_all(&self, reminders: Vec<UpdatedReminder>) -> Result<Vec<Reminder>, Error> {
+        self.database.connection().transaction(|| {
+            reminders
+                .into_iter()
+                .map(|reminder| self.update(reminder))
+                .collect()
+        })
+    }
+
+    fn set_status(&self, uid: i32, status: Status) -> Result<(), Error> {
+        diesel::update(reminders::table.find(uid))
+            .set(reminders::columns::status.eq(status))
+            .execute(self.database.connection())?;
 
         Ok(())
     }
 
+    fn remove(&self, uid: i32) -> Result<(), Error> {
+        self.database.connection().transaction(|| {
+            diesel::delete(
+                assignment_overrides::table.filter(assignment_overrides::reminder_uid.eq(uid)),
+            )
+            .execute(self.database.connection())?;
+
+            diesel::delete(
+                assignment_history::table.filter(assignment_history::reminder_uid.eq(uid)),
+            )
+            .execute(self.database.connection())?;
+
+            diesel::delete(last_occurrences::table.find(uid))
+                .execute(self.database.connection())?;
+
+            diesel::delete(reminders::table.find(uid)).execute(self.database.connection())?;
+
+            Ok(())
+        })
+    }
+
     fn get_all(&self) -> Result<Vec<Reminder>, Error> {
         Ok(reminders::table.load(self.database.connection())?)
     }
@@ -106,4 +231,79 @@ impl Providable for Provider {
             .find(uid)
             .first(self.database.connection())?)
     }
+
+    fn set_override(&self, assignment_override: AssignmentOverride) -> Result<(), Error> {
+        let insertable_override = InsertableAssignmentOverride::try_from(assignment_override)?;
+
+        diesel::replace_into(assignment_overrides::table)
+            .values(insertable_override)
+            .execute(self.database.connection())?;
+
+        Ok(())
+    }
+
+    fn set_overrides(&self, assignment_overrides: Vec<AssignmentOverride>) -> Result<(), Error> {
+        self.database.connection().transaction(|| {
+            assignment_overrides
+                .into_iter()
+                .try_for_each(|assignment_override| self.set_override(assignment_override))
+        })
+    }
+
+    fn get_overrides(&self, uid: i32) -> Result<Vec<AssignmentOverride>, Error> {
+        Ok(assignment_overrides::table
+            .filter(assignment_overrides::reminder_uid.eq(uid))
+            .load(self.database.connection())?)
+    }
+
+    fn add_history(&self, past_assignment: PastAssignment) -> Result<(), Error> {
+        let insertable_assignment = InsertablePastAssignment::try_from(past_assignment)?;
+
+        diesel::replace_into(assignment_history::table)
+            .values(insertable_assignment)
+            .execute(self.database.connection())?;
+
+        Ok(())
+    }
+
+    fn get_history(&self, uid: i32) -> Result<Vec<PastAssignment>, Error> {
+        Ok(assignment_history::table
+            .filter(assignment_history::reminder_uid.eq(uid))
+            .load(self.database.connection())?)
+    }
+
+    fn set_last_occurrence(&self, uid: i32, occurrence: OffsetDateTime) -> Result<(), Error> {
+        let occurrence = occurrence
+            .to_offset(UtcOffset::UTC)
+            .format(&Rfc3339)
+            .map_err(|error| Error::LastOccurrence(error.to_string()))?;
+
+        diesel::replace_into(last_occurrences::table)
+            .values((
+                last_occurrences::reminder_uid.eq(uid),
+                last_occurrences::occurrence.eq(occurrence),
+            ))
+            .execute(self.database.connection())?;
+
+        Ok(())
+    }
+
+    fn get_last_occurrence(&self, uid: i32) -> Result<Option<OffsetDateTime>, Error> {
+        last_occurrences::table
+            .find(uid)
+            .select(last_occurrences::occurrence)
+            .first::<String>(self.database.connection())
+            .optional()?
+            .map(|occurrence| {
+                OffsetDateTime::parse(&occurrence, &Rfc3339)
+                    .map_err(|error| Error::LastOccurrence(error.to_string()))
+            })
+            .transpose()
+    }
+
+    fn get_change_count(&self) -> Result<i32, Error> {
+        Ok(reminder_changes::table
+            .select(reminder_changes::changes)
+            .first(self.database.connection())?)
+    }
 }
@@ -0,0 +1,106 @@
+//! Assignees with a weighted share of a schedule's occurrences
+
+use serde::{Deserialize, Serialize};
+use std::num::NonZeroU32;
+use std::str::FromStr;
+
+/// Assignee of a schedule and their share of its occurrences
+///
+/// Written as a user uid such as `1`, or with a weight such as `1:2` for an assignee who takes
+/// twice as many turns as an assignee with the default weight of 1
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Assignee {
+    /// Uid of the assigned user
+    pub uid: i32,
+    /// Share of the occurrences relative to the other assignees, which is 1 or more
+    pub weight: u32,
+}
+
+impl From<i32> for Assignee {
+    fn from(uid: i32) -> Self {
+        Self { uid, weight: 1 }
+    }
+}
+
+impl FromStr for Assignee {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "Invalid assignee `{}`, should be a uid or <uid>:<weight> such as 1:2",
+                value
+            )
+        };
+
+        let (uid, weight) = value.trim().split_once(':').unwrap_or((value.trim(), "1"));
+
+        Ok(Self {
+            uid: uid.parse().map_err(|_| invalid())?,
+            weight: weight.parse::<NonZeroU32>().map_err(|_| invalid())?.get(),
+        })
+    }
+}
+
+/// Evenly spreads the turns of weighted assignees over one round of a rotation
+///
+/// Each assignee appears as many times as their weight, with their turns interleaved as evenly
+/// as possible so heavier assignees don't take several turns in a row. Weights of 0 count as 1.
+/// Returns the indices of the assignees in order of assignment
+pub(crate) fn spread_turns(weights: &[u32]) -> Vec<usize> {
+    let weights: Vec<i64> = weights
+        .iter()
+        .map(|weight| i64::from((*weight).max(1)))
+        .collect();
+    let total: i64 = weights.iter().sum();
+    let mut credits = vec![0_i64; weights.len()];
+    let mut turns = Vec::with_capacity(usize::try_from(total).unwrap_or(0));
+
+    // Smooth weighted round-robin, where each turn goes to the assignee with the most credit
+    for _ in 0..total {
+        for (credit, weight) in credits.iter_mut().zip(&weights) {
+            *credit += weight;
+        }
+
+        let (index, _) = credits
+            .iter()
+            .enumerate()
+            .fold((0, i64::MIN), |best, (index, credit)| {
+                if *credit > best.1 {
+                    (index, *credit)
+                } else {
+                    best
+                }
+            });
+
+        credits[index] -= total;
+        turns.push(index);
+    }
+
+    turns
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+    #[test]
+    fn it_parses_assignees_with_and_without_weights() -> Result<()> {
+        assert_eq!(Assignee::from(1), "1".parse()?);
+        assert_eq!(Assignee { uid: 1, weight: 2 }, "1:2".parse()?);
+
+        assert!("1:0".parse::<Assignee>().is_err());
+        assert!("laura".parse::<Assignee>().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_spreads_turns_evenly_by_weight() {
+        assert_eq!(vec![0, 1, 2], spread_turns(&[1, 1, 1]));
+        assert_eq!(vec![0, 1, 0], spread_turns(&[2, 1]));
+        assert_eq!(vec![0, 1, 0, 2, 0], spread_turns(&[3, 1, 1]));
+    }
+}
@@ -0,0 +1,88 @@
+//! Dates on which assignees are unavailable
+
+use super::exclusions::{merge, DateRange};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::iter::FromIterator;
+use time::Date;
+
+/// Dates on which an assignee is away
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Absence {
+    /// Dates of the absence, as wall-clock dates in each schedule's time zone
+    pub dates: DateRange,
+    /// Whether the assignee keeps their turn until they return, instead of losing it
+    pub defer_turn: bool,
+}
+
+/// Absences of assignees, by assignee uid
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Availability(HashMap<i32, Vec<Absence>>);
+
+impl Availability {
+    /// Marks the given assignee as away
+    pub fn add(&mut self, assignee: i32, absence: Absence) {
+        self.0.entry(assignee).or_default().push(absence);
+    }
+
+    /// Finds the absence of the given assignee on the given date, if any
+    #[must_use]
+    pub fn find(&self, assignee: i32, date: Date) -> Option<&Absence> {
+        self.0
+            .get(&assignee)?
+            .iter()
+            .find(|absence| absence.dates.contains(date))
+    }
+
+    /// Whether the given assignee is available on the given date
+    #[must_use]
+    pub fn is_available(&self, assignee: i32, date: Date) -> bool {
+        self.find(assignee, date).is_none()
+    }
+
+    /// Provides the merged date ranges in which any of the given assignees defer their turn
+    pub(crate) fn deferred_dates(&self, assignees: &[i32]) -> Vec<DateRange> {
+        merge(
+            assignees
+                .iter()
+                .filter_map(|assignee| self.0.get(assignee))
+                .flatten()
+                .filter(|absence| absence.defer_turn)
+                .map(|absence| absence.dates)
+                .collect(),
+        )
+    }
+}
+
+impl FromIterator<(i32, Absence)> for Availability {
+    fn from_iter<T: IntoIterator<Item = (i32, Absence)>>(absences: T) -> Self {
+        let mut availability = Self::default();
+
+        for (assignee, absence) in absences {
+            availability.add(assignee, absence);
+        }
+
+        availability
+    }
+}
+
+/// Assignee covering for a scheduled assignee who is away
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub struct Substitution {
+    /// Uid of the scheduled assignee who is away
+    pub absent: i32,
+    /// Uid of the assignee covering for them
+    pub substitute: i32,
+}
+
+/// Assignees for an occurrence once absences and overrides are taken into account
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct Assignment {
+    /// Uids of the assignees, in order of assignment
+    pub assignees: Vec<i32>,
+    /// Scheduled assignees who are away, and who is covering for them
+    pub substitutions: Vec<Substitution>,
+    /// Whether the assignees were chosen by hand instead of by the rotation
+    #[serde(rename = "override")]
+    pub is_override: bool,
+}
@@ -0,0 +1,80 @@
+//! Policies for occurrences which were missed while the scheduler wasn't running
+
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+/// How occurrences which were missed while the scheduler wasn't running are handled once it
+/// starts again, written as `all`, `latest` or `skip`
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum CatchUp {
+    /// Every missed occurrence is notified late, in order
+    All,
+    /// Only the most recent missed occurrence is notified late
+    Latest,
+    /// Missed occurrences are not notified
+    Skip,
+}
+
+impl Default for CatchUp {
+    fn default() -> Self {
+        Self::Skip
+    }
+}
+
+impl FromStr for CatchUp {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "all" => Ok(Self::All),
+            "latest" => Ok(Self::Latest),
+            "skip" => Ok(Self::Skip),
+            _ => Err(format!(
+                "Invalid catch-up `{}`, should be all, latest or skip",
+                value
+            )),
+        }
+    }
+}
+
+impl TryFrom<String> for CatchUp {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<CatchUp> for String {
+    fn from(catch_up: CatchUp) -> Self {
+        catch_up.to_string()
+    }
+}
+
+impl fmt::Display for CatchUp {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::All => write!(formatter, "all"),
+            Self::Latest => write!(formatter, "latest"),
+            Self::Skip => write!(formatter, "skip"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_and_displays_catch_up_policies() {
+        for catch_up in &[CatchUp::All, CatchUp::Latest, CatchUp::Skip] {
+            assert_eq!(Ok(*catch_up), catch_up.to_string().parse());
+        }
+
+        assert_eq!(Ok(CatchUp::Latest), " Latest ".parse());
+        assert!("some".parse::<CatchUp>().is_err());
+    }
+}
@@ -0,0 +1,375 @@
+//! Schedules defined by cron expressions
+
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use time::util::days_in_year_month;
+use time::{Date, Duration, Month, PrimitiveDateTime, Time};
+
+/// Abbreviated month names, starting from January
+const MONTHS: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+
+/// Abbreviated weekday names, starting from Sunday
+const WEEKDAYS: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// Number of days to search for the next matching day, which is enough to find the 29th of
+/// February after a century which is not a leap year
+const DAYS_TO_SEARCH: i64 = 366 * 8 + 1;
+
+/// Standard 5-field cron expression, or a 6-field expression with leading seconds
+///
+/// Expressions are written as `30 7 * * 1-5` for 07:30 on weekdays, or `0 30 7 * * 1-5`
+/// with seconds. Fields support `*`, lists such as `1,15`, ranges such as `1-5`, steps such as
+/// `*/15` or `0-30/10`, and month and weekday names such as `JAN` or `MON-FRI`. Weekdays are
+/// numbered from 0 for Sunday, and 7 is also Sunday. The shorthands `@hourly`, `@daily`,
+/// `@weekly`, `@monthly` and `@yearly` are also supported.
+///
+/// As with most cron implementations, a day matches either the day of the month or the day of
+/// the week when both are restricted, and must match both when either begins with `*`.
+/// The expression is kept exactly as written, so it serializes back to the original text
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct CronExpression {
+    /// Expression exactly as written
+    source: String,
+    /// Sorted times of day at which the expression fires
+    times: Vec<Time>,
+    /// Days of the month on which the expression fires
+    days_of_month: Vec<u8>,
+    /// Months in which the expression fires
+    months: Vec<u8>,
+    /// Days of the week on which the expression fires, numbered from 0 for Sunday
+    days_of_week: Vec<u8>,
+    /// Whether a day matches when either the day of the month or day of the week matches
+    is_either_day: bool,
+}
+
+impl CronExpression {
+    /// Determines the next wall-clock time at which the expression fires, at or after the given
+    /// wall-clock time
+    pub(crate) fn get_next_time(&self, local_time: PrimitiveDateTime) -> Option<PrimitiveDateTime> {
+        let later_today = self
+            .times
+            .iter()
+            .find(|time| **time >= local_time.time())
+            .filter(|_| self.is_on_date(local_time.date()))
+            .map(|time| local_time.replace_time(*time));
+
+        later_today.or_else(|| {
+            let first_time = self.times.first()?;
+
+            (1..=DAYS_TO_SEARCH)
+                .map_while(|days| local_time.date().checked_add(Duration::days(days)))
+                .find(|date| self.is_on_date(*date))
+                .map(|date| date.with_time(*first_time))
+        })
+    }
+
+    /// Counts the times at which the expression fired from the given wall-clock time up to
+    /// another wall-clock time, inclusively
+    pub(crate) fn count_times(
+        &self,
+        start_time: PrimitiveDateTime,
+        local_time: PrimitiveDateTime,
+    ) -> usize {
+        if local_time < start_time {
+            return 0;
+        }
+
+        let count_times_on = |date: Date, is_counted: &dyn Fn(&Time) -> bool| {
+            if self.is_on_date(date) {
+                self.times.iter().filter(|time| is_counted(time)).count()
+            } else {
+                0
+            }
+        };
+
+        if start_time.date() == local_time.date() {
+            return count_times_on(start_time.date(), &|time| {
+                start_time.time() <= *time && *time <= local_time.time()
+            });
+        }
+
+        // Full days between the first and last day fire at every time of day
+        let full_days = (1..(local_time.date() - start_time.date()).whole_days())
+            .filter_map(|days| start_time.date().checked_add(Duration::days(days)))
+            .filter(|date| self.is_on_date(*date))
+            .count();
+
+        count_times_on(start_time.date(), &|time| start_time.time() <= *time)
+            + full_days.saturating_mul(self.times.len())
+            + count_times_on(local_time.date(), &|time| *time <= local_time.time())
+    }
+
+    /// Whether the expression fires on the given date
+    fn is_on_date(&self, date: Date) -> bool {
+        let is_on_day_of_month = self.days_of_month.contains(&date.day());
+        let is_on_day_of_week = self
+            .days_of_week
+            .contains(&date.weekday().number_days_from_sunday());
+
+        let is_on_day = if self.is_either_day {
+            is_on_day_of_month || is_on_day_of_week
+        } else {
+            is_on_day_of_month && is_on_day_of_week
+        };
+
+        is_on_day && self.months.contains(&u8::from(date.month()))
+    }
+}
+
+/// Parses a cron field of comma-separated values, ranges and steps within the given range
+///
+/// Names may be given in place of numbers, starting from the first number of the range
+fn parse_field(
+    field_name: &str,
+    value: &str,
+    range: RangeInclusive<u8>,
+    names: &[&str],
+) -> Result<Vec<u8>, String> {
+    let invalid = || format!("invalid {} `{}`", field_name, value);
+
+    let parse_number = |number: &str| {
+        names
+            .iter()
+            .position(|name| name.eq_ignore_ascii_case(number))
+            .and_then(|index| u8::try_from(index).ok())
+            .map(|index| range.start() + index)
+            .or_else(|| number.parse().ok())
+            .filter(|number| range.contains(number))
+            .ok_or_else(invalid)
+    };
+
+    let mut values = Vec::new();
+
+    for item in value.split(',') {
+        let (item_range, step) = match item.split_once('/') {
+            Some((item_range, step)) => (
+                item_range,
+                step.parse::<u8>()
+                    .ok()
+                    .filter(|step| *step > 0)
+                    .ok_or_else(invalid)?,
+            ),
+            None => (item, 1),
+        };
+
+        let (first, last) = match item_range.split_once('-') {
+            _ if item_range == "*" || item_range == "?" => (*range.start(), *range.end()),
+            Some((first, last)) => (parse_number(first)?, parse_number(last)?),
+            // A single value with a step repeats until the end of the range
+            None if item.contains('/') => (parse_number(item_range)?, *range.end()),
+            None => {
+                let number = parse_number(item_range)?;
+                (number, number)
+            }
+        };
+
+        if first > last {
+            return Err(invalid());
+        }
+
+        values.extend((first..=last).step_by(usize::from(step)));
+    }
+
+    values.sort_unstable();
+    values.dedup();
+    Ok(values)
+}
+
+impl FromStr for CronExpression {
+    type Err = String;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let expression = match source.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            expression => expression,
+        };
+
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+
+        let (seconds, fields) = match fields.len() {
+            5 => (vec![0], fields.as_slice()),
+            6 => (parse_field("second", fields[0], 0..=59, &[])?, &fields[1..]),
+            count => return Err(format!("expected 5 or 6 fields but found {}", count)),
+        };
+
+        let minutes = parse_field("minute", fields[0], 0..=59, &[])?;
+        let hours = parse_field("hour", fields[1], 0..=23, &[])?;
+        let days_of_month = parse_field("day of the month", fields[2], 1..=31, &[])?;
+        let months = parse_field("month", fields[3], 1..=12, &MONTHS)?;
+
+        // Sunday may be written as either 0 or 7
+        let mut days_of_week: Vec<u8> =
+            parse_field("day of the week", fields[4], 0..=7, &WEEKDAYS)?
+                .into_iter()
+                .map(|day| day % 7)
+                .collect();
+        days_of_week.sort_unstable();
+        days_of_week.dedup();
+
+        let is_either_day =
+            !fields[2].starts_with(['*', '?']) && !fields[4].starts_with(['*', '?']);
+
+        let mut times: Vec<Time> = hours
+            .iter()
+            .flat_map(|hour| minutes.iter().map(move |minute| (*hour, *minute)))
+            .flat_map(|(hour, minute)| {
+                seconds
+                    .iter()
+                    .filter_map(move |second| Time::from_hms(hour, minute, *second).ok())
+            })
+            .collect();
+        times.sort();
+
+        // Days of the month which no month has, such as the 30th of February, never fire
+        let fires_on_day_of_month = months.iter().any(|month| {
+            Month::try_from(*month).map_or(false, |month| {
+                days_of_month
+                    .iter()
+                    .any(|day| *day <= days_in_year_month(2000, month))
+            })
+        });
+
+        if !fires_on_day_of_month && !is_either_day {
+            return Err(String::from(
+                "the day of the month never occurs in those months",
+            ));
+        }
+
+        Ok(Self {
+            source: source.to_string(),
+            times,
+            days_of_month,
+            months,
+            days_of_week,
+            is_either_day,
+        })
+    }
+}
+
+impl TryFrom<String> for CronExpression {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value
+            .parse()
+            .map_err(|error| format!("Invalid cron expression `{}`: {}", value, error))
+    }
+}
+
+impl From<CronExpression> for String {
+    fn from(cron_expression: CronExpression) -> Self {
+        cron_expression.source
+    }
+}
+
+impl fmt::Display for CronExpression {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{}", self.source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::{datetime, time};
+
+    type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+    fn parse(value: &str) -> std::result::Result<CronExpression, String> {
+        CronExpression::try_from(value.to_string())
+    }
+
+    #[test]
+    fn it_parses_cron_expressions() -> Result<()> {
+        let expression = parse("30 7 * * 1-5")?;
+        assert_eq!(vec![time!(07:30)], expression.times);
+        assert_eq!(vec![1, 2, 3, 4, 5], expression.days_of_week);
+
+        let expression = parse("*/20 8-9 * JAN,dec sat,7")?;
+        assert_eq!(6, expression.times.len());
+        assert_eq!(vec![1, 12], expression.months);
+        assert_eq!(vec![0, 6], expression.days_of_week);
+
+        assert_eq!(vec![time!(00:00:15)], parse("15 0 0 * * *")?.times);
+        assert!(parse("@weekly").is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_rejects_invalid_cron_expressions() {
+        assert!(parse("30 7 * *").is_err());
+        assert!(parse("60 7 * * *").is_err());
+        assert!(parse("30 7 0 * *").is_err());
+        assert!(parse("30 7 * * 8").is_err());
+        assert!(parse("30 7 * * FRI-MON").is_err());
+        assert!(parse("30 7 * * */0").is_err());
+        assert!(parse("30 7 30 2 *").is_err());
+    }
+
+    #[test]
+    fn it_fires_at_the_next_matching_time() -> Result<()> {
+        let expression = parse("30 7 * * 1-5")?;
+
+        assert_eq!(
+            Some(datetime!(2026-10-16 07:30)),
+            expression.get_next_time(datetime!(2026-10-16 07:30))
+        );
+        assert_eq!(
+            Some(datetime!(2026-10-19 07:30)),
+            expression.get_next_time(datetime!(2026-10-16 07:31))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_fires_on_either_day_when_both_are_restricted() -> Result<()> {
+        let expression = parse("0 9 13 * FRI")?;
+
+        assert_eq!(
+            Some(datetime!(2026-10-13 09:00)),
+            expression.get_next_time(datetime!(2026-10-10 00:00))
+        );
+        assert_eq!(
+            Some(datetime!(2026-10-16 09:00)),
+            expression.get_next_time(datetime!(2026-10-14 00:00))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_counts_each_firing() -> Result<()> {
+        let expression = parse("0 9,17 * * 1-5")?;
+
+        assert_eq!(
+            0,
+            expression.count_times(datetime!(2026-10-12 00:00), datetime!(2026-10-12 08:59))
+        );
+        assert_eq!(
+            1,
+            expression.count_times(datetime!(2026-10-12 00:00), datetime!(2026-10-12 09:00))
+        );
+        assert_eq!(
+            10,
+            expression.count_times(datetime!(2026-10-12 00:00), datetime!(2026-10-18 23:59))
+        );
+        assert_eq!(
+            11,
+            expression.count_times(datetime!(2026-10-12 00:00), datetime!(2026-10-19 09:00))
+        );
+
+        Ok(())
+    }
+}
@@ -19,4 +19,34 @@ pub enum Error {
     /// The week of the year for a schedule's starting week is too large
     #[error("Week {0} of the year is too large, should be between 1 and 53 inclusive")]
     WeekTooLarge(i32),
+    /// The time zone for a schedule does not exist
+    #[error("Unknown time zone {0}")]
+    InvalidTimezone(String),
+    /// The week interval for a schedule is not a positive number
+    #[error("Invalid week interval {0}, should be 1 or more")]
+    InvalidWeekInterval(i32),
+    /// The end date for a schedule is not a valid date
+    #[error("Invalid end date {0}")]
+    InvalidUntil(String),
+    /// The occurrence limit for a schedule is not a positive number
+    #[error("Invalid occurrence limit {0}, should be 1 or more")]
+    InvalidOccurrenceLimit(i32),
+    /// The number of assignees for each occurrence of a schedule is not a positive number
+    #[error("Invalid number of assignees per occurrence {0}, should be 1 or more")]
+    InvalidAssigneesPerOccurrence(i32),
+    /// The rotation strategy for a schedule is not recognized
+    #[error("{0}")]
+    InvalidRotation(String),
+    /// The rotation offset for a schedule is negative
+    #[error("Invalid rotation offset {0}, should be 0 or more")]
+    InvalidRotationOffset(i32),
+    /// The handoff notice for a schedule is negative
+    #[error("Invalid handoff notice of {0} minutes, should be 0 or more")]
+    InvalidHandoffNotice(i32),
+    /// The catch-up policy for a schedule is not recognized
+    #[error("{0}")]
+    InvalidCatchUp(String),
+    /// The creation time for a schedule is not an RFC 3339 datetime
+    #[error("Invalid creation time {0}")]
+    InvalidCreated(String),
 }
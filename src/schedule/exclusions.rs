@@ -0,0 +1,185 @@
+//! Dates on which a schedule does not occur
+
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+use time::{format_description::FormatItem, macros::format_description, Date};
+
+/// Format of dates in a date range
+pub(crate) const DATE_FORMAT: &[FormatItem<'static>] = format_description!("[year]-[month]-[day]");
+
+/// Separator between the first and last dates of a date range
+const RANGE_SEPARATOR: &str = "..";
+
+/// Inclusive range of dates, written as `2026-12-25` or `2026-08-10..2026-08-16`
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct DateRange {
+    /// First date in the range
+    pub from: Date,
+    /// Last date in the range
+    pub to: Date,
+}
+
+impl DateRange {
+    /// Whether the given date is within this range
+    #[must_use]
+    pub fn contains(&self, date: Date) -> bool {
+        self.from <= date && date <= self.to
+    }
+}
+
+impl FromStr for DateRange {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid date or date range `{}`", value);
+        let parse = |date: &str| Date::parse(date.trim(), DATE_FORMAT).map_err(|_| invalid());
+
+        let (from, to) = match value.split_once(RANGE_SEPARATOR) {
+            Some((from, to)) => (parse(from)?, parse(to)?),
+            None => (parse(value)?, parse(value)?),
+        };
+
+        if from > to {
+            return Err(invalid());
+        }
+
+        Ok(Self { from, to })
+    }
+}
+
+impl TryFrom<String> for DateRange {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<DateRange> for String {
+    fn from(date_range: DateRange) -> Self {
+        date_range.to_string()
+    }
+}
+
+impl fmt::Display for DateRange {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let format = |date: Date| date.format(DATE_FORMAT).map_err(|_| fmt::Error);
+
+        if self.from == self.to {
+            write!(formatter, "{}", format(self.from)?)
+        } else {
+            write!(
+                formatter,
+                "{}{}{}",
+                format(self.from)?,
+                RANGE_SEPARATOR,
+                format(self.to)?
+            )
+        }
+    }
+}
+
+/// Dates on which a schedule does not occur
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Exclusions {
+    /// Excluded dates and date ranges, as wall-clock dates in the schedule's time zone
+    pub dates: Vec<DateRange>,
+    /// Whether excluded occurrences count towards the assignee rotation
+    ///
+    /// When false, the assignee of an excluded occurrence is assigned the next occurrence instead
+    pub advance_rotation: bool,
+}
+
+impl Exclusions {
+    /// Finds the excluded date range containing the given date, if any
+    #[must_use]
+    pub fn find(&self, date: Date) -> Option<&DateRange> {
+        self.dates
+            .iter()
+            .find(|date_range| date_range.contains(date))
+    }
+
+    /// Provides the excluded date ranges sorted and with overlapping ranges merged
+    pub(crate) fn merged(&self) -> Vec<DateRange> {
+        merge(self.dates.clone())
+    }
+}
+
+/// Sorts the given date ranges and merges any overlapping ranges
+pub(crate) fn merge(mut date_ranges: Vec<DateRange>) -> Vec<DateRange> {
+    date_ranges.sort_by_key(|date_range| date_range.from);
+
+    date_ranges
+        .into_iter()
+        .fold(Vec::new(), |mut merged: Vec<DateRange>, date_range| {
+            match merged.last_mut() {
+                Some(last) if date_range.from <= last.to => {
+                    last.to = last.to.max(date_range.to);
+                }
+                _ => merged.push(date_range),
+            }
+            merged
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::date;
+
+    #[test]
+    fn it_parses_dates_and_date_ranges() {
+        assert_eq!(
+            Ok(DateRange {
+                from: date!(2026 - 12 - 25),
+                to: date!(2026 - 12 - 25)
+            }),
+            "2026-12-25".parse()
+        );
+        assert_eq!(
+            Ok(DateRange {
+                from: date!(2026 - 08 - 10),
+                to: date!(2026 - 08 - 16)
+            }),
+            "2026-08-10..2026-08-16".parse()
+        );
+        assert!("2026-08-16..2026-08-10".parse::<DateRange>().is_err());
+        assert!("christmas".parse::<DateRange>().is_err());
+    }
+
+    #[test]
+    fn it_displays_dates_and_date_ranges() -> Result<(), String> {
+        assert_eq!("2026-12-25", "2026-12-25".parse::<DateRange>()?.to_string());
+        assert_eq!(
+            "2026-08-10..2026-08-16",
+            "2026-08-10..2026-08-16".parse::<DateRange>()?.to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_merges_overlapping_date_ranges() -> Result<(), String> {
+        let exclusions = Exclusions {
+            dates: vec![
+                "2026-08-14..2026-08-20".parse()?,
+                "2026-12-25".parse()?,
+                "2026-08-10..2026-08-16".parse()?,
+            ],
+            advance_rotation: false,
+        };
+
+        assert_eq!(
+            vec![
+                "2026-08-10..2026-08-20".parse::<DateRange>()?,
+                "2026-12-25".parse()?
+            ],
+            exclusions.merged()
+        );
+
+        Ok(())
+    }
+}
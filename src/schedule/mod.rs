@@ -1,8 +1,30 @@
 //! Models and functionality for scheduling reminders
 
+pub mod assignee;
+pub mod availability;
+pub mod catch_up;
+pub mod cron_expression;
 mod error;
+pub mod exclusions;
 pub mod model;
+pub mod month_day;
+pub mod notice;
 pub mod provider;
+pub mod recurrence_rule;
+pub(crate) mod rfc3339;
+pub mod rotation_strategy;
+pub mod shorthand;
+pub mod timezone;
 
+pub use assignee::Assignee;
+pub use availability::{Absence, Availability};
+pub use catch_up::CatchUp;
+pub use cron_expression::CronExpression;
 pub use error::Error;
+pub use exclusions::{DateRange, Exclusions};
+pub use month_day::MonthDay;
+pub use notice::Notice;
 pub use provider::Provider;
+pub use recurrence_rule::RecurrenceRule;
+pub use rotation_strategy::{Rotation, RotationStrategy};
+pub use timezone::Timezone;
@@ -1,62 +1,811 @@
-//! Data models for a stateless weekly schedule
+//! Data models for a stateless weekly or monthly schedule
 
-use serde::Serialize;
+use super::assignee::{spread_turns, Assignee};
+use super::availability::{Assignment, Availability, Substitution};
+use super::rotation_strategy::{Fair, Turn};
+use super::{
+    CatchUp, CronExpression, Exclusions, MonthDay, Notice, RecurrenceRule, Rotation, Timezone,
+};
+use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 use std::collections::{BTreeMap, HashMap};
 use std::convert::TryFrom;
-use time::{Duration, OffsetDateTime, Time, Weekday};
+use std::num::NonZeroU32;
+use time::{Date, Duration, Month, OffsetDateTime, PrimitiveDateTime, Time, Weekday};
 
 /// Mapping of weekdays to a list of times
 pub type WeeklyTimes = HashMap<Weekday, Vec<Time>>;
 
+/// Mapping of days of the month to a list of times
+pub type MonthlyTimes = HashMap<MonthDay, Vec<Time>>;
+
+/// Mapping of occurrence times to the assignees replacing the rotation for that occurrence
+pub type Overrides = HashMap<OffsetDateTime, Vec<i32>>;
+
+/// Sorted mapping of past occurrence times to the assignees who were assigned to them
+pub type History = BTreeMap<OffsetDateTime, Vec<i32>>;
+
+/// Mapping of assignee uids to their share of the occurrences, for assignees without a weight of 1
+pub type Weights = BTreeMap<i32, u32>;
+
 /// Sorted mapping of weekdays to a list of times
 type SortedWeeklyTimes = BTreeMap<u8, Vec<Time>>;
 
-/// Stateless weekly schedule with support for rotating assignees
-#[derive(Debug, Eq, PartialEq, Serialize)]
+/// Number of months to search for the next time of a monthly schedule
+const MONTHS_TO_SEARCH: usize = 13;
+
+/// Number of upcoming occurrences to search for a change of assignees
+const HANDOFF_SEARCH_LIMIT: usize = 1000;
+
+/// Scheduled times of day on recurring days, or a single scheduled datetime
+///
+/// Serialized as a JSON object of weekdays or days of the month to arrays of times,
+/// such as `{"Monday":["10:30:00"]}` or `{"1":["09:00:00"],"last Friday":["18:00:00"]}`,
+/// as an RFC 3339 datetime such as `{"once":"2026-11-03T14:00:00+01:00"}`,
+/// as an iCalendar recurrence rule such as `{"rrule":"FREQ=WEEKLY;BYDAY=MO,TH;BYHOUR=9"}`,
+/// or as a cron expression such as `{"cron":"30 7 * * 1-5"}`
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum Recurrence {
+    /// Times of day on weekdays, recurring every week
+    Weekly(WeeklyTimes),
+    /// Times of day on days of the month, recurring every month
+    Monthly(MonthlyTimes),
+    /// Single datetime which does not recur
+    Once {
+        /// Datetime at which the schedule occurs
+        #[serde(rename = "once", with = "super::rfc3339")]
+        time: OffsetDateTime,
+    },
+    /// Times given by an iCalendar recurrence rule
+    Rule {
+        /// Recurrence rule, kept exactly as written
+        #[serde(rename = "rrule")]
+        rule: RecurrenceRule,
+    },
+    /// Times given by a cron expression
+    Cron {
+        /// Cron expression, kept exactly as written
+        cron: CronExpression,
+    },
+}
+
+impl Recurrence {
+    /// Whether the schedule occurs only once
+    #[must_use]
+    pub fn is_once(&self) -> bool {
+        matches!(self, Self::Once { .. })
+    }
+
+    /// Whether a recurring schedule ends because of its recurrence rule
+    #[must_use]
+    pub fn has_end(&self) -> bool {
+        match self {
+            Self::Rule { rule } => rule.has_end(),
+            _ => false,
+        }
+    }
+}
+
+/// Stateless weekly or monthly schedule with support for rotating assignees
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 pub struct Schedule {
-    /// Scheduled times of day throughout the week
-    pub(crate) weekly_times: WeeklyTimes,
+    /// Scheduled times of day throughout the week or month
+    #[serde(rename = "schedule")]
+    pub(crate) recurrence: Recurrence,
     /// Sorted array of scheduled weekdays, for internal use
     sorted_weekdays: SortedWeeklyTimes,
     /// Beginning of the week in which the schedule started
     pub(crate) start_date: OffsetDateTime,
+    /// When the schedule was created, if it was recorded, for internal use
+    #[serde(skip_serializing)]
+    pub(crate) created: Option<OffsetDateTime>,
     /// Assignee ids in order of assignment
     pub(crate) assignees: Vec<i32>,
+    /// Share of the occurrences for each assignee, when not 1
+    #[serde(skip_serializing_if = "Weights::is_empty")]
+    pub(crate) weights: Weights,
+    /// Time zone in which the scheduled times of day are interpreted
+    pub(crate) timezone: Timezone,
+    /// Number of weeks between each week in which a weekly schedule occurs
+    pub(crate) week_interval: u32,
+    /// Dates on which the schedule does not occur
+    pub(crate) exclusions: Exclusions,
+    /// Last date on which the schedule occurs, if the schedule ends
+    pub(crate) until: Option<Date>,
+    /// Maximum number of times the schedule occurs, if the schedule ends
+    pub(crate) occurrence_limit: Option<u32>,
+    /// Number of assignees for each occurrence
+    pub(crate) assignees_per_occurrence: u32,
+    /// Strategy for rotating through the assignees
+    pub(crate) rotation: Rotation,
+    /// Number of turns the rotation is moved along by
+    #[serde(skip_serializing_if = "is_zero")]
+    pub(crate) rotation_offset: u32,
+    /// How long before a change of assignees it is announced, if it is announced
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) handoff_notice: Option<Notice>,
+    /// How occurrences missed while the scheduler wasn't running are handled
+    pub(crate) catch_up: CatchUp,
+}
+
+/// Change of assignees from one occurrence of a schedule to the next
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Handoff {
+    /// First occurrence assigned to the incoming assignees
+    pub time: OffsetDateTime,
+    /// Assignees of this occurrence who were not assigned to the previous one
+    pub incoming: Vec<i32>,
+    /// Assignees of the previous occurrence who are not assigned to this one
+    pub outgoing: Vec<i32>,
 }
 
 impl Schedule {
     /// Creates a new stateless schedule starting from the given week
     ///
+    /// Scheduled times are interpreted in UTC unless a time zone is set with `with_timezone`
+    ///
     /// # Errors
     ///
     /// When the given start week is invalid or ambiguous
     #[must_use]
     pub fn new(weekly_times: WeeklyTimes, start_date: OffsetDateTime, assignees: Vec<i32>) -> Self {
-        let sorted_weekdays = weekly_times
-            .iter()
-            .map(|(weekday, times)| (weekday.number_from_monday(), times.clone()))
-            .collect();
+        Self::from_recurrence(Recurrence::Weekly(weekly_times), start_date, assignees)
+    }
+
+    /// Creates a new stateless weekly or monthly schedule starting from the given week
+    #[must_use]
+    pub fn from_recurrence(
+        recurrence: Recurrence,
+        start_date: OffsetDateTime,
+        assignees: Vec<i32>,
+    ) -> Self {
+        let sorted_weekdays = match &recurrence {
+            Recurrence::Weekly(weekly_times) => weekly_times
+                .iter()
+                .map(|(weekday, times)| (weekday.number_from_monday(), times.clone()))
+                .collect(),
+            Recurrence::Monthly(_)
+            | Recurrence::Once { .. }
+            | Recurrence::Rule { .. }
+            | Recurrence::Cron { .. } => SortedWeeklyTimes::new(),
+        };
 
         Schedule {
-            weekly_times,
+            recurrence,
             sorted_weekdays,
             start_date,
+            created: None,
             assignees,
+            weights: Weights::new(),
+            timezone: Timezone::default(),
+            week_interval: 1,
+            exclusions: Exclusions::default(),
+            until: None,
+            occurrence_limit: None,
+            assignees_per_occurrence: 1,
+            rotation: Rotation::default(),
+            rotation_offset: 0,
+            handoff_notice: None,
+            catch_up: CatchUp::default(),
+        }
+    }
+
+    /// Interprets the scheduled times of day as wall-clock times in the given time zone
+    #[must_use]
+    pub fn with_timezone(mut self, timezone: Timezone) -> Self {
+        self.timezone = timezone;
+        self
+    }
+
+    /// Only fires a weekly schedule every given number of weeks, counting from the start week
+    ///
+    /// An interval of 2 fires in the start week, skips the following week, and so on.
+    /// Monthly schedules are unaffected by the week interval
+    #[must_use]
+    pub fn with_week_interval(mut self, week_interval: NonZeroU32) -> Self {
+        self.week_interval = week_interval.get();
+        self
+    }
+
+    /// Skips scheduled times on the excluded dates
+    #[must_use]
+    pub fn with_exclusions(mut self, exclusions: Exclusions) -> Self {
+        self.exclusions = exclusions;
+        self
+    }
+
+    /// Ends the schedule after the given wall-clock date in the schedule's time zone
+    #[must_use]
+    pub fn with_until(mut self, until: Date) -> Self {
+        self.until = Some(until);
+        self
+    }
+
+    /// Records when the schedule was created, so times earlier in the start week don't count
+    /// towards its occurrence limit
+    #[must_use]
+    pub fn with_created(mut self, created: OffsetDateTime) -> Self {
+        self.created = Some(created);
+        self
+    }
+
+    /// Ends the schedule after it occurs the given number of times, not counting excluded dates
+    /// since the schedule was created
+    #[must_use]
+    pub fn with_occurrence_limit(mut self, occurrence_limit: NonZeroU32) -> Self {
+        self.occurrence_limit = Some(occurrence_limit.get());
+        self
+    }
+
+    /// Assigns the given number of assignees to each occurrence, rotating through the assignees
+    /// that many at a time
+    ///
+    /// Occurrences never have more assignees than the schedule, so each assignee is assigned at
+    /// most once per occurrence
+    #[must_use]
+    pub fn with_assignees_per_occurrence(mut self, assignees_per_occurrence: NonZeroU32) -> Self {
+        self.assignees_per_occurrence = assignees_per_occurrence.get();
+        self
+    }
+
+    /// Rotates through the assignees using the given strategy, instead of rotating on every
+    /// occurrence
+    #[must_use]
+    pub fn with_rotation(mut self, rotation: Rotation) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Moves the rotation along by the given number of turns
+    #[must_use]
+    pub fn with_rotation_offset(mut self, rotation_offset: u32) -> Self {
+        self.rotation_offset = rotation_offset;
+        self
+    }
+
+    /// Announces each change of assignees the given length of time before the first occurrence
+    /// assigned to the incoming assignees
+    #[must_use]
+    pub fn with_handoff_notice(mut self, handoff_notice: Notice) -> Self {
+        self.handoff_notice = Some(handoff_notice);
+        self
+    }
+
+    /// Handles occurrences which were missed while the scheduler wasn't running with the given
+    /// policy, instead of skipping them
+    #[must_use]
+    pub fn with_catch_up(mut self, catch_up: CatchUp) -> Self {
+        self.catch_up = catch_up;
+        self
+    }
+
+    /// Moves the rotation along so the next occurrences after the given datetime are assigned
+    /// the same as in the given schedule, as far as the assignees allow
+    ///
+    /// This keeps the rotation in place when a schedule is edited. The offset matching the most
+    /// upcoming assignees is used, or no offset when none of them are still assigned
+    #[must_use]
+    pub fn with_rotation_continued_from(
+        mut self,
+        previous: &Schedule,
+        current_time: OffsetDateTime,
+    ) -> Self {
+        let upcoming = |schedule: &Schedule| -> Vec<Vec<i32>> {
+            let mut from = Some(current_time);
+            std::iter::from_fn(|| {
+                let time = schedule.get_next_time(from?)?;
+                from = time.checked_add(Duration::NANOSECOND);
+                Some(schedule.get_assignees(time))
+            })
+            .take(2)
+            .collect()
+        };
+
+        let expected = upcoming(previous);
+        let turn_count = self
+            .assignees
+            .iter()
+            .map(|assignee| self.get_weight(*assignee))
+            .sum();
+        let mut best = (0, (0, false));
+
+        for offset in 0..turn_count {
+            self.rotation_offset = offset;
+
+            let assignees = upcoming(&self);
+            let matches = assignees
+                .iter()
+                .zip(&expected)
+                .filter(|(assignees, expected)| assignees == expected)
+                .count();
+            let is_next_matching = assignees.first().and_then(|assignees| assignees.first())
+                == expected.first().and_then(|expected| expected.first());
+
+            if (matches, is_next_matching) > best.1 {
+                best = (offset, (matches, is_next_matching));
+            }
+        }
+
+        self.rotation_offset = best.0;
+        self
+    }
+
+    /// Replaces an assignee with another assignee who takes over their weight, or removes them
+    /// when no replacement is given. Upcoming occurrences after the given datetime stay with
+    /// the same assignees, as far as the remaining assignees allow
+    ///
+    /// Replacing an assignee with someone who is already assigned removes them instead
+    #[must_use]
+    pub fn with_assignee_replaced(
+        &self,
+        assignee: i32,
+        replacement: Option<i32>,
+        current_time: OffsetDateTime,
+    ) -> Self {
+        let mut schedule = self.clone();
+        let weight = schedule.weights.remove(&assignee);
+
+        match replacement.filter(|replacement| !self.assignees.contains(replacement)) {
+            Some(replacement) => {
+                for uid in &mut schedule.assignees {
+                    if *uid == assignee {
+                        *uid = replacement;
+                    }
+                }
+
+                if let Some(weight) = weight {
+                    schedule.weights.insert(replacement, weight);
+                }
+            }
+            None => schedule.assignees.retain(|uid| *uid != assignee),
         }
+
+        schedule.with_rotation_continued_from(self, current_time)
+    }
+
+    /// Gives each assignee a share of the occurrences proportional to their weight, instead of
+    /// an equal share. Turns are spread out evenly, and assignees without a weight have a
+    /// weight of 1
+    #[must_use]
+    pub fn with_weights(mut self, weights: Weights) -> Self {
+        self.weights = weights
+            .into_iter()
+            .filter(|(_, weight)| *weight > 1)
+            .collect();
+        self
+    }
+
+    /// Provides the assignees in order of assignment, along with their weights
+    #[must_use]
+    pub fn weighted_assignees(&self) -> Vec<Assignee> {
+        self.assignees
+            .iter()
+            .map(|uid| Assignee {
+                uid: *uid,
+                weight: self.get_weight(*uid),
+            })
+            .collect()
     }
 
-    /// Determines the scheduled assignee for the given datetime
+    /// Whether the schedule has ended and will not occur at or after the given datetime
+    ///
+    /// One-off schedules end once their time has passed
+    #[must_use]
+    pub fn is_exhausted(&self, current_time: OffsetDateTime) -> bool {
+        (self.until.is_some()
+            || self.occurrence_limit.is_some()
+            || self.recurrence.is_once()
+            || self.recurrence.has_end())
+            && self.get_next_time(current_time).is_none()
+    }
+
+    /// Determines the first scheduled assignee for the given datetime
+    ///
+    /// An occurrence counts towards the rotation once its wall-clock time has passed
     #[must_use]
     pub fn get_assignee(&self, current_time: OffsetDateTime) -> i32 {
+        self.get_assignees(current_time)[0]
+    }
+
+    /// Determines all scheduled assignees for the given datetime, in order of assignment
+    ///
+    /// Each turn of the rotation moves along by the number of assignees per occurrence
+    #[must_use]
+    pub fn get_assignees(&self, current_time: OffsetDateTime) -> Vec<i32> {
+        self.walk_rotation(self.get_position(current_time))
+            .take(self.get_assignee_count())
+            .collect()
+    }
+
+    /// Determines the assignees for the given datetime, substituting anyone who is away
+    ///
+    /// Assignees who are away are covered by the next available assignees in the rotation.
+    /// Assignees who defer their turn while away are assigned the first turn after they return,
+    /// and the rest of the rotation follows on from there. Overrides replace the assignees
+    /// entirely when the given datetime is exactly the time of an overridden occurrence
+    ///
+    /// Fair rotations choose from the available assignees using the history instead, so
+    /// assignees who are away simply catch up on their turns once they return
+    #[must_use]
+    pub fn get_assignment(
+        &self,
+        current_time: OffsetDateTime,
+        availability: &Availability,
+        overrides: &Overrides,
+        history: &History,
+    ) -> Assignment {
+        if let Some(assignees) = overrides.get(&current_time) {
+            return Assignment {
+                assignees: assignees.clone(),
+                substitutions: Vec::new(),
+                is_override: true,
+            };
+        }
+
+        if let Rotation::Fair(fair) = self.rotation {
+            return Assignment {
+                assignees: self.get_fair_assignees(current_time, fair, availability, history),
+                ..Assignment::default()
+            };
+        }
+
+        let date = self.timezone.to_local(current_time).date();
+        let position = self
+            .get_position(current_time)
+            .saturating_sub(self.count_deferred_turns(current_time, availability));
+
+        let count = self.get_assignee_count();
+        let scheduled: Vec<i32> = self.walk_rotation(position).take(count).collect();
+        let mut substitutes = self
+            .walk_rotation(position)
+            .skip(count)
+            .filter(|assignee| availability.is_available(*assignee, date));
+
+        let mut assignment = Assignment::default();
+        for assignee in scheduled {
+            if availability.is_available(assignee, date) {
+                assignment.assignees.push(assignee);
+            } else if let Some(substitute) = substitutes.next() {
+                assignment.assignees.push(substitute);
+                assignment.substitutions.push(Substitution {
+                    absent: assignee,
+                    substitute,
+                });
+            } else {
+                // Nobody is available to cover, so the assignment stands
+                assignment.assignees.push(assignee);
+            }
+        }
+
+        assignment
+    }
+
+    /// Determines the assignees with the fewest assignments in the history within the window
+    /// of a fair rotation, preferring assignees who are available on the given datetime
+    ///
+    /// Assignments are counted relative to each assignee's weight. Ties go to whoever was
+    /// assigned least recently, then to whoever comes first
+    fn get_fair_assignees(
+        &self,
+        current_time: OffsetDateTime,
+        fair: Fair,
+        availability: &Availability,
+        history: &History,
+    ) -> Vec<i32> {
+        let date = self.timezone.to_local(current_time).date();
+        let window_start = current_time - Duration::days(i64::from(fair.window_days));
+        let mut assignees: Vec<(bool, u64, Option<OffsetDateTime>, i32)> = self
+            .assignees
+            .iter()
+            .map(|assignee| {
+                let is_assigned =
+                    |(_, assignees): &(&OffsetDateTime, &Vec<i32>)| assignees.contains(assignee);
+
+                let count = history
+                    .range(window_start..current_time)
+                    .filter(is_assigned)
+                    .count();
+                let count = u64::try_from(count).unwrap_or(u64::MAX);
+                let last_assigned = history
+                    .range(..current_time)
+                    .rev()
+                    .find(is_assigned)
+                    .map(|(time, _)| *time);
+
+                let is_away = !availability.is_available(*assignee, date);
+                (is_away, count, last_assigned, *assignee)
+            })
+            .collect();
+
+        // Unavailable assignees sort last, and are only assigned when nobody else can be.
+        // The sort is stable, so assignees who are otherwise tied stay in order
+        assignees.sort_by(|first, second| {
+            let (first_away, first_count, first_assigned, first_uid) = *first;
+            let (second_away, second_count, second_assigned, second_uid) = *second;
+            let first_share = first_count * u64::from(self.get_weight(second_uid));
+            let second_share = second_count * u64::from(self.get_weight(first_uid));
+
+            (first_away, first_share, first_assigned).cmp(&(
+                second_away,
+                second_share,
+                second_assigned,
+            ))
+        });
+        assignees
+            .into_iter()
+            .take(self.get_assignee_count())
+            .map(|(_, _, _, assignee)| assignee)
+            .collect()
+    }
+
+    /// Share of the occurrences for the given assignee
+    fn get_weight(&self, assignee: i32) -> u32 {
+        self.weights.get(&assignee).copied().unwrap_or(1).max(1)
+    }
+
+    /// Number of assignees for each occurrence, which is at most the number of assignees
+    fn get_assignee_count(&self) -> usize {
+        usize::try_from(self.assignees_per_occurrence)
+            .unwrap_or(usize::MAX)
+            .min(self.assignees.len())
+    }
+
+    /// Determines the position in the rotation for the given datetime, before deferred turns
+    fn get_position(&self, current_time: OffsetDateTime) -> usize {
+        self.rotation
+            .strategy()
+            .get_position(self.get_turn(current_time))
+            .wrapping_add(usize::try_from(self.rotation_offset).unwrap_or(0))
+    }
+
+    /// Iterates over the assignees in order of assignment from the given position in the
+    /// rotation, with each assignee appearing once
+    ///
+    /// Each turn of the rotation moves along by the number of assignees per occurrence.
+    /// Weighted assignees take as many turns in each round of the rotation as their weight
+    fn walk_rotation(&self, position: usize) -> impl Iterator<Item = i32> + '_ {
+        let strategy = self.rotation.strategy();
+        let turns = spread_turns(
+            &self
+                .assignees
+                .iter()
+                .map(|assignee| self.get_weight(*assignee))
+                .collect::<Vec<_>>(),
+        );
+        let turn_count = turns.len();
+        let first_position = position.wrapping_mul(self.get_assignee_count());
+        let mut indices = Vec::with_capacity(self.assignees.len());
+
+        // Strategies may repeat an assignee between turns, so skip anyone already assigned
+        (0..turn_count.saturating_mul(2))
+            .map(move |offset| {
+                turns[strategy.get_index(first_position.wrapping_add(offset), turn_count)]
+            })
+            .filter(move |index| {
+                let is_new = !indices.contains(index);
+                if is_new {
+                    indices.push(*index);
+                }
+                is_new
+            })
+            .map(move |index| self.assignees[index])
+    }
+
+    /// Counts the turns deferred by assignees who were away, up to the given datetime
+    ///
+    /// A turn is deferred when an assignee who defers their turn is scheduled while away,
+    /// and the deferral takes effect once the rotation moves on to the next turn
+    fn count_deferred_turns(
+        &self,
+        current_time: OffsetDateTime,
+        availability: &Availability,
+    ) -> usize {
+        let mut deferred_turns = 0;
+        // Position of the turn being deferred, until the rotation moves on
+        let mut deferred_position = None;
+
+        for dates in availability.deferred_dates(&self.assignees) {
+            let from = dates.from.max(self.start_date.date()).midnight();
+            let mut next_time = self.get_next_time(self.timezone.from_local(from));
+
+            while let Some(time) = next_time.filter(|time| {
+                *time <= current_time && self.timezone.to_local(*time).date() <= dates.to
+            }) {
+                let position = self.get_position(time);
+                if deferred_position.map_or(false, |deferred| deferred != position) {
+                    deferred_turns += 1;
+                    deferred_position = None;
+                }
+
+                let date = self.timezone.to_local(time).date();
+                let is_deferred = self
+                    .walk_rotation(position.saturating_sub(deferred_turns))
+                    .take(self.get_assignee_count())
+                    .any(|assignee| {
+                        availability
+                            .find(assignee, date)
+                            .map_or(false, |absence| absence.defer_turn)
+                    });
+
+                if is_deferred {
+                    deferred_position = Some(position);
+                }
+
+                next_time = time
+                    .checked_add(Duration::NANOSECOND)
+                    .and_then(|time| self.get_next_time(time));
+            }
+        }
+
+        let position = self.get_position(current_time);
+        deferred_turns
+            + usize::from(deferred_position.map_or(false, |deferred| deferred != position))
+    }
+
+    /// Determines the turn of the rotation for the given datetime
+    fn get_turn(&self, current_time: OffsetDateTime) -> Turn {
+        let local_time = self.timezone.to_local(current_time);
+        let local_time = PrimitiveDateTime::new(local_time.date(), local_time.time());
+
+        let mut elapsed_times = self.count_times(local_time);
+
+        // Excluded times are only counted if they advance the rotation
+        if !self.exclusions.advance_rotation {
+            elapsed_times = elapsed_times.saturating_sub(self.count_excluded_times(local_time));
+        }
+
+        // Days and weeks before the start week count as the first turn
+        let elapsed_days =
+            usize::try_from((local_time.date() - self.start_date.date()).whole_days()).unwrap_or(0);
+        let week_interval = usize::try_from(self.week_interval).unwrap_or(usize::MAX);
+
+        Turn {
+            // Subtract 1 from the value so the first occurrence is the first turn
+            occurrence: elapsed_times.saturating_sub(1),
+            week: elapsed_days / 7 / week_interval,
+            day: elapsed_days,
+        }
+    }
+
+    /// Counts the scheduled times from the start week up to the given wall-clock time
+    fn count_times(&self, local_time: PrimitiveDateTime) -> usize {
+        match &self.recurrence {
+            Recurrence::Weekly(weekly_times) => self.count_weekly_times(weekly_times, local_time),
+            Recurrence::Monthly(monthly_times) => {
+                self.count_monthly_times(monthly_times, local_time)
+            }
+            Recurrence::Once { time } => {
+                let time = self.timezone.to_local(*time);
+                usize::from(PrimitiveDateTime::new(time.date(), time.time()) <= local_time)
+            }
+            Recurrence::Rule { rule } => {
+                let rule_start = self.get_rule_start();
+                let start_time = self.start_date.date().midnight();
+
+                // Times of the rule before the start week don't count
+                let times_before = if start_time > rule_start {
+                    rule.count_times(
+                        rule_start,
+                        start_time - Duration::NANOSECOND,
+                        &self.timezone,
+                    )
+                } else {
+                    0
+                };
+
+                rule.count_times(rule_start, local_time, &self.timezone)
+                    .saturating_sub(times_before)
+            }
+            Recurrence::Cron { cron } => {
+                cron.count_times(self.start_date.date().midnight(), local_time)
+            }
+        }
+    }
+
+    /// Counts the scheduled times on excluded dates from the start week up to the given
+    /// wall-clock time
+    fn count_excluded_times(&self, local_time: PrimitiveDateTime) -> usize {
+        let start_time = self.start_date.date().midnight();
+
+        self.exclusions
+            .merged()
+            .into_iter()
+            .map(|date_range| {
+                // Wall-clock times from the start of the first date to the end of the last date
+                let from = date_range.from.midnight().max(start_time);
+                let to = date_range
+                    .to
+                    .next_day()
+                    .map_or(local_time, |date| date.midnight() - Duration::NANOSECOND)
+                    .min(local_time);
+
+                (from, to)
+            })
+            .filter(|(from, to)| from <= to)
+            .map(|(from, to)| {
+                // Times before the start time are never counted
+                let times_before = if from > start_time {
+                    self.count_times(from - Duration::NANOSECOND)
+                } else {
+                    0
+                };
+
+                self.count_times(to).saturating_sub(times_before)
+            })
+            .sum()
+    }
+
+    /// Counts the scheduled times which were not excluded from the start week up to the given
+    /// wall-clock time
+    fn count_occurred_times(&self, local_time: PrimitiveDateTime) -> usize {
+        if local_time < self.start_date.date().midnight() {
+            return 0;
+        }
+
+        self.count_times(local_time)
+            .saturating_sub(self.count_excluded_times(local_time))
+    }
+
+    /// Determines the wall-clock time at which a recurrence rule starts, which is when the
+    /// schedule was created to the second, or the start of the start week if that wasn't recorded
+    fn get_rule_start(&self) -> PrimitiveDateTime {
+        self.created.map_or_else(
+            || self.start_date.date().midnight(),
+            |created| {
+                let created = self.timezone.to_local(created);
+                PrimitiveDateTime::new(created.date(), created.time())
+                    - Duration::nanoseconds(i64::from(created.nanosecond()))
+            },
+        )
+    }
+
+    /// Counts the scheduled times which were not excluded from the start week up to when the
+    /// schedule was created, which never occurred
+    fn count_times_before_created(&self) -> usize {
+        self.created.map_or(0, |created| {
+            let created = self.timezone.to_local(created);
+            let created = PrimitiveDateTime::new(created.date(), created.time());
+            self.count_occurred_times(created - Duration::NANOSECOND)
+        })
+    }
+
+    /// Whether the given wall-clock time is after the end of the schedule
+    fn is_after_end(&self, local_time: PrimitiveDateTime) -> bool {
+        let is_after_until = self.until.map_or(false, |until| local_time.date() > until);
+
+        let is_after_limit = self.occurrence_limit.map_or(false, |occurrence_limit| {
+            let previous_times = self
+                .count_occurred_times(local_time - Duration::NANOSECOND)
+                .saturating_sub(self.count_times_before_created());
+            usize::try_from(occurrence_limit).map_or(false, |limit| previous_times >= limit)
+        });
+
+        is_after_until || is_after_limit
+    }
+
+    /// Counts the weekly scheduled times from the start week up to the given wall-clock time
+    fn count_weekly_times(
+        &self,
+        weekly_times: &WeeklyTimes,
+        current_time: PrimitiveDateTime,
+    ) -> usize {
         // Number of fully elapsed weeks since the start week
         // This value is capped if it exceeds `usize::MAX`
         let elapsed_weeks =
             usize::try_from((current_time.date() - self.start_date.date()).whole_weeks())
                 .unwrap_or(usize::MAX);
 
+        // Number of fully elapsed weeks in which the schedule occurred, rounding up
+        let week_interval = usize::try_from(self.week_interval).unwrap_or(usize::MAX);
+        let elapsed_active_weeks =
+            elapsed_weeks / week_interval + usize::from(elapsed_weeks % week_interval != 0);
+
+        // Whether the schedule occurs during this week
+        let is_active_week = elapsed_weeks % week_interval == 0;
+
         // Number of scheduled times in a full week
-        let times_in_full_week: usize = self.weekly_times.values().map(Vec::len).sum();
+        let times_in_full_week: usize = weekly_times.values().map(Vec::len).sum();
 
         // Number of fully elapsed time periods in this week
         let this_week_day = current_time.weekday();
@@ -65,6 +814,7 @@ impl Schedule {
             .iter()
             .map(|(week_day, times_in_day)| {
                 match week_day.cmp(&this_week_day.number_from_monday()) {
+                    _ if !is_active_week => 0,
                     Ordering::Less => times_in_day.len(),
                     Ordering::Equal => times_in_day
                         .iter()
@@ -75,351 +825,1691 @@ impl Schedule {
             })
             .sum();
 
-        // Subtract 1 from the value to obtain an array index
-        let index = (elapsed_weeks
+        elapsed_active_weeks
             .saturating_mul(times_in_full_week)
-            .saturating_add(times_in_this_week))
-        .saturating_sub(1);
+            .saturating_add(times_in_this_week)
+    }
+
+    /// Counts the monthly scheduled times from the start week up to the given wall-clock time
+    fn count_monthly_times(
+        &self,
+        monthly_times: &MonthlyTimes,
+        current_time: PrimitiveDateTime,
+    ) -> usize {
+        let start_time = self.start_date.date().midnight();
 
-        self.assignees[index % self.assignees.len()]
+        get_months_from(start_time.year(), start_time.month())
+            .take_while(|(year, month)| {
+                (*year, u8::from(*month)) <= (current_time.year(), u8::from(current_time.month()))
+            })
+            .map(|(year, month)| {
+                get_monthly_times_in(monthly_times, year, month)
+                    .into_iter()
+                    .filter(|time| *time >= start_time && *time <= current_time)
+                    .count()
+            })
+            .sum()
     }
 
     /// Calculates the duration until the next time on the schedule
+    ///
+    /// Scheduled times skipped by a daylight saving transition occur after the gap,
+    /// and scheduled times repeated by a daylight saving transition only occur once
     #[must_use]
     pub fn get_next_duration(&self, current_time: OffsetDateTime) -> Option<Duration> {
-        let this_week_day = current_time.weekday().number_from_monday();
+        self.get_next_time(current_time)
+            .map(|next_time| next_time - current_time)
+    }
+
+    /// Provides an iterator over scheduled times and their assignees, starting from the given
+    /// datetime. A time exactly at `from` is included as the first occurrence
+    ///
+    /// # Panics
+    ///
+    /// When iterated for a schedule without assignees, as with `get_assignee`
+    #[must_use]
+    pub fn occurrences(&self, from: OffsetDateTime) -> Occurrences {
+        Occurrences {
+            schedule: self,
+            from: Some(from),
+        }
+    }
+
+    /// Provides an iterator over scheduled times and their assignments, starting from the given
+    /// datetime. A time exactly at `from` is included as the first occurrence
+    ///
+    /// Each assignment is added to the given history before the next is determined, so fair
+    /// rotations account for the occurrences leading up to each one
+    #[must_use]
+    pub fn assignments<'a>(
+        &'a self,
+        from: OffsetDateTime,
+        availability: &'a Availability,
+        overrides: &'a Overrides,
+        history: History,
+    ) -> Assignments<'a> {
+        Assignments {
+            schedule: self,
+            from: Some(from),
+            availability,
+            overrides,
+            history,
+        }
+    }
+
+    /// Finds the first occurrence after the given datetime which is assigned to someone who
+    /// wasn't assigned to the occurrence before it
+    ///
+    /// Occurrences are assigned from `current_time` on, following the most recent occurrence in
+    /// the history. An occurrence without a previous assignment is never a handoff
+    #[must_use]
+    pub fn get_next_handoff(
+        &self,
+        current_time: OffsetDateTime,
+        after: OffsetDateTime,
+        availability: &Availability,
+        overrides: &Overrides,
+        history: History,
+    ) -> Option<Handoff> {
+        let mut previous = history
+            .range(..current_time)
+            .next_back()
+            .map(|(_, assignees)| assignees.clone());
+
+        self.assignments(current_time, availability, overrides, history)
+            .take(HANDOFF_SEARCH_LIMIT)
+            .find_map(|(time, assignment)| {
+                let outgoing = previous.replace(assignment.assignees.clone())?;
+                let incoming: Vec<_> = assignment
+                    .assignees
+                    .iter()
+                    .filter(|assignee| !outgoing.contains(assignee))
+                    .copied()
+                    .collect();
+
+                if time <= after || incoming.is_empty() {
+                    return None;
+                }
+
+                Some(Handoff {
+                    time,
+                    incoming,
+                    outgoing: outgoing
+                        .into_iter()
+                        .filter(|assignee| !assignment.assignees.contains(assignee))
+                        .collect(),
+                })
+            })
+    }
+
+    /// Determines which occurrences after the last notified occurrence and before the given
+    /// datetime are notified late, according to the catch-up policy
+    #[must_use]
+    pub fn get_missed_occurrences(
+        &self,
+        last_occurrence: OffsetDateTime,
+        current_time: OffsetDateTime,
+    ) -> Vec<OffsetDateTime> {
+        let from = match last_occurrence.checked_add(Duration::NANOSECOND) {
+            Some(from) => from,
+            None => return Vec::new(),
+        };
+
+        let missed = self
+            .occurrences(from)
+            .map(|(time, _)| time)
+            .take_while(|time| *time < current_time);
+
+        match self.catch_up {
+            CatchUp::All => missed.collect(),
+            CatchUp::Latest => missed.last().into_iter().collect(),
+            CatchUp::Skip => Vec::new(),
+        }
+    }
+
+    /// Determines the next scheduled time at or after the given datetime
+    fn get_next_time(&self, current_time: OffsetDateTime) -> Option<OffsetDateTime> {
+        if let Recurrence::Once { time } = self.recurrence {
+            let local_time = self.timezone.to_local(time);
+            let local_time = PrimitiveDateTime::new(local_time.date(), local_time.time());
+
+            return Some(time).filter(|time| {
+                *time >= current_time
+                    && self.exclusions.find(local_time.date()).is_none()
+                    && !self.is_after_end(local_time)
+            });
+        }
+
+        let local_time = self.timezone.to_local(current_time);
+        let mut local_time = PrimitiveDateTime::new(local_time.date(), local_time.time());
+
+        loop {
+            let next_local_time = self.get_next_local_time(local_time)?;
+            let next_time = self.timezone.from_local(next_local_time);
+
+            // Stop searching once past the until date, rather than skipping through exclusions
+            if self
+                .until
+                .map_or(false, |until| next_local_time.date() > until)
+            {
+                return None;
+            }
+
+            // Continue from the day after an excluded date range
+            if let Some(date_range) = self.exclusions.find(next_local_time.date()) {
+                local_time = date_range.to.next_day()?.midnight();
+                continue;
+            }
+
+            // Times in a repeated hour resolve to the first instance, which may have passed
+            if next_time >= current_time {
+                return Some(next_time).filter(|_| !self.is_after_end(next_local_time));
+            }
+
+            local_time = next_local_time + Duration::NANOSECOND;
+        }
+    }
+
+    /// Determines the next scheduled wall-clock time at or after the given wall-clock time
+    fn get_next_local_time(&self, local_time: PrimitiveDateTime) -> Option<PrimitiveDateTime> {
+        match &self.recurrence {
+            Recurrence::Weekly(_) => self.get_next_weekly_interval_time(local_time),
+            Recurrence::Monthly(monthly_times) => {
+                get_months_from(local_time.year(), local_time.month())
+                    .take(MONTHS_TO_SEARCH)
+                    .find_map(|(year, month)| {
+                        get_monthly_times_in(monthly_times, year, month)
+                            .into_iter()
+                            .find(|time| *time >= local_time)
+                    })
+            }
+            Recurrence::Once { .. } => None,
+            Recurrence::Rule { rule } => {
+                rule.get_next_time(self.get_rule_start(), local_time, &self.timezone)
+            }
+            Recurrence::Cron { cron } => cron.get_next_time(local_time),
+        }
+    }
+
+    /// Determines the next weekly wall-clock time at or after the given wall-clock time,
+    /// skipping weeks which are excluded by the week interval
+    fn get_next_weekly_interval_time(
+        &self,
+        mut local_time: PrimitiveDateTime,
+    ) -> Option<PrimitiveDateTime> {
+        let week_interval = i64::from(self.week_interval);
+
+        loop {
+            let next_time = self.get_next_weekly_time(local_time)?;
+
+            // Week index relative to the start week, negative for weeks before the start week
+            let week = (next_time.date() - self.start_date.date())
+                .whole_days()
+                .div_euclid(7);
+
+            let skipped_weeks = week.rem_euclid(week_interval);
+            if skipped_weeks == 0 {
+                return Some(next_time);
+            }
+
+            // Continue from the start of the next week in which the schedule occurs
+            local_time = self
+                .start_date
+                .date()
+                .checked_add(Duration::weeks(week + week_interval - skipped_weeks))?
+                .midnight();
+        }
+    }
+
+    /// Determines the next weekly wall-clock time at or after the given wall-clock time
+    fn get_next_weekly_time(&self, local_time: PrimitiveDateTime) -> Option<PrimitiveDateTime> {
+        let this_week_day = local_time.weekday().number_from_monday();
+
+        let (week_day, times_in_day) = self
+            .sorted_weekdays
+            .iter()
+            .find(|(week_day, times_in_day)| {
+                match week_day.cmp(&&this_week_day) {
+                    Ordering::Less => false,
+                    Ordering::Equal => {
+                        // Check if the next scheduled time is on the same weekday
+                        times_in_day.iter().any(|time| *time >= local_time.time())
+                    }
+                    Ordering::Greater => true,
+                }
+            })
+            .or_else(|| self.sorted_weekdays.iter().next())?;
+
+        // Determine the number of days that will elapse
+        let days = (i64::from(*week_day) - i64::from(this_week_day)).rem_euclid(7);
+
+        match times_in_day.iter().find(|time| **time >= local_time.time()) {
+            // Same day means the next time later in the day
+            Some(time) if days == 0 => Some(local_time.replace_time(*time)),
+            // Same day without a later time means the first time of the day next week
+            _ if days == 0 => times_in_day.get(0).and_then(|time| {
+                local_time
+                    .date()
+                    .checked_add(Duration::days(7))
+                    .map(|date| date.with_time(*time))
+            }),
+            // Different days means the first time of that day
+            _ => times_in_day.get(0).and_then(|time| {
+                local_time
+                    .date()
+                    .checked_add(Duration::days(days))
+                    .map(|date| date.with_time(*time))
+            }),
+        }
+    }
+}
+
+/// Provides an endless sequence of years and months, starting from the given month
+fn get_months_from(year: i32, month: Month) -> impl Iterator<Item = (i32, Month)> {
+    std::iter::successors(Some((year, month)), |(year, month)| match month {
+        Month::December => year.checked_add(1).map(|year| (year, Month::January)),
+        _ => Some((*year, month.next())),
+    })
+}
+
+/// Provides the sorted wall-clock times of a monthly schedule within the given month
+fn get_monthly_times_in(
+    monthly_times: &MonthlyTimes,
+    year: i32,
+    month: Month,
+) -> Vec<PrimitiveDateTime> {
+    let mut times: Vec<PrimitiveDateTime> = monthly_times
+        .iter()
+        .filter_map(|(month_day, times)| {
+            month_day
+                .date_in(year, month)
+                .map(|date| times.iter().map(move |time| date.with_time(*time)))
+        })
+        .flatten()
+        .collect();
+
+    times.sort();
+    times.dedup();
+    times
+}
+
+/// Whether a number is zero, for skipping serialization of unset offsets
+#[allow(clippy::trivially_copy_pass_by_ref)] // serde passes fields by reference
+fn is_zero(value: &u32) -> bool {
+    *value == 0
+}
+
+/// Iterator over the scheduled times of a schedule, paired with the assignee uid for each time
+pub struct Occurrences<'a> {
+    /// Schedule being iterated over
+    schedule: &'a Schedule,
+    /// Datetime from which to find the next occurrence, or `None` when exhausted
+    from: Option<OffsetDateTime>,
+}
+
+impl<'a> Iterator for Occurrences<'a> {
+    type Item = (OffsetDateTime, i32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let time = self.schedule.get_next_time(self.from?)?;
+
+        self.from = time.checked_add(Duration::NANOSECOND);
+
+        Some((time, self.schedule.get_assignee(time)))
+    }
+}
+
+/// Iterator over the scheduled times of a schedule, paired with the assignment for each time
+pub struct Assignments<'a> {
+    /// Schedule being iterated over
+    schedule: &'a Schedule,
+    /// Datetime from which to find the next occurrence, or `None` when exhausted
+    from: Option<OffsetDateTime>,
+    /// Absences of the assignees
+    availability: &'a Availability,
+    /// Assignees replacing the rotation for overridden occurrences
+    overrides: &'a Overrides,
+    /// Past assignments, including those already iterated over
+    history: History,
+}
+
+impl<'a> Iterator for Assignments<'a> {
+    type Item = (OffsetDateTime, Assignment);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let time = self.schedule.get_next_time(self.from?)?;
+
+        self.from = time.checked_add(Duration::NANOSECOND);
+
+        let assignment =
+            self.schedule
+                .get_assignment(time, self.availability, self.overrides, &self.history);
+        self.history.insert(time, assignment.assignees.clone());
+
+        Some((time, assignment))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schedule::Absence;
+    use time::macros::{datetime, time};
+    use time::Date;
+
+    type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+    fn week(year: i32, week: u8) -> Result<OffsetDateTime> {
+        Ok(Date::from_iso_week_date(year, week, Weekday::Monday)?
+            .midnight()
+            .assume_utc())
+    }
+
+    #[test]
+    fn it_caps_elapsed_weeks() -> Result<()> {
+        let schedule = Schedule::new(
+            vec![(Weekday::Monday, vec![time!(00:00:00)])]
+                .into_iter()
+                .collect(),
+            week(2020, 3)?,
+            vec![1, 2],
+        );
+
+        // Times in the past will overflow `usize`, so they will be capped
+        assert_eq!(1, schedule.get_assignee(week(2020, 1)?));
+        assert_eq!(1, schedule.get_assignee(week(2020, 2)?));
+        // 0 elapsed weeks starts with the first assignee
+        assert_eq!(1, schedule.get_assignee(week(2020, 3)?));
+        // 1 elapsed week continues as normal
+        assert_eq!(2, schedule.get_assignee(week(2020, 4)?));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_assigns_days_correctly_without_rollover() -> Result<()> {
+        let schedule = Schedule::new(
+            vec![
+                (Weekday::Monday, vec![time!(12:30)]),
+                (Weekday::Wednesday, vec![time!(12:30)]),
+                (Weekday::Friday, vec![time!(12:30)]),
+            ]
+            .into_iter()
+            .collect(),
+            week(2020, 3)?,
+            vec![1, 2, 3],
+        );
+
+        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-13 12:30:00 UTC)));
+        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-15 12:29:59 UTC)));
+        assert_eq!(2, schedule.get_assignee(datetime!(2020-01-15 12:30:00 UTC)));
+        assert_eq!(2, schedule.get_assignee(datetime!(2020-01-17 12:29:59 UTC)));
+        assert_eq!(3, schedule.get_assignee(datetime!(2020-01-17 12:30:00 UTC)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_assigns_days_correctly_with_rollover() -> Result<()> {
+        let schedule = Schedule::new(
+            vec![
+                (Weekday::Monday, vec![time!(12:30)]),
+                (Weekday::Wednesday, vec![time!(12:30)]),
+                (Weekday::Friday, vec![time!(12:30)]),
+            ]
+            .into_iter()
+            .collect(),
+            week(2020, 3)?,
+            vec![1, 2],
+        );
+
+        // First week
+        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-13 12:30:00 UTC)));
+        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-15 12:29:59 UTC)));
+        assert_eq!(2, schedule.get_assignee(datetime!(2020-01-15 12:30:00 UTC)));
+        assert_eq!(2, schedule.get_assignee(datetime!(2020-01-17 12:29:59 UTC)));
+        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-17 12:30:00 UTC)));
+
+        // Second week
+        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-20 12:29:59 UTC)));
+        assert_eq!(2, schedule.get_assignee(datetime!(2020-01-20 12:30:00 UTC)));
+        assert_eq!(2, schedule.get_assignee(datetime!(2020-01-22 12:29:59 UTC)));
+        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-22 12:30:00 UTC)));
+        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-24 12:29:59 UTC)));
+        assert_eq!(2, schedule.get_assignee(datetime!(2020-01-24 12:30:00 UTC)));
+
+        // Start of third week
+        assert_eq!(2, schedule.get_assignee(datetime!(2020-01-27 12:29:59 UTC)));
+        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-27 12:30:00 UTC)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_assigns_times_correctly_without_rollover() -> Result<()> {
+        let schedule = Schedule::new(
+            vec![(
+                Weekday::Monday,
+                vec![time!(10:30), time!(11:30), time!(12:30)],
+            )]
+            .into_iter()
+            .collect(),
+            week(2020, 3)?,
+            vec![1, 2, 3],
+        );
+
+        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-13 10:30:00 UTC)));
+        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-13 11:29:59 UTC)));
+        assert_eq!(2, schedule.get_assignee(datetime!(2020-01-13 11:30:00 UTC)));
+        assert_eq!(2, schedule.get_assignee(datetime!(2020-01-13 12:29:59 UTC)));
+        assert_eq!(3, schedule.get_assignee(datetime!(2020-01-13 12:30:00 UTC)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_assigns_times_correctly_with_rollover() -> Result<()> {
+        let schedule = Schedule::new(
+            vec![
+                (Weekday::Monday, vec![time!(10:30), time!(11:30)]),
+                (Weekday::Friday, vec![time!(12:30)]),
+            ]
+            .into_iter()
+            .collect(),
+            week(2020, 3)?,
+            vec![1, 2],
+        );
+
+        // First week
+        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-13 10:30:00 UTC)));
+        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-13 11:29:59 UTC)));
+        assert_eq!(2, schedule.get_assignee(datetime!(2020-01-13 11:30:00 UTC)));
+        assert_eq!(2, schedule.get_assignee(datetime!(2020-01-17 12:29:59 UTC)));
+        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-17 12:30:00 UTC)));
+
+        // Second week
+        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-20 10:29:59 UTC)));
+        assert_eq!(2, schedule.get_assignee(datetime!(2020-01-20 10:30:00 UTC)));
+        assert_eq!(2, schedule.get_assignee(datetime!(2020-01-20 11:29:59 UTC)));
+        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-20 11:30:00 UTC)));
+        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-24 12:29:59 UTC)));
+        assert_eq!(2, schedule.get_assignee(datetime!(2020-01-24 12:30:00 UTC)));
+
+        // Start of third week
+        assert_eq!(2, schedule.get_assignee(datetime!(2020-01-27 10:29:59 UTC)));
+        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-27 10:30:00 UTC)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_returns_none_without_next_duration() -> Result<()> {
+        let schedule = Schedule::new(vec![].into_iter().collect(), week(2020, 1)?, vec![]);
+
+        assert_eq!(None, schedule.get_next_duration(OffsetDateTime::now_utc()));
+        Ok(())
+    }
+
+    #[test]
+    fn it_returns_0_when_next_duration_is_now() -> Result<()> {
+        let schedule = Schedule::new(
+            vec![(Weekday::Monday, vec![time!(12:30)])]
+                .into_iter()
+                .collect(),
+            week(2020, 1)?,
+            vec![1],
+        );
+
+        assert_eq!(
+            Some(Duration::ZERO),
+            schedule.get_next_duration(datetime!(2020-01-06 12:30:00 UTC))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_returns_remaining_time_when_next_duration_is_same_day() -> Result<()> {
+        let schedule = Schedule::new(
+            vec![(Weekday::Monday, vec![time!(12:30)])]
+                .into_iter()
+                .collect(),
+            week(2020, 1)?,
+            vec![1],
+        );
+
+        assert_eq!(
+            Some(Duration::minutes(12 * 60 + 30)),
+            schedule.get_next_duration(datetime!(2020-01-06 00:00:00 UTC))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_returns_1_day_when_next_duration_is_next_day_at_same_time() -> Result<()> {
+        let schedule = Schedule::new(
+            vec![(Weekday::Monday, vec![time!(12:30)])]
+                .into_iter()
+                .collect(),
+            week(2020, 1)?,
+            vec![1],
+        );
+
+        assert_eq!(
+            Some(Duration::days(1)),
+            schedule.get_next_duration(datetime!(2020-01-05 12:30:00 UTC))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_returns_time_gap_when_next_duration_is_less_than_24h() -> Result<()> {
+        let schedule = Schedule::new(
+            vec![(Weekday::Monday, vec![time!(12:30)])]
+                .into_iter()
+                .collect(),
+            week(2020, 1)?,
+            vec![1],
+        );
+
+        assert_eq!(
+            Some(Duration::seconds(60 * 60 * 24 - 1)),
+            schedule.get_next_duration(datetime!(2020-01-05 12:30:01 UTC))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_returns_time_gap_when_next_duration_wraps_to_next_week() -> Result<()> {
+        let schedule = Schedule::new(
+            vec![(Weekday::Monday, vec![time!(12:30)])]
+                .into_iter()
+                .collect(),
+            week(2020, 1)?,
+            vec![1],
+        );
+
+        assert_eq!(
+            Some(Duration::seconds(60 * 60 * 24 * 7 - 1)),
+            schedule.get_next_duration(datetime!(2020-01-06 12:30:01 UTC))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_returns_time_gap_when_next_duration_is_a_future_day_of_the_week() -> Result<()> {
+        let schedule = Schedule::new(
+            vec![
+                (Weekday::Monday, vec![time!(10:30)]),
+                (Weekday::Friday, vec![time!(20:30)]),
+            ]
+            .into_iter()
+            .collect(),
+            week(2020, 1)?,
+            vec![1],
+        );
+
+        assert_eq!(
+            // Next time is 4 days and 10 hours minus 1 second
+            Some(Duration::seconds(60 * 60 * 24 * 4 + 60 * 60 * 10 - 1)),
+            schedule.get_next_duration(datetime!(2020-01-06 10:30:01 UTC))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_returns_time_gap_when_next_duration_is_a_past_day_of_the_week() -> Result<()> {
+        let schedule = Schedule::new(
+            vec![
+                (Weekday::Monday, vec![time!(10:30)]),
+                (Weekday::Friday, vec![time!(20:30)]),
+            ]
+            .into_iter()
+            .collect(),
+            week(2020, 1)?,
+            vec![1],
+        );
+
+        assert_eq!(
+            // Next time is 2 days and 14 hours minus 1 second
+            Some(Duration::seconds(60 * 60 * 24 * 2 + 60 * 60 * 14 - 1)),
+            schedule.get_next_duration(datetime!(2020-01-10 20:30:01 UTC))
+        );
+
+        Ok(())
+    }
+
+    fn berlin_schedule(times: Vec<Time>) -> Result<Schedule> {
+        Ok(Schedule::new(
+            vec![(Weekday::Sunday, times)].into_iter().collect(),
+            week(2020, 1)?,
+            vec![1, 2],
+        )
+        .with_timezone(Timezone::new("Europe/Berlin")?))
+    }
+
+    #[test]
+    fn it_interprets_times_in_the_schedule_timezone() -> Result<()> {
+        let schedule = berlin_schedule(vec![time!(08:00)])?;
+
+        // 08:00 in Berlin is 07:00 UTC in winter and 06:00 UTC in summer
+        assert_eq!(
+            Some(Duration::hours(1)),
+            schedule.get_next_duration(datetime!(2020-01-12 06:00:00 UTC))
+        );
+        assert_eq!(
+            Some(Duration::hours(1)),
+            schedule.get_next_duration(datetime!(2020-07-12 05:00:00 UTC))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_keeps_wall_clock_times_across_daylight_saving_transitions() -> Result<()> {
+        let schedule = berlin_schedule(vec![time!(08:00)])?;
+
+        // The day of the spring transition is an hour shorter
+        assert_eq!(
+            Some(Duration::hours(23)),
+            schedule.get_next_duration(datetime!(2020-03-28 07:00:00 UTC))
+        );
+
+        // The day of the autumn transition is an hour longer
+        assert_eq!(
+            Some(Duration::hours(25)),
+            schedule.get_next_duration(datetime!(2020-10-24 06:00:00 UTC))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_shifts_times_skipped_by_spring_forward() -> Result<()> {
+        let schedule = berlin_schedule(vec![time!(02:30)])?;
+
+        // Clocks skip from 02:00 to 03:00 (01:00 UTC), so 02:30 occurs at 03:30
+        assert_eq!(
+            Some(Duration::hours(1)),
+            schedule.get_next_duration(datetime!(2020-03-29 00:30:00 UTC))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_occurs_once_for_times_repeated_by_fall_back() -> Result<()> {
+        let schedule = berlin_schedule(vec![time!(02:30)])?;
+
+        // Clocks fall back from 03:00 to 02:00 (01:00 UTC), so 02:30 occurs in summer time
+        assert_eq!(
+            Some(Duration::minutes(30)),
+            schedule.get_next_duration(datetime!(2020-10-25 00:00:00 UTC))
+        );
+
+        // The repeated 02:30 in winter time does not occur again
+        assert_eq!(
+            Some(Duration::days(7) + Duration::minutes(30)),
+            schedule.get_next_duration(datetime!(2020-10-25 01:00:00 UTC))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_assigns_by_wall_clock_time_in_the_schedule_timezone() -> Result<()> {
+        let schedule = berlin_schedule(vec![time!(00:30)])?;
+
+        // 00:30 on Sunday in Berlin is still Saturday in UTC
+        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-11 23:29:59 UTC)));
+        assert_eq!(2, schedule.get_assignee(datetime!(2020-01-11 23:30:00 UTC)));
+        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-18 23:30:00 UTC)));
+
+        // 00:30 on Sunday in Berlin summer time is 22:30 UTC on Saturday
+        assert_eq!(2, schedule.get_assignee(datetime!(2020-07-04 22:29:59 UTC)));
+        assert_eq!(1, schedule.get_assignee(datetime!(2020-07-04 22:30:00 UTC)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_iterates_over_occurrences_with_assignees() -> Result<()> {
+        let schedule = Schedule::new(
+            vec![
+                (Weekday::Monday, vec![time!(10:30), time!(11:30)]),
+                (Weekday::Friday, vec![time!(12:30)]),
+            ]
+            .into_iter()
+            .collect(),
+            week(2020, 3)?,
+            vec![1, 2],
+        );
+
+        let occurrences: Vec<_> = schedule
+            .occurrences(datetime!(2020-01-13 10:30:00 UTC))
+            .take(5)
+            .collect();
+
+        assert_eq!(
+            vec![
+                (datetime!(2020-01-13 10:30:00 UTC), 1),
+                (datetime!(2020-01-13 11:30:00 UTC), 2),
+                (datetime!(2020-01-17 12:30:00 UTC), 1),
+                (datetime!(2020-01-20 10:30:00 UTC), 2),
+                (datetime!(2020-01-20 11:30:00 UTC), 1),
+            ],
+            occurrences
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_matches_assignees_to_occurrence_times() -> Result<()> {
+        let schedule = Schedule::new(
+            vec![
+                (Weekday::Monday, vec![time!(12:30)]),
+                (Weekday::Wednesday, vec![time!(12:30)]),
+            ]
+            .into_iter()
+            .collect(),
+            week(2020, 3)?,
+            vec![1, 2, 3],
+        );
+
+        for (time, assignee) in schedule
+            .occurrences(datetime!(2020-01-15 00:00:00 UTC))
+            .take(10)
+        {
+            assert_eq!(schedule.get_assignee(time), assignee);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_has_no_occurrences_without_times() -> Result<()> {
+        let schedule = Schedule::new(vec![].into_iter().collect(), week(2020, 1)?, vec![1]);
+
+        assert_eq!(None, schedule.occurrences(OffsetDateTime::now_utc()).next());
+        Ok(())
+    }
+
+    #[test]
+    fn it_has_one_occurrence_for_times_repeated_by_fall_back() -> Result<()> {
+        let schedule = berlin_schedule(vec![time!(02:30)])?;
+
+        let occurrences: Vec<_> = schedule
+            .occurrences(datetime!(2020-10-25 00:00:00 UTC))
+            .map(|(time, _)| time)
+            .take(2)
+            .collect();
+
+        assert_eq!(
+            vec![
+                datetime!(2020-10-25 00:30:00 UTC),
+                datetime!(2020-11-01 01:30:00 UTC),
+            ],
+            occurrences
+        );
+
+        Ok(())
+    }
+
+    fn biweekly_schedule() -> Result<Schedule> {
+        Ok(Schedule::new(
+            vec![(Weekday::Monday, vec![time!(10:00)])]
+                .into_iter()
+                .collect(),
+            week(2020, 3)?,
+            vec![1, 2],
+        )
+        .with_week_interval(NonZeroU32::new(2).ok_or("Invalid week interval")?))
+    }
+
+    #[test]
+    fn it_skips_weeks_outside_of_the_week_interval() -> Result<()> {
+        let schedule = biweekly_schedule()?;
+
+        // The week after the start week is skipped
+        assert_eq!(
+            Some(Duration::days(13) + Duration::hours(10)),
+            schedule.get_next_duration(datetime!(2020-01-14 00:00:00 UTC))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_assigns_only_weeks_within_the_week_interval() -> Result<()> {
+        let schedule = biweekly_schedule()?;
+
+        let occurrences: Vec<_> = schedule
+            .occurrences(datetime!(2020-01-13 00:00:00 UTC))
+            .take(3)
+            .collect();
+
+        assert_eq!(
+            vec![
+                (datetime!(2020-01-13 10:00:00 UTC), 1),
+                (datetime!(2020-01-27 10:00:00 UTC), 2),
+                (datetime!(2020-02-10 10:00:00 UTC), 1),
+            ],
+            occurrences
+        );
+
+        // Skipped weeks keep the assignee of the last occurrence
+        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-20 12:00:00 UTC)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_occurs_once_for_one_off_schedules() -> Result<()> {
+        let schedule = Schedule::from_recurrence(
+            Recurrence::Once {
+                time: datetime!(2026-11-03 14:00:00 +01:00),
+            },
+            week(2026, 44)?,
+            vec![1, 2],
+        );
+
+        assert_eq!(
+            Some(Duration::hours(1)),
+            schedule.get_next_duration(datetime!(2026-11-03 12:00:00 UTC))
+        );
+        assert_eq!(
+            None,
+            schedule.get_next_duration(datetime!(2026-11-03 13:00:01 UTC))
+        );
+        assert_eq!(
+            vec![(datetime!(2026-11-03 13:00:00 UTC), 1)],
+            schedule
+                .occurrences(datetime!(2026-11-01 00:00:00 UTC))
+                .collect::<Vec<_>>()
+        );
+
+        Ok(())
+    }
+
+    fn schedule_with_exclusions(advance_rotation: bool) -> Result<Schedule> {
+        Ok(Schedule::new(
+            vec![(Weekday::Monday, vec![time!(10:00)])]
+                .into_iter()
+                .collect(),
+            week(2020, 3)?,
+            vec![1, 2, 3],
+        )
+        .with_exclusions(Exclusions {
+            dates: vec!["2020-01-20".parse()?, "2020-02-01..2020-02-09".parse()?],
+            advance_rotation,
+        }))
+    }
+
+    #[test]
+    fn it_skips_excluded_dates() -> Result<()> {
+        let schedule = schedule_with_exclusions(false)?;
+
+        assert_eq!(
+            Some(Duration::days(13) + Duration::hours(10)),
+            schedule.get_next_duration(datetime!(2020-01-14 00:00:00 UTC))
+        );
+        assert_eq!(
+            Some(Duration::days(9) + Duration::hours(10)),
+            schedule.get_next_duration(datetime!(2020-02-01 00:00:00 UTC))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_keeps_the_rotation_for_excluded_dates() -> Result<()> {
+        let schedule = schedule_with_exclusions(false)?;
+
+        let occurrences: Vec<_> = schedule
+            .occurrences(datetime!(2020-01-13 00:00:00 UTC))
+            .take(3)
+            .collect();
+
+        assert_eq!(
+            vec![
+                (datetime!(2020-01-13 10:00:00 UTC), 1),
+                (datetime!(2020-01-27 10:00:00 UTC), 2),
+                (datetime!(2020-02-10 10:00:00 UTC), 3),
+            ],
+            occurrences
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_advances_the_rotation_for_excluded_dates() -> Result<()> {
+        let schedule = schedule_with_exclusions(true)?;
+
+        let occurrences: Vec<_> = schedule
+            .occurrences(datetime!(2020-01-13 00:00:00 UTC))
+            .take(3)
+            .collect();
+
+        assert_eq!(
+            vec![
+                (datetime!(2020-01-13 10:00:00 UTC), 1),
+                (datetime!(2020-01-27 10:00:00 UTC), 3),
+                (datetime!(2020-02-10 10:00:00 UTC), 2),
+            ],
+            occurrences
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_ends_after_the_until_date() -> Result<()> {
+        let schedule = Schedule::new(
+            vec![(Weekday::Monday, vec![time!(10:00)])]
+                .into_iter()
+                .collect(),
+            week(2020, 3)?,
+            vec![1, 2],
+        )
+        .with_until(Date::from_calendar_date(2020, Month::January, 27)?);
+
+        let occurrences: Vec<_> = schedule
+            .occurrences(datetime!(2020-01-13 00:00:00 UTC))
+            .collect();
+
+        assert_eq!(
+            vec![
+                (datetime!(2020-01-13 10:00:00 UTC), 1),
+                (datetime!(2020-01-20 10:00:00 UTC), 2),
+                (datetime!(2020-01-27 10:00:00 UTC), 1),
+            ],
+            occurrences
+        );
+        assert!(!schedule.is_exhausted(datetime!(2020-01-14 00:00:00 UTC)));
+        assert!(schedule.is_exhausted(datetime!(2020-01-28 00:00:00 UTC)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_ends_after_the_occurrence_limit_without_counting_exclusions() -> Result<()> {
+        let schedule = Schedule::new(
+            vec![(Weekday::Monday, vec![time!(10:00)])]
+                .into_iter()
+                .collect(),
+            week(2020, 3)?,
+            vec![1, 2],
+        )
+        .with_exclusions(Exclusions {
+            dates: vec!["2020-01-20".parse()?],
+            advance_rotation: false,
+        })
+        .with_occurrence_limit(NonZeroU32::new(2).ok_or("Invalid occurrence limit")?);
+
+        let occurrences: Vec<_> = schedule
+            .occurrences(datetime!(2020-01-13 00:00:00 UTC))
+            .collect();
+
+        assert_eq!(
+            vec![
+                (datetime!(2020-01-13 10:00:00 UTC), 1),
+                (datetime!(2020-01-27 10:00:00 UTC), 2),
+            ],
+            occurrences
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_counts_the_occurrence_limit_from_when_it_was_created() -> Result<()> {
+        let schedule = Schedule::new(
+            [
+                Weekday::Monday,
+                Weekday::Tuesday,
+                Weekday::Wednesday,
+                Weekday::Thursday,
+                Weekday::Friday,
+                Weekday::Saturday,
+                Weekday::Sunday,
+            ]
+            .into_iter()
+            .map(|weekday| (weekday, vec![time!(09:00)]))
+            .collect(),
+            week(2020, 2)?,
+            vec![1, 2],
+        )
+        .with_created(datetime!(2020-01-09 12:00:00 UTC)) // Thursday of the start week
+        .with_occurrence_limit(NonZeroU32::new(10).ok_or("Invalid occurrence limit")?);
+
+        let occurrences: Vec<_> = schedule
+            .occurrences(datetime!(2020-01-09 12:00:00 UTC))
+            .map(|(time, _)| time)
+            .collect();
+
+        assert_eq!(10, occurrences.len());
+        assert_eq!(
+            Some(&datetime!(2020-01-10 09:00:00 UTC)),
+            occurrences.first()
+        );
+        assert_eq!(
+            Some(&datetime!(2020-01-19 09:00:00 UTC)),
+            occurrences.last()
+        );
+        assert!(schedule.is_exhausted(datetime!(2020-01-19 09:00:01 UTC)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_occurs_and_assigns_by_recurrence_rules() -> Result<()> {
+        let schedule = Schedule::from_recurrence(
+            Recurrence::Rule {
+                rule: "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH;BYHOUR=9;COUNT=4\nEXDATE:20200116T090000"
+                    .parse()?,
+            },
+            week(2020, 3)?,
+            vec![1, 2],
+        );
+
+        let occurrences: Vec<_> = schedule
+            .occurrences(datetime!(2020-01-01 00:00:00 UTC))
+            .collect();
+
+        assert_eq!(
+            vec![
+                (datetime!(2020-01-13 09:00:00 UTC), 1),
+                (datetime!(2020-01-27 09:00:00 UTC), 2),
+                (datetime!(2020-01-30 09:00:00 UTC), 1),
+            ],
+            occurrences
+        );
+        assert!(schedule.is_exhausted(datetime!(2020-01-31 00:00:00 UTC)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_starts_recurrence_rules_when_they_were_created() -> Result<()> {
+        let schedule = Schedule::from_recurrence(
+            Recurrence::Rule {
+                rule: "FREQ=DAILY;INTERVAL=2;COUNT=3".parse()?,
+            },
+            week(2020, 2)?,
+            vec![1, 2],
+        )
+        .with_created(datetime!(2020-01-09 12:30:15.5 UTC)); // Thursday of the start week
+
+        let expected_times = vec![
+            datetime!(2020-01-09 12:30:15 UTC),
+            datetime!(2020-01-11 12:30:15 UTC),
+            datetime!(2020-01-13 12:30:15 UTC),
+        ];
+
+        let times: Vec<_> = schedule
+            .occurrences(datetime!(2020-01-01 00:00:00 UTC))
+            .map(|(time, _)| time)
+            .collect();
+
+        assert_eq!(expected_times, times);
+
+        // Restarting the rotation from a later week keeps the times of the rule
+        let mut restarted = schedule;
+        restarted.start_date = week(2020, 3)?;
+
+        let restarted_occurrences: Vec<_> = restarted
+            .occurrences(datetime!(2020-01-01 00:00:00 UTC))
+            .collect();
+
+        assert_eq!(
+            vec![
+                (datetime!(2020-01-09 12:30:15 UTC), 1),
+                (datetime!(2020-01-11 12:30:15 UTC), 1),
+                (datetime!(2020-01-13 12:30:15 UTC), 1),
+            ],
+            restarted_occurrences
+        );
 
-        let next_scheduled_day = self
-            .sorted_weekdays
-            .iter()
-            .find(|(week_day, times_in_day)| {
-                match week_day.cmp(&&this_week_day) {
-                    Ordering::Less => false,
-                    Ordering::Equal => {
-                        // Check if the next scheduled time is on the same weekday
-                        times_in_day.iter().any(|time| *time >= current_time.time())
-                    }
-                    Ordering::Greater => true,
-                }
-            })
-            .or_else(|| self.sorted_weekdays.iter().next());
-
-        match next_scheduled_day {
-            Some((week_day, times_in_day)) => {
-                // Determine the number of days that will elapse
-                match (i64::from(*week_day) - i64::from(this_week_day)).checked_rem_euclid(7) {
-                    // Same day means either the time between now and the next time,
-                    // or the first time of the day if it wrapped into the next week
-                    Some(days) if days == 0 => times_in_day
-                        .iter()
-                        .find_map(|time| {
-                            Some(*time - current_time.time()).filter(|time| time >= &Duration::ZERO)
-                        })
-                        .or_else(|| {
-                            times_in_day.get(0).and_then(|time| {
-                                Duration::days(7).checked_sub(current_time.time() - *time)
-                            })
-                        }),
-                    // Different days means the time between now and the first time of that day
-                    Some(days) => times_in_day.get(0).and_then(|time| {
-                        Duration::days(days).checked_sub(current_time.time() - *time)
-                    }),
-                    None => None,
-                }
-            }
-            None => None,
-        }
+        Ok(())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use time::macros::{datetime, time};
-    use time::Date;
+    #[test]
+    fn it_assigns_each_cron_firing_in_turn() -> Result<()> {
+        let schedule = Schedule::from_recurrence(
+            Recurrence::Cron {
+                cron: "0 9,17 * * MON".parse()?,
+            },
+            week(2020, 3)?,
+            vec![1, 2, 3],
+        );
 
-    type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+        let occurrences: Vec<_> = schedule
+            .occurrences(datetime!(2020-01-13 00:00:00 UTC))
+            .take(4)
+            .collect();
 
-    fn week(year: i32, week: u8) -> Result<OffsetDateTime> {
-        Ok(Date::from_iso_week_date(year, week, Weekday::Monday)?
-            .midnight()
-            .assume_utc())
+        assert_eq!(
+            vec![
+                (datetime!(2020-01-13 09:00:00 UTC), 1),
+                (datetime!(2020-01-13 17:00:00 UTC), 2),
+                (datetime!(2020-01-20 09:00:00 UTC), 3),
+                (datetime!(2020-01-20 17:00:00 UTC), 1),
+            ],
+            occurrences
+        );
+
+        Ok(())
     }
 
     #[test]
-    fn it_caps_elapsed_weeks() -> Result<()> {
+    fn it_rotates_through_multiple_assignees_per_occurrence() -> Result<()> {
         let schedule = Schedule::new(
-            vec![(Weekday::Monday, vec![time!(00:00:00)])]
+            vec![(Weekday::Monday, vec![time!(10:00)])]
                 .into_iter()
                 .collect(),
             week(2020, 3)?,
-            vec![1, 2],
+            vec![1, 2, 3, 4, 5],
+        )
+        .with_assignees_per_occurrence(NonZeroU32::new(2).ok_or("Invalid assignees")?);
+
+        assert_eq!(vec![1, 2], schedule.get_assignees(week(2020, 3)?));
+        assert_eq!(
+            vec![3, 4],
+            schedule.get_assignees(datetime!(2020-01-20 10:00:00 UTC))
         );
+        assert_eq!(
+            vec![5, 1],
+            schedule.get_assignees(datetime!(2020-01-27 10:00:00 UTC))
+        );
+        assert_eq!(2, schedule.get_assignee(datetime!(2020-02-03 10:00:00 UTC)));
 
-        // Times in the past will overflow `usize`, so they will be capped
-        assert_eq!(1, schedule.get_assignee(week(2020, 1)?));
-        assert_eq!(1, schedule.get_assignee(week(2020, 2)?));
-        // 0 elapsed weeks starts with the first assignee
-        assert_eq!(1, schedule.get_assignee(week(2020, 3)?));
-        // 1 elapsed week continues as normal
-        assert_eq!(2, schedule.get_assignee(week(2020, 4)?));
+        // Occurrences never assign someone twice
+        let schedule =
+            schedule.with_assignees_per_occurrence(NonZeroU32::new(7).ok_or("Invalid assignees")?);
+        assert_eq!(vec![1, 2, 3, 4, 5], schedule.get_assignees(week(2020, 3)?));
 
         Ok(())
     }
 
     #[test]
-    fn it_assigns_days_correctly_without_rollover() -> Result<()> {
+    fn it_continues_the_rotation_of_edited_schedules() -> Result<()> {
+        let schedule = |weekday: Weekday, assignees: Vec<i32>| -> Result<Schedule> {
+            Ok(Schedule::new(
+                vec![(weekday, vec![time!(10:00)])].into_iter().collect(),
+                week(2020, 3)?,
+                assignees,
+            ))
+        };
+        let now = datetime!(2020-02-05 00:00:00 UTC);
+        let upcoming = |schedule: &Schedule| -> Vec<i32> {
+            schedule
+                .occurrences(now)
+                .take(2)
+                .map(|(_, assignee)| assignee)
+                .collect()
+        };
+
+        let previous = schedule(Weekday::Monday, vec![1, 2, 3])?;
+        assert_eq!(vec![2, 3], upcoming(&previous));
+
+        // Moving the schedule to another day keeps whoever is up next
+        let edited = schedule(Weekday::Wednesday, vec![1, 2, 3])?;
+        assert_eq!(vec![1, 2], upcoming(&edited));
+        let edited = edited.with_rotation_continued_from(&previous, now);
+        assert_eq!(vec![2, 3], upcoming(&edited));
+
+        // Adding an assignee keeps whoever is up next
+        let edited = schedule(Weekday::Monday, vec![1, 2, 3, 4])?
+            .with_rotation_continued_from(&previous, now);
+        assert_eq!(vec![2, 3], upcoming(&edited));
+
+        // Removing the assignee who is up next keeps as many upcoming assignees as possible
+        let edited =
+            schedule(Weekday::Monday, vec![1, 3])?.with_rotation_continued_from(&previous, now);
+        assert_eq!(vec![1, 3], upcoming(&edited));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_finds_the_next_handoff_to_new_assignees() -> Result<()> {
         let schedule = Schedule::new(
             vec![
-                (Weekday::Monday, vec![time!(12:30)]),
-                (Weekday::Wednesday, vec![time!(12:30)]),
-                (Weekday::Friday, vec![time!(12:30)]),
+                (Weekday::Monday, vec![time!(10:00)]),
+                (Weekday::Thursday, vec![time!(10:00)]),
             ]
             .into_iter()
             .collect(),
             week(2020, 3)?,
-            vec![1, 2, 3],
+            vec![1, 2],
+        )
+        .with_rotation("per-week".parse()?);
+        let start = week(2020, 3)?;
+        let availability = Availability::default();
+        let overrides = Overrides::new();
+        let handoff = |after: OffsetDateTime, history: History| {
+            schedule.get_next_handoff(start, after, &availability, &overrides, history)
+        };
+
+        // The first occurrence is not a handoff without a previous assignment
+        assert_eq!(
+            Some(Handoff {
+                time: datetime!(2020-01-20 10:00 UTC),
+                incoming: vec![2],
+                outgoing: vec![1],
+            }),
+            handoff(start, History::new())
+        );
+        assert_eq!(
+            Some(Handoff {
+                time: datetime!(2020-01-27 10:00 UTC),
+                incoming: vec![1],
+                outgoing: vec![2],
+            }),
+            handoff(datetime!(2020-01-20 10:00 UTC), History::new())
         );
 
-        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-13 12:30:00 UTC)));
-        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-15 12:29:59 UTC)));
-        assert_eq!(2, schedule.get_assignee(datetime!(2020-01-15 12:30:00 UTC)));
-        assert_eq!(2, schedule.get_assignee(datetime!(2020-01-17 12:29:59 UTC)));
-        assert_eq!(3, schedule.get_assignee(datetime!(2020-01-17 12:30:00 UTC)));
+        // The most recent assignment in the history precedes the first occurrence
+        let history = vec![(datetime!(2020-01-09 10:00 UTC), vec![2])]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            Some(Handoff {
+                time: datetime!(2020-01-13 10:00 UTC),
+                incoming: vec![1],
+                outgoing: vec![2],
+            }),
+            handoff(start, history)
+        );
+
+        // A fixed rotation never hands off
+        let schedule = schedule.clone().with_rotation("fixed".parse()?);
+        assert_eq!(
+            None,
+            schedule.get_next_handoff(start, start, &availability, &overrides, History::new())
+        );
 
         Ok(())
     }
 
     #[test]
-    fn it_assigns_days_correctly_with_rollover() -> Result<()> {
+    fn it_finds_missed_occurrences_to_catch_up_on() -> Result<()> {
         let schedule = Schedule::new(
             vec![
-                (Weekday::Monday, vec![time!(12:30)]),
-                (Weekday::Wednesday, vec![time!(12:30)]),
-                (Weekday::Friday, vec![time!(12:30)]),
+                (Weekday::Monday, vec![time!(07:00)]),
+                (Weekday::Tuesday, vec![time!(07:00)]),
+                (Weekday::Wednesday, vec![time!(07:00)]),
             ]
             .into_iter()
             .collect(),
             week(2020, 3)?,
-            vec![1, 2],
+            vec![1],
         );
+        let last_occurrence = datetime!(2020-01-13 07:00 UTC);
+        let now = datetime!(2020-01-15 07:00 UTC);
+        let missed = |catch_up: CatchUp| {
+            schedule
+                .clone()
+                .with_catch_up(catch_up)
+                .get_missed_occurrences(last_occurrence, now)
+        };
 
-        // First week
-        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-13 12:30:00 UTC)));
-        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-15 12:29:59 UTC)));
-        assert_eq!(2, schedule.get_assignee(datetime!(2020-01-15 12:30:00 UTC)));
-        assert_eq!(2, schedule.get_assignee(datetime!(2020-01-17 12:29:59 UTC)));
-        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-17 12:30:00 UTC)));
-
-        // Second week
-        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-20 12:29:59 UTC)));
-        assert_eq!(2, schedule.get_assignee(datetime!(2020-01-20 12:30:00 UTC)));
-        assert_eq!(2, schedule.get_assignee(datetime!(2020-01-22 12:29:59 UTC)));
-        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-22 12:30:00 UTC)));
-        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-24 12:29:59 UTC)));
-        assert_eq!(2, schedule.get_assignee(datetime!(2020-01-24 12:30:00 UTC)));
-
-        // Start of third week
-        assert_eq!(2, schedule.get_assignee(datetime!(2020-01-27 12:29:59 UTC)));
-        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-27 12:30:00 UTC)));
+        // The last notified occurrence and the current occurrence are not missed
+        assert_eq!(vec![datetime!(2020-01-14 07:00 UTC)], missed(CatchUp::All));
+        assert_eq!(
+            vec![
+                datetime!(2020-01-14 07:00 UTC),
+                datetime!(2020-01-15 07:00 UTC)
+            ],
+            schedule
+                .clone()
+                .with_catch_up(CatchUp::All)
+                .get_missed_occurrences(last_occurrence, now + Duration::minutes(1))
+        );
+        assert_eq!(
+            vec![datetime!(2020-01-15 07:00 UTC)],
+            schedule
+                .clone()
+                .with_catch_up(CatchUp::Latest)
+                .get_missed_occurrences(last_occurrence, now + Duration::minutes(1))
+        );
+        assert!(missed(CatchUp::Skip).is_empty());
 
         Ok(())
     }
 
     #[test]
-    fn it_assigns_times_correctly_without_rollover() -> Result<()> {
+    fn it_spreads_the_turns_of_weighted_assignees() -> Result<()> {
         let schedule = Schedule::new(
-            vec![(
-                Weekday::Monday,
-                vec![time!(10:30), time!(11:30), time!(12:30)],
-            )]
-            .into_iter()
-            .collect(),
+            vec![(Weekday::Monday, vec![time!(10:00)])]
+                .into_iter()
+                .collect(),
             week(2020, 3)?,
             vec![1, 2, 3],
-        );
+        )
+        .with_weights(vec![(1, 3)].into_iter().collect());
+        let start = week(2020, 3)?;
+        let assignees = |schedule: &Schedule| -> Vec<i32> {
+            schedule
+                .assignments(
+                    start,
+                    &Availability::default(),
+                    &Overrides::new(),
+                    History::new(),
+                )
+                .take(10)
+                .map(|(_, assignment)| assignment.assignees[0])
+                .collect()
+        };
 
-        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-13 10:30:00 UTC)));
-        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-13 11:29:59 UTC)));
-        assert_eq!(2, schedule.get_assignee(datetime!(2020-01-13 11:30:00 UTC)));
-        assert_eq!(2, schedule.get_assignee(datetime!(2020-01-13 12:29:59 UTC)));
-        assert_eq!(3, schedule.get_assignee(datetime!(2020-01-13 12:30:00 UTC)));
+        assert_eq!(vec![1, 2, 1, 3, 1, 1, 2, 1, 3, 1], assignees(&schedule));
+
+        // Fair rotations count assignments relative to each assignee's weight
+        let schedule = Schedule::new(
+            vec![(Weekday::Monday, vec![time!(10:00)])]
+                .into_iter()
+                .collect(),
+            week(2020, 3)?,
+            vec![1, 2],
+        )
+        .with_weights(vec![(1, 2)].into_iter().collect())
+        .with_rotation("fair:28".parse()?);
+
+        assert_eq!(vec![1, 2, 1, 2, 1, 1, 2, 1, 2, 1], assignees(&schedule));
 
         Ok(())
     }
 
     #[test]
-    fn it_assigns_times_correctly_with_rollover() -> Result<()> {
+    fn it_rotates_by_the_rotation_strategy() -> Result<()> {
         let schedule = Schedule::new(
             vec![
-                (Weekday::Monday, vec![time!(10:30), time!(11:30)]),
-                (Weekday::Friday, vec![time!(12:30)]),
+                (Weekday::Monday, vec![time!(10:00), time!(18:00)]),
+                (Weekday::Tuesday, vec![time!(10:00)]),
             ]
             .into_iter()
             .collect(),
             week(2020, 3)?,
-            vec![1, 2],
+            vec![1, 2, 3],
         );
+        let start_time = week(2020, 3)?;
+        let assignees = |schedule: &Schedule| -> Vec<i32> {
+            schedule
+                .occurrences(start_time)
+                .take(6)
+                .map(|(_, assignee)| assignee)
+                .collect()
+        };
 
-        // First week
-        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-13 10:30:00 UTC)));
-        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-13 11:29:59 UTC)));
-        assert_eq!(2, schedule.get_assignee(datetime!(2020-01-13 11:30:00 UTC)));
-        assert_eq!(2, schedule.get_assignee(datetime!(2020-01-17 12:29:59 UTC)));
-        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-17 12:30:00 UTC)));
-
-        // Second week
-        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-20 10:29:59 UTC)));
-        assert_eq!(2, schedule.get_assignee(datetime!(2020-01-20 10:30:00 UTC)));
-        assert_eq!(2, schedule.get_assignee(datetime!(2020-01-20 11:29:59 UTC)));
-        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-20 11:30:00 UTC)));
-        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-24 12:29:59 UTC)));
-        assert_eq!(2, schedule.get_assignee(datetime!(2020-01-24 12:30:00 UTC)));
+        let schedule = schedule.with_rotation("per-week".parse()?);
+        assert_eq!(vec![1, 1, 1, 2, 2, 2], assignees(&schedule));
 
-        // Start of third week
-        assert_eq!(2, schedule.get_assignee(datetime!(2020-01-27 10:29:59 UTC)));
-        assert_eq!(1, schedule.get_assignee(datetime!(2020-01-27 10:30:00 UTC)));
+        let schedule = schedule.with_rotation("per-day".parse()?);
+        assert_eq!(vec![1, 1, 2, 2, 2, 3], assignees(&schedule));
 
-        Ok(())
-    }
+        let schedule = schedule.with_rotation("fixed".parse()?);
+        assert_eq!(vec![1, 1, 1, 1, 1, 1], assignees(&schedule));
 
-    #[test]
-    fn it_returns_none_without_next_duration() -> Result<()> {
-        let schedule = Schedule::new(vec![].into_iter().collect(), week(2020, 1)?, vec![]);
+        let schedule = schedule.with_rotation("random:42".parse()?);
+        let mut first_round = assignees(&schedule)[..3].to_vec();
+        first_round.sort_unstable();
+        assert_eq!(vec![1, 2, 3], first_round);
 
-        assert_eq!(None, schedule.get_next_duration(OffsetDateTime::now_utc()));
         Ok(())
     }
 
     #[test]
-    fn it_returns_0_when_next_duration_is_now() -> Result<()> {
+    fn it_reassigns_turns_of_assignees_who_are_away() -> Result<()> {
         let schedule = Schedule::new(
-            vec![(Weekday::Monday, vec![time!(12:30)])]
+            vec![(Weekday::Monday, vec![time!(10:00)])]
                 .into_iter()
                 .collect(),
-            week(2020, 1)?,
-            vec![1],
+            week(2020, 3)?,
+            vec![1, 2, 3],
         );
+        let availability = vec![(
+            2,
+            Absence {
+                dates: "2020-01-19..2020-01-21".parse()?,
+                defer_turn: false,
+            },
+        )]
+        .into_iter()
+        .collect();
+        let assignees = |time| {
+            schedule
+                .get_assignment(time, &availability, &Overrides::new(), &History::new())
+                .assignees
+        };
+
+        assert_eq!(vec![1], assignees(datetime!(2020-01-13 10:00:00 UTC)));
+        assert_eq!(vec![3], assignees(datetime!(2020-01-20 10:00:00 UTC)));
+        assert_eq!(vec![3], assignees(datetime!(2020-01-27 10:00:00 UTC)));
+        assert_eq!(vec![1], assignees(datetime!(2020-02-03 10:00:00 UTC)));
 
         assert_eq!(
-            Some(Duration::ZERO),
-            schedule.get_next_duration(datetime!(2020-01-06 12:30:00 UTC))
+            vec![Substitution {
+                absent: 2,
+                substitute: 3
+            }],
+            schedule
+                .get_assignment(
+                    datetime!(2020-01-20 10:00:00 UTC),
+                    &availability,
+                    &Overrides::new(),
+                    &History::new()
+                )
+                .substitutions
         );
 
         Ok(())
     }
 
     #[test]
-    fn it_returns_remaining_time_when_next_duration_is_same_day() -> Result<()> {
+    fn it_replaces_assignees_of_overridden_occurrences() -> Result<()> {
         let schedule = Schedule::new(
-            vec![(Weekday::Monday, vec![time!(12:30)])]
+            vec![(Weekday::Monday, vec![time!(10:00)])]
                 .into_iter()
                 .collect(),
-            week(2020, 1)?,
-            vec![1],
+            week(2020, 3)?,
+            vec![1, 2, 3],
         );
+        let overrides = vec![(datetime!(2020-01-20 11:00:00 +01:00), vec![3])]
+            .into_iter()
+            .collect();
+        let assignment = |time| {
+            schedule.get_assignment(time, &Availability::default(), &overrides, &History::new())
+        };
 
         assert_eq!(
-            Some(Duration::minutes(12 * 60 + 30)),
-            schedule.get_next_duration(datetime!(2020-01-06 00:00:00 UTC))
+            Assignment {
+                assignees: vec![3],
+                substitutions: Vec::new(),
+                is_override: true,
+            },
+            assignment(datetime!(2020-01-20 10:00:00 UTC))
+        );
+        assert_eq!(
+            vec![3],
+            assignment(datetime!(2020-01-27 10:00:00 UTC)).assignees
         );
+        assert!(!assignment(datetime!(2020-01-27 10:00:00 UTC)).is_override);
 
         Ok(())
     }
 
     #[test]
-    fn it_returns_1_day_when_next_duration_is_next_day_at_same_time() -> Result<()> {
+    fn it_defers_turns_of_assignees_who_are_away() -> Result<()> {
         let schedule = Schedule::new(
-            vec![(Weekday::Monday, vec![time!(12:30)])]
+            vec![(Weekday::Monday, vec![time!(10:00)])]
                 .into_iter()
                 .collect(),
-            week(2020, 1)?,
-            vec![1],
+            week(2020, 3)?,
+            vec![1, 2, 3],
         );
+        let availability = vec![(
+            2,
+            Absence {
+                dates: "2020-01-19..2020-01-21".parse()?,
+                defer_turn: true,
+            },
+        )]
+        .into_iter()
+        .collect();
+        let assignees = |time| {
+            schedule
+                .get_assignment(time, &availability, &Overrides::new(), &History::new())
+                .assignees
+        };
 
-        assert_eq!(
-            Some(Duration::days(1)),
-            schedule.get_next_duration(datetime!(2020-01-05 12:30:00 UTC))
-        );
+        assert_eq!(vec![3], assignees(datetime!(2020-01-20 10:00:00 UTC)));
+        assert_eq!(vec![2], assignees(datetime!(2020-01-27 10:00:00 UTC)));
+        assert_eq!(vec![3], assignees(datetime!(2020-02-03 10:00:00 UTC)));
+        assert_eq!(vec![1], assignees(datetime!(2020-02-10 10:00:00 UTC)));
 
         Ok(())
     }
 
     #[test]
-    fn it_returns_time_gap_when_next_duration_is_less_than_24h() -> Result<()> {
+    fn it_assigns_whoever_was_assigned_least_with_fair_rotations() -> Result<()> {
         let schedule = Schedule::new(
-            vec![(Weekday::Monday, vec![time!(12:30)])]
+            vec![(Weekday::Monday, vec![time!(10:00)])]
                 .into_iter()
                 .collect(),
-            week(2020, 1)?,
-            vec![1],
-        );
+            week(2020, 3)?,
+            vec![1, 2, 3],
+        )
+        .with_rotation("fair:28".parse()?);
+        let history: History = vec![
+            (datetime!(2019-11-04 10:00:00 UTC), vec![2]),
+            (datetime!(2019-12-30 10:00:00 UTC), vec![1]),
+            (datetime!(2020-01-06 10:00:00 UTC), vec![1]),
+            (datetime!(2020-01-13 10:00:00 UTC), vec![3]),
+        ]
+        .into_iter()
+        .collect();
+        let assignees = |availability: &Availability| -> Vec<i32> {
+            schedule
+                .assignments(
+                    datetime!(2020-01-20 00:00:00 UTC),
+                    availability,
+                    &Overrides::new(),
+                    history.clone(),
+                )
+                .take(4)
+                .map(|(_, assignment)| assignment.assignees[0])
+                .collect()
+        };
 
-        assert_eq!(
-            Some(Duration::seconds(60 * 60 * 24 - 1)),
-            schedule.get_next_duration(datetime!(2020-01-05 12:30:01 UTC))
-        );
+        // Ties go to whoever was assigned least recently
+        assert_eq!(vec![2, 3, 1, 2], assignees(&Availability::default()));
+
+        // Assignees who are away catch up once they return
+        let availability = vec![(
+            2,
+            Absence {
+                dates: "2020-01-19..2020-01-21".parse()?,
+                defer_turn: false,
+            },
+        )]
+        .into_iter()
+        .collect();
+        assert_eq!(vec![3, 2, 1, 2], assignees(&availability));
 
         Ok(())
     }
 
     #[test]
-    fn it_returns_time_gap_when_next_duration_wraps_to_next_week() -> Result<()> {
-        let schedule = Schedule::new(
-            vec![(Weekday::Monday, vec![time!(12:30)])]
+    fn it_is_not_exhausted_without_an_end() -> Result<()> {
+        let schedule = Schedule::new(vec![].into_iter().collect(), week(2020, 3)?, vec![1]);
+
+        assert!(!schedule.is_exhausted(datetime!(2020-01-28 00:00:00 UTC)));
+
+        Ok(())
+    }
+
+    fn monthly_schedule() -> Result<Schedule> {
+        Ok(Schedule::from_recurrence(
+            Recurrence::Monthly(
+                vec![
+                    (MonthDay::Day(31), vec![time!(09:00)]),
+                    (MonthDay::Weekday(2, Weekday::Tuesday), vec![time!(09:00)]),
+                ]
                 .into_iter()
                 .collect(),
+            ),
             week(2020, 1)?,
-            vec![1],
-        );
+            vec![1, 2, 3],
+        ))
+    }
+
+    #[test]
+    fn it_returns_time_gap_until_next_day_of_the_month() -> Result<()> {
+        let schedule = monthly_schedule()?;
 
+        // February has no 31st, so the 2nd Tuesday on the 11th is next
         assert_eq!(
-            Some(Duration::seconds(60 * 60 * 24 * 7 - 1)),
-            schedule.get_next_duration(datetime!(2020-01-06 12:30:01 UTC))
+            Some(Duration::days(10) + Duration::hours(9)),
+            schedule.get_next_duration(datetime!(2020-02-01 00:00:00 UTC))
         );
 
         Ok(())
     }
 
     #[test]
-    fn it_returns_time_gap_when_next_duration_is_a_future_day_of_the_week() -> Result<()> {
-        let schedule = Schedule::new(
-            vec![
-                (Weekday::Monday, vec![time!(10:30)]),
-                (Weekday::Friday, vec![time!(20:30)]),
-            ]
-            .into_iter()
-            .collect(),
-            week(2020, 1)?,
-            vec![1],
-        );
+    fn it_assigns_monthly_times_in_order() -> Result<()> {
+        let schedule = monthly_schedule()?;
+
+        let occurrences: Vec<_> = schedule
+            .occurrences(datetime!(2019-12-30 00:00:00 UTC))
+            .take(6)
+            .collect();
 
         assert_eq!(
-            // Next time is 4 days and 10 hours minus 1 second
-            Some(Duration::seconds(60 * 60 * 24 * 4 + 60 * 60 * 10 - 1)),
-            schedule.get_next_duration(datetime!(2020-01-06 10:30:01 UTC))
+            vec![
+                (datetime!(2019-12-31 09:00:00 UTC), 1),
+                (datetime!(2020-01-14 09:00:00 UTC), 2),
+                (datetime!(2020-01-31 09:00:00 UTC), 3),
+                (datetime!(2020-02-11 09:00:00 UTC), 1),
+                (datetime!(2020-03-10 09:00:00 UTC), 2),
+                (datetime!(2020-03-31 09:00:00 UTC), 3),
+            ],
+            occurrences
         );
 
         Ok(())
     }
 
     #[test]
-    fn it_returns_time_gap_when_next_duration_is_a_past_day_of_the_week() -> Result<()> {
-        let schedule = Schedule::new(
-            vec![
-                (Weekday::Monday, vec![time!(10:30)]),
-                (Weekday::Friday, vec![time!(20:30)]),
-            ]
-            .into_iter()
-            .collect(),
-            week(2020, 1)?,
-            vec![1],
+    fn it_deserializes_recurrences() -> Result<()> {
+        assert_eq!(
+            Recurrence::Weekly(
+                vec![(Weekday::Monday, vec![time!(10:30)])]
+                    .into_iter()
+                    .collect()
+            ),
+            serde_json::from_str(r#"{"Monday":["10:30:00.0"]}"#)?
         );
-
         assert_eq!(
-            // Next time is 2 days and 14 hours minus 1 second
-            Some(Duration::seconds(60 * 60 * 24 * 2 + 60 * 60 * 14 - 1)),
-            schedule.get_next_duration(datetime!(2020-01-10 20:30:01 UTC))
+            Recurrence::Monthly(
+                vec![(MonthDay::LastWeekday(Weekday::Friday), vec![time!(18:00)])]
+                    .into_iter()
+                    .collect()
+            ),
+            serde_json::from_str(r#"{"last friday":["18:00:00.0"]}"#)?
+        );
+        assert_eq!(
+            Recurrence::Once {
+                time: datetime!(2026-11-03 14:00:00 +01:00)
+            },
+            serde_json::from_str(r#"{"once":"2026-11-03T14:00:00+01:00"}"#)?
         );
 
         Ok(())
@@ -0,0 +1,227 @@
+//! Days within a month for monthly schedules
+
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::fmt;
+use time::{util::days_in_year_month, Date, Month, Weekday};
+
+/// Weekdays in order from Monday, for parsing
+pub(crate) const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Monday,
+    Weekday::Tuesday,
+    Weekday::Wednesday,
+    Weekday::Thursday,
+    Weekday::Friday,
+    Weekday::Saturday,
+    Weekday::Sunday,
+];
+
+/// Ordinal suffixes for the first through fifth weekdays of a month
+const ORDINALS: [&str; 5] = ["1st", "2nd", "3rd", "4th", "5th"];
+
+/// Day within a month on which a monthly schedule occurs
+///
+/// Days are written as `1` through `31`, `last`, `2nd tuesday` or `last friday`.
+/// Weekday names may also be abbreviated, such as `2nd tue`
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum MonthDay {
+    /// Numbered day of the month, which is skipped in months without that day
+    Day(u8),
+    /// Last day of the month
+    LastDay,
+    /// Nth weekday of the month between 1 and 5, which is skipped in months without one
+    Weekday(u8, Weekday),
+    /// Last weekday of the month
+    LastWeekday(Weekday),
+}
+
+impl MonthDay {
+    /// Determines the date of this day in the given month, if the month has one
+    #[must_use]
+    pub fn date_in(self, year: i32, month: Month) -> Option<Date> {
+        let days_in_month = days_in_year_month(year, month);
+
+        let day = match self {
+            Self::Day(day) => day,
+            Self::LastDay => days_in_month,
+            Self::Weekday(nth, weekday) => {
+                let first_weekday = Date::from_calendar_date(year, month, 1).ok()?.weekday();
+                let first_day = 1 + days_between(first_weekday, weekday);
+                first_day.checked_add(nth.checked_sub(1)?.checked_mul(7)?)?
+            }
+            Self::LastWeekday(weekday) => {
+                let last_weekday = Date::from_calendar_date(year, month, days_in_month)
+                    .ok()?
+                    .weekday();
+                days_in_month - days_between(weekday, last_weekday)
+            }
+        };
+
+        Some(day)
+            .filter(|day| *day <= days_in_month)
+            .and_then(|day| Date::from_calendar_date(year, month, day).ok())
+    }
+}
+
+/// Number of days from one weekday until the next occurrence of another weekday
+fn days_between(from: Weekday, to: Weekday) -> u8 {
+    (7 + to.number_days_from_monday() - from.number_days_from_monday()) % 7
+}
+
+/// Parses a full or abbreviated weekday name
+pub(crate) fn parse_weekday(name: &str) -> Option<Weekday> {
+    WEEKDAYS.iter().copied().find(|weekday| {
+        let full_name = weekday.to_string().to_lowercase();
+        name == full_name || (name.len() >= 3 && full_name.starts_with(name))
+    })
+}
+
+impl TryFrom<String> for MonthDay {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let value = value.trim().to_lowercase();
+        let invalid = || format!("Invalid day of the month `{}`", value);
+
+        let mut words = value.split_whitespace();
+
+        let month_day = match (words.next(), words.next(), words.next()) {
+            (Some("last"), None, None) => Self::LastDay,
+            (Some("last"), Some(weekday), None) => {
+                Self::LastWeekday(parse_weekday(weekday).ok_or_else(invalid)?)
+            }
+            (Some(nth), Some(weekday), None) => {
+                let nth = ORDINALS
+                    .iter()
+                    .position(|ordinal| ordinal == &nth)
+                    .and_then(|index| u8::try_from(index + 1).ok())
+                    .ok_or_else(invalid)?;
+                Self::Weekday(nth, parse_weekday(weekday).ok_or_else(invalid)?)
+            }
+            (Some(day), None, None) => Self::Day(
+                day.parse()
+                    .ok()
+                    .filter(|day| (1..=31).contains(day))
+                    .ok_or_else(invalid)?,
+            ),
+            _ => return Err(invalid()),
+        };
+
+        Ok(month_day)
+    }
+}
+
+impl From<MonthDay> for String {
+    fn from(month_day: MonthDay) -> Self {
+        month_day.to_string()
+    }
+}
+
+impl fmt::Display for MonthDay {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Day(day) => write!(formatter, "{}", day),
+            Self::LastDay => write!(formatter, "last"),
+            Self::Weekday(nth, weekday) => match nth
+                .checked_sub(1)
+                .and_then(|index| ORDINALS.get(usize::from(index)))
+            {
+                Some(ordinal) => write!(formatter, "{} {}", ordinal, weekday),
+                None => write!(formatter, "{}th {}", nth, weekday),
+            },
+            Self::LastWeekday(weekday) => write!(formatter, "last {}", weekday),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::date;
+
+    fn parse(value: &str) -> Result<MonthDay, String> {
+        MonthDay::try_from(value.to_string())
+    }
+
+    #[test]
+    fn it_parses_days_of_the_month() {
+        assert_eq!(Ok(MonthDay::Day(1)), parse("1"));
+        assert_eq!(Ok(MonthDay::Day(31)), parse("31"));
+        assert_eq!(Ok(MonthDay::LastDay), parse("last"));
+        assert!(parse("0").is_err());
+        assert!(parse("32").is_err());
+    }
+
+    #[test]
+    fn it_parses_weekdays_of_the_month() {
+        assert_eq!(
+            Ok(MonthDay::Weekday(2, Weekday::Tuesday)),
+            parse("2nd Tuesday")
+        );
+        assert_eq!(Ok(MonthDay::Weekday(1, Weekday::Monday)), parse("1st mon"));
+        assert_eq!(
+            Ok(MonthDay::LastWeekday(Weekday::Friday)),
+            parse("last fri")
+        );
+        assert!(parse("6th friday").is_err());
+        assert!(parse("2nd fr").is_err());
+        assert!(parse("2nd friday evening").is_err());
+    }
+
+    #[test]
+    fn it_displays_days_of_the_month() {
+        assert_eq!("15", MonthDay::Day(15).to_string());
+        assert_eq!("last", MonthDay::LastDay.to_string());
+        assert_eq!(
+            "2nd Tuesday",
+            MonthDay::Weekday(2, Weekday::Tuesday).to_string()
+        );
+        assert_eq!(
+            "last Friday",
+            MonthDay::LastWeekday(Weekday::Friday).to_string()
+        );
+    }
+
+    #[test]
+    fn it_finds_numbered_days_in_a_month() {
+        assert_eq!(
+            Some(date!(2020 - 01 - 31)),
+            MonthDay::Day(31).date_in(2020, Month::January)
+        );
+        assert_eq!(None, MonthDay::Day(31).date_in(2020, Month::April));
+        assert_eq!(
+            Some(date!(2020 - 02 - 29)),
+            MonthDay::LastDay.date_in(2020, Month::February)
+        );
+    }
+
+    #[test]
+    fn it_finds_weekdays_in_a_month() {
+        // January 2020 starts on a Wednesday and ends on a Friday
+        assert_eq!(
+            Some(date!(2020 - 01 - 14)),
+            MonthDay::Weekday(2, Weekday::Tuesday).date_in(2020, Month::January)
+        );
+        assert_eq!(
+            Some(date!(2020 - 01 - 01)),
+            MonthDay::Weekday(1, Weekday::Wednesday).date_in(2020, Month::January)
+        );
+        assert_eq!(
+            Some(date!(2020 - 01 - 29)),
+            MonthDay::Weekday(5, Weekday::Wednesday).date_in(2020, Month::January)
+        );
+        assert_eq!(
+            None,
+            MonthDay::Weekday(5, Weekday::Monday).date_in(2020, Month::January)
+        );
+        assert_eq!(
+            Some(date!(2020 - 01 - 31)),
+            MonthDay::LastWeekday(Weekday::Friday).date_in(2020, Month::January)
+        );
+        assert_eq!(
+            Some(date!(2020 - 01 - 25)),
+            MonthDay::LastWeekday(Weekday::Saturday).date_in(2020, Month::January)
+        );
+    }
+}
@@ -0,0 +1,120 @@
+//! Lengths of time ahead of a schedule's occurrences
+
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+use time::Duration;
+
+/// Number of minutes in an hour
+const MINUTES_PER_HOUR: u32 = 60;
+
+/// Number of minutes in a day
+const MINUTES_PER_DAY: u32 = 24 * MINUTES_PER_HOUR;
+
+/// Length of time ahead of an occurrence, such as for announcing who is assigned to it
+///
+/// Written as a number of minutes, hours or days, such as `30m`, `12h` or `2d`
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Notice {
+    /// Length of the notice in minutes
+    minutes: u32,
+}
+
+impl Notice {
+    /// Creates a notice of the given number of minutes
+    #[must_use]
+    pub fn from_minutes(minutes: u32) -> Self {
+        Self { minutes }
+    }
+
+    /// Length of the notice in minutes
+    #[must_use]
+    pub fn minutes(self) -> u32 {
+        self.minutes
+    }
+
+    /// Length of the notice as a duration
+    #[must_use]
+    pub fn duration(self) -> Duration {
+        Duration::minutes(i64::from(self.minutes))
+    }
+}
+
+impl FromStr for Notice {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim().to_lowercase();
+        let invalid = || {
+            format!(
+                "Invalid notice `{}`, should be minutes, hours or days such as 30m, 12h or 2d",
+                value
+            )
+        };
+
+        let unit = match value.chars().last() {
+            Some('m') => 1,
+            Some('h') => MINUTES_PER_HOUR,
+            Some('d') => MINUTES_PER_DAY,
+            _ => return Err(invalid()),
+        };
+
+        let minutes = value[..value.len() - 1]
+            .parse::<u32>()
+            .ok()
+            .and_then(|count| count.checked_mul(unit))
+            .ok_or_else(invalid)?;
+
+        Ok(Self { minutes })
+    }
+}
+
+impl TryFrom<String> for Notice {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Notice> for String {
+    fn from(notice: Notice) -> Self {
+        notice.to_string()
+    }
+}
+
+impl fmt::Display for Notice {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.minutes {
+            minutes if minutes > 0 && minutes % MINUTES_PER_DAY == 0 => {
+                write!(formatter, "{}d", minutes / MINUTES_PER_DAY)
+            }
+            minutes if minutes > 0 && minutes % MINUTES_PER_HOUR == 0 => {
+                write!(formatter, "{}h", minutes / MINUTES_PER_HOUR)
+            }
+            minutes => write!(formatter, "{}m", minutes),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_and_displays_notices() {
+        assert_eq!(Ok(Notice::from_minutes(30)), "30m".parse());
+        assert_eq!(Ok(Notice::from_minutes(720)), "12h".parse());
+        assert_eq!(Ok(Notice::from_minutes(2880)), "2d".parse());
+        assert!("2".parse::<Notice>().is_err());
+        assert!("-2d".parse::<Notice>().is_err());
+        assert!("d".parse::<Notice>().is_err());
+
+        assert_eq!("30m", Notice::from_minutes(30).to_string());
+        assert_eq!("12h", Notice::from_minutes(720).to_string());
+        assert_eq!("2d", Notice::from_minutes(2880).to_string());
+        assert_eq!("90m", Notice::from_minutes(90).to_string());
+    }
+}
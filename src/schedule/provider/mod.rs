@@ -2,42 +2,100 @@
 
 mod model;
 
-use super::{model::Schedule, Error};
+use super::{exclusions::DATE_FORMAT, model::Schedule, Assignee, Error, Notice, Timezone};
 use crate::database::schema::reminders;
 use diesel::deserialize::{FromSqlRow, Result as FromSqlResult};
-use diesel::sql_types::{Integer, Text};
+use diesel::sql_types::{Integer, Nullable, Text};
 use diesel::{backend::Backend, row::Row};
-use model::{StoredAssignees, StoredIsoWeek, StoredWeeklyTimes};
+use model::{StoredAssignees, StoredExclusions, StoredIsoWeek, StoredRecurrence};
 use serde::Deserialize;
 use std::convert::{From, TryFrom, TryInto};
+use std::num::NonZeroU32;
+use time::format_description::well_known::Rfc3339;
+use time::{Date, OffsetDateTime, UtcOffset};
 
 /// Provides access to scheduling data in persistent storage
 #[derive(Debug, Deserialize, Eq, Insertable, PartialEq, Queryable)]
 #[table_name = "reminders"]
 pub struct Provider {
-    /// Scheduled times of day throughout the week
+    /// Scheduled times of day throughout the week or month
     #[column_name = "schedule"]
-    pub(crate) weekly_times: StoredWeeklyTimes,
+    pub(crate) recurrence: StoredRecurrence,
     /// Week in which the schedule started
     pub(crate) start_week: StoredIsoWeek,
-    /// Assignee ids in order of assignment
+    /// Assignee ids and weights in order of assignment
     pub(crate) assignees: StoredAssignees,
+    /// IANA name of the time zone for scheduled times
+    pub(crate) timezone: String,
+    /// Number of weeks between each week in which a weekly schedule occurs
+    pub(crate) week_interval: i32,
+    /// Dates on which the schedule does not occur
+    pub(crate) exclusions: StoredExclusions,
+    /// Last date on which the schedule occurs, if the schedule ends
+    pub(crate) until: Option<String>,
+    /// Maximum number of times the schedule occurs, if the schedule ends
+    pub(crate) occurrence_limit: Option<i32>,
+    /// Number of assignees for each occurrence
+    pub(crate) assignees_per_occurrence: i32,
+    /// Strategy for rotating through the assignees
+    pub(crate) rotation: String,
+    /// Number of turns the rotation is moved along by
+    pub(crate) rotation_offset: i32,
+    /// Minutes before a change of assignees that it is announced, if it is announced
+    pub(crate) handoff_notice: Option<i32>,
+    /// How occurrences missed while the scheduler wasn't running are handled
+    pub(crate) catch_up: String,
+    /// RFC 3339 datetime in UTC when the schedule was created, if it was recorded
+    pub(crate) created: Option<String>,
 }
 
-impl<TDatabase> FromSqlRow<(Text, Integer, Text), TDatabase> for Provider
+impl<TDatabase>
+    FromSqlRow<
+        (
+            Text,
+            Integer,
+            Text,
+            Text,
+            Integer,
+            Text,
+            Nullable<Text>,
+            Nullable<Integer>,
+            Integer,
+            Text,
+            Integer,
+            Nullable<Integer>,
+            Text,
+            Nullable<Text>,
+        ),
+        TDatabase,
+    > for Provider
 where
     TDatabase: Backend,
-    StoredWeeklyTimes: FromSqlRow<Text, TDatabase>,
+    StoredRecurrence: FromSqlRow<Text, TDatabase>,
     StoredIsoWeek: FromSqlRow<Integer, TDatabase>,
     StoredAssignees: FromSqlRow<Text, TDatabase>,
+    String: FromSqlRow<Text, TDatabase>,
+    i32: FromSqlRow<Integer, TDatabase>,
+    StoredExclusions: FromSqlRow<Text, TDatabase>,
 {
-    const FIELDS_NEEDED: usize = 3;
+    const FIELDS_NEEDED: usize = 14;
 
     fn build_from_row<TRow: Row<TDatabase>>(row: &mut TRow) -> FromSqlResult<Self> {
         Ok(Self {
-            weekly_times: StoredWeeklyTimes::build_from_row(row)?,
+            recurrence: StoredRecurrence::build_from_row(row)?,
             start_week: StoredIsoWeek::build_from_row(row)?,
             assignees: StoredAssignees::build_from_row(row)?,
+            timezone: String::build_from_row(row)?,
+            week_interval: i32::build_from_row(row)?,
+            exclusions: StoredExclusions::build_from_row(row)?,
+            until: Option::<String>::build_from_row(row)?,
+            occurrence_limit: Option::<i32>::build_from_row(row)?,
+            assignees_per_occurrence: i32::build_from_row(row)?,
+            rotation: String::build_from_row(row)?,
+            rotation_offset: i32::build_from_row(row)?,
+            handoff_notice: Option::<i32>::build_from_row(row)?,
+            catch_up: String::build_from_row(row)?,
+            created: Option::<String>::build_from_row(row)?,
         })
     }
 }
@@ -45,14 +103,33 @@ where
 impl From<Schedule> for Provider {
     fn from(schedule: Schedule) -> Self {
         let iso_week = schedule.start_date.iso_week();
+        let assignees = schedule.weighted_assignees();
 
         Self {
-            weekly_times: StoredWeeklyTimes(schedule.weekly_times),
+            recurrence: schedule.recurrence.into(),
             start_week: StoredIsoWeek {
                 year: schedule.start_date.year(),
                 week: i32::try_from(iso_week.max(1).min(53)).unwrap_or(1),
             },
-            assignees: StoredAssignees(schedule.assignees),
+            assignees: assignees.into(),
+            timezone: schedule.timezone.name().to_string(),
+            week_interval: i32::try_from(schedule.week_interval).unwrap_or(i32::MAX),
+            exclusions: StoredExclusions(schedule.exclusions),
+            until: schedule.until.map(|until| until.to_string()),
+            occurrence_limit: schedule
+                .occurrence_limit
+                .map(|occurrence_limit| i32::try_from(occurrence_limit).unwrap_or(i32::MAX)),
+            assignees_per_occurrence: i32::try_from(schedule.assignees_per_occurrence)
+                .unwrap_or(i32::MAX),
+            rotation: schedule.rotation.to_string(),
+            rotation_offset: i32::try_from(schedule.rotation_offset).unwrap_or(i32::MAX),
+            handoff_notice: schedule
+                .handoff_notice
+                .map(|notice| i32::try_from(notice.minutes()).unwrap_or(i32::MAX)),
+            catch_up: schedule.catch_up.to_string(),
+            created: schedule
+                .created
+                .and_then(|created| created.to_offset(UtcOffset::UTC).format(&Rfc3339).ok()),
         }
     }
 }
@@ -61,18 +138,100 @@ impl TryInto<Schedule> for Provider {
     type Error = Error;
 
     fn try_into(self) -> Result<Schedule, Self::Error> {
-        Ok(Schedule::new(
-            self.weekly_times.0,
+        let week_interval = u32::try_from(self.week_interval)
+            .ok()
+            .and_then(NonZeroU32::new)
+            .ok_or(Error::InvalidWeekInterval(self.week_interval))?;
+
+        let until = self
+            .until
+            .map(|until| Date::parse(&until, DATE_FORMAT).map_err(|_| Error::InvalidUntil(until)))
+            .transpose()?;
+
+        let occurrence_limit = self
+            .occurrence_limit
+            .map(|occurrence_limit| {
+                u32::try_from(occurrence_limit)
+                    .ok()
+                    .and_then(NonZeroU32::new)
+                    .ok_or(Error::InvalidOccurrenceLimit(occurrence_limit))
+            })
+            .transpose()?;
+
+        let assignees_per_occurrence = u32::try_from(self.assignees_per_occurrence)
+            .ok()
+            .and_then(NonZeroU32::new)
+            .ok_or(Error::InvalidAssigneesPerOccurrence(
+                self.assignees_per_occurrence,
+            ))?;
+
+        let rotation = self.rotation.parse().map_err(Error::InvalidRotation)?;
+        let catch_up = self.catch_up.parse().map_err(Error::InvalidCatchUp)?;
+        let rotation_offset = u32::try_from(self.rotation_offset)
+            .map_err(|_| Error::InvalidRotationOffset(self.rotation_offset))?;
+        let handoff_notice = self
+            .handoff_notice
+            .map(|minutes| {
+                u32::try_from(minutes)
+                    .map(Notice::from_minutes)
+                    .map_err(|_| Error::InvalidHandoffNotice(minutes))
+            })
+            .transpose()?;
+        let created = self
+            .created
+            .map(|created| {
+                OffsetDateTime::parse(&created, &Rfc3339)
+                    .map_err(|_| Error::InvalidCreated(created))
+            })
+            .transpose()?;
+        let assignees: Vec<Assignee> = self.assignees.into();
+
+        let mut schedule = Schedule::from_recurrence(
+            self.recurrence.into(),
             self.start_week.try_into()?,
-            self.assignees.0,
-        ))
+            assignees.iter().map(|assignee| assignee.uid).collect(),
+        )
+        .with_weights(
+            assignees
+                .iter()
+                .map(|assignee| (assignee.uid, assignee.weight))
+                .collect(),
+        )
+        .with_timezone(Timezone::new(&self.timezone)?)
+        .with_week_interval(week_interval)
+        .with_exclusions(self.exclusions.0)
+        .with_assignees_per_occurrence(assignees_per_occurrence)
+        .with_rotation(rotation)
+        .with_rotation_offset(rotation_offset)
+        .with_catch_up(catch_up);
+
+        if let Some(until) = until {
+            schedule = schedule.with_until(until);
+        }
+
+        if let Some(occurrence_limit) = occurrence_limit {
+            schedule = schedule.with_occurrence_limit(occurrence_limit);
+        }
+
+        if let Some(handoff_notice) = handoff_notice {
+            schedule = schedule.with_handoff_notice(handoff_notice);
+        }
+
+        if let Some(created) = created {
+            schedule = schedule.with_created(created);
+        }
+
+        Ok(schedule)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use time::{macros::time, Date, Weekday};
+    use crate::schedule::Exclusions;
+    use model::StoredWeeklyTimes;
+    use time::macros::{datetime, time};
+    use time::{Date, Weekday};
 
     #[test]
     fn it_converts_from_schedule() -> Result<(), Box<dyn std::error::Error>> {
@@ -87,16 +246,31 @@ mod tests {
         );
 
         let expected_provider = Provider {
-            weekly_times: StoredWeeklyTimes(
+            recurrence: StoredRecurrence::Weekly(StoredWeeklyTimes(
                 vec![(Weekday::Monday, vec![time!(10:30:00), time!(22:30:00)])]
                     .into_iter()
                     .collect(),
-            ),
+            )),
             start_week: StoredIsoWeek {
                 week: 2,
                 year: 2020,
             },
-            assignees: StoredAssignees(vec![1, 2, 3]),
+            assignees: StoredAssignees::from(vec![
+                Assignee::from(1),
+                Assignee::from(2),
+                Assignee::from(3),
+            ]),
+            timezone: String::from("UTC"),
+            week_interval: 1,
+            exclusions: StoredExclusions(Exclusions::default()),
+            until: None,
+            occurrence_limit: None,
+            assignees_per_occurrence: 1,
+            rotation: String::from("round-robin"),
+            rotation_offset: 0,
+            handoff_notice: None,
+            catch_up: String::from("skip"),
+            created: None,
         };
 
         assert_eq!(expected_provider, schedule.into());
@@ -106,16 +280,31 @@ mod tests {
     #[test]
     fn it_converts_to_schedule() -> Result<(), Box<dyn std::error::Error>> {
         let provider = Provider {
-            weekly_times: StoredWeeklyTimes(
+            recurrence: StoredRecurrence::Weekly(StoredWeeklyTimes(
                 vec![(Weekday::Monday, vec![time!(10:30:00), time!(22:30:00)])]
                     .into_iter()
                     .collect(),
-            ),
+            )),
             start_week: StoredIsoWeek {
                 week: 2,
                 year: 2020,
             },
-            assignees: StoredAssignees(vec![1, 2, 3]),
+            assignees: StoredAssignees::from(vec![
+                Assignee::from(1),
+                Assignee::from(2),
+                Assignee::from(3),
+            ]),
+            timezone: String::from("UTC"),
+            week_interval: 1,
+            exclusions: StoredExclusions(Exclusions::default()),
+            until: None,
+            occurrence_limit: None,
+            assignees_per_occurrence: 1,
+            rotation: String::from("round-robin"),
+            rotation_offset: 0,
+            handoff_notice: None,
+            catch_up: String::from("skip"),
+            created: None,
         };
 
         let expected_schedule = Schedule::new(
@@ -136,16 +325,31 @@ mod tests {
     fn it_fails_to_convert_to_schedule_with_invalid_iso_week(
     ) -> Result<(), Box<dyn std::error::Error>> {
         let provider = Provider {
-            weekly_times: StoredWeeklyTimes(
+            recurrence: StoredRecurrence::Weekly(StoredWeeklyTimes(
                 vec![(Weekday::Monday, vec![time!(10:30:00), time!(22:30:00)])]
                     .into_iter()
                     .collect(),
-            ),
+            )),
             start_week: StoredIsoWeek {
                 week: 256,
                 year: 2020,
             },
-            assignees: StoredAssignees(vec![1, 2, 3]),
+            assignees: StoredAssignees::from(vec![
+                Assignee::from(1),
+                Assignee::from(2),
+                Assignee::from(3),
+            ]),
+            timezone: String::from("UTC"),
+            week_interval: 1,
+            exclusions: StoredExclusions(Exclusions::default()),
+            until: None,
+            occurrence_limit: None,
+            assignees_per_occurrence: 1,
+            rotation: String::from("round-robin"),
+            rotation_offset: 0,
+            handoff_notice: None,
+            catch_up: String::from("skip"),
+            created: None,
         };
 
         let expected_error: Result<Schedule, _> = Err(Error::WeekTooLarge(256));
@@ -153,4 +357,138 @@ mod tests {
         assert_eq!(expected_error, provider.try_into());
         Ok(())
     }
+
+    #[test]
+    fn it_fails_to_convert_to_schedule_with_invalid_timezone() {
+        let provider = Provider {
+            recurrence: StoredRecurrence::Weekly(StoredWeeklyTimes(
+                vec![(Weekday::Monday, vec![time!(10:30:00), time!(22:30:00)])]
+                    .into_iter()
+                    .collect(),
+            )),
+            start_week: StoredIsoWeek {
+                week: 2,
+                year: 2020,
+            },
+            assignees: StoredAssignees::from(vec![
+                Assignee::from(1),
+                Assignee::from(2),
+                Assignee::from(3),
+            ]),
+            timezone: String::from("Black/Lodge"),
+            week_interval: 1,
+            exclusions: StoredExclusions(Exclusions::default()),
+            until: None,
+            occurrence_limit: None,
+            assignees_per_occurrence: 1,
+            rotation: String::from("round-robin"),
+            rotation_offset: 0,
+            handoff_notice: None,
+            catch_up: String::from("skip"),
+            created: None,
+        };
+
+        let expected_error: Result<Schedule, _> =
+            Err(Error::InvalidTimezone(String::from("Black/Lodge")));
+
+        assert_eq!(expected_error, provider.try_into());
+    }
+
+    #[test]
+    fn it_fails_to_convert_to_schedule_with_invalid_week_interval() {
+        let provider = Provider {
+            recurrence: StoredRecurrence::Weekly(StoredWeeklyTimes(
+                vec![(Weekday::Monday, vec![time!(10:30:00)])]
+                    .into_iter()
+                    .collect(),
+            )),
+            start_week: StoredIsoWeek {
+                week: 2,
+                year: 2020,
+            },
+            assignees: StoredAssignees::from(vec![
+                Assignee::from(1),
+                Assignee::from(2),
+                Assignee::from(3),
+            ]),
+            timezone: String::from("UTC"),
+            week_interval: 0,
+            exclusions: StoredExclusions(Exclusions::default()),
+            until: None,
+            occurrence_limit: None,
+            assignees_per_occurrence: 1,
+            rotation: String::from("round-robin"),
+            rotation_offset: 0,
+            handoff_notice: None,
+            catch_up: String::from("skip"),
+            created: None,
+        };
+
+        let expected_error: Result<Schedule, _> = Err(Error::InvalidWeekInterval(0));
+
+        assert_eq!(expected_error, provider.try_into());
+    }
+
+    #[test]
+    fn it_fails_to_convert_to_schedule_with_invalid_rotation() {
+        let provider = Provider {
+            recurrence: StoredRecurrence::Weekly(StoredWeeklyTimes(
+                vec![(Weekday::Monday, vec![time!(10:30:00)])]
+                    .into_iter()
+                    .collect(),
+            )),
+            start_week: StoredIsoWeek {
+                week: 2,
+                year: 2020,
+            },
+            assignees: StoredAssignees::from(vec![
+                Assignee::from(1),
+                Assignee::from(2),
+                Assignee::from(3),
+            ]),
+            timezone: String::from("UTC"),
+            week_interval: 1,
+            exclusions: StoredExclusions(Exclusions::default()),
+            until: None,
+            occurrence_limit: None,
+            assignees_per_occurrence: 1,
+            rotation: String::from("alphabetical"),
+            rotation_offset: 0,
+            handoff_notice: None,
+            catch_up: String::from("skip"),
+            created: None,
+        };
+
+        let result: Result<Schedule, _> = provider.try_into();
+
+        assert!(matches!(result, Err(Error::InvalidRotation(_))));
+    }
+
+    #[test]
+    fn it_converts_creation_times_in_utc() -> Result<(), Box<dyn std::error::Error>> {
+        let created = datetime!(2020-01-09 12:00:00 +02:00);
+        let schedule = Schedule::new(
+            vec![(Weekday::Monday, vec![time!(10:30:00)])]
+                .into_iter()
+                .collect(),
+            Date::from_iso_week_date(2020, 2, Weekday::Monday)?
+                .midnight()
+                .assume_utc(),
+            vec![1],
+        )
+        .with_created(created);
+
+        let mut provider = Provider::from(schedule);
+        assert_eq!(Some(String::from("2020-01-09T10:00:00Z")), provider.created);
+
+        let schedule: Schedule = provider.try_into()?;
+        assert_eq!(Some(created), schedule.created);
+
+        provider = Provider::from(schedule);
+        provider.created = Some(String::from("Thursday"));
+        let result: Result<Schedule, _> = provider.try_into();
+        assert!(matches!(result, Err(Error::InvalidCreated(_))));
+
+        Ok(())
+    }
 }
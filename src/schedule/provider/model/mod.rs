@@ -1,9 +1,15 @@
 //! Models for serialized scheduling data in persistent storage
 
 mod stored_assignees;
+mod stored_exclusions;
 mod stored_iso_week;
+mod stored_monthly_times;
+mod stored_recurrence;
 mod stored_weekly_times;
 
 pub use stored_assignees::StoredAssignees;
+pub use stored_exclusions::StoredExclusions;
 pub use stored_iso_week::StoredIsoWeek;
+pub use stored_monthly_times::StoredMonthlyTimes;
+pub use stored_recurrence::StoredRecurrence;
 pub use stored_weekly_times::StoredWeeklyTimes;
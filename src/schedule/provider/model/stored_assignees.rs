@@ -1,5 +1,6 @@
 //! Model for serialized scheduled assignees in persistent storage
 
+use crate::schedule::Assignee;
 use diesel::deserialize::{FromSql, Result as FromSqlResult};
 use diesel::serialize::{Output, Result as ToSqlResult, ToSql};
 use diesel::{backend::Backend, sql_types::Text};
@@ -9,13 +10,47 @@ use std::io::Write;
 /// Model for serialized scheduled assignees in persistent storage
 #[derive(AsExpression, Debug, Deserialize, Eq, FromSqlRow, PartialEq, Serialize)]
 #[sql_type = "Text"]
-pub struct StoredAssignees(pub Vec<i32>);
+pub struct StoredAssignees(pub Vec<StoredAssignee>);
+
+/// Model for a serialized assignee, which was stored as a plain uid before assignees had weights
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum StoredAssignee {
+    /// Assignee with their weight
+    Weighted(Assignee),
+    /// Uid of an assignee with a weight of 1
+    Uid(i32),
+}
+
+impl From<Vec<Assignee>> for StoredAssignees {
+    fn from(assignees: Vec<Assignee>) -> Self {
+        Self(
+            assignees
+                .into_iter()
+                .map(StoredAssignee::Weighted)
+                .collect(),
+        )
+    }
+}
+
+impl From<StoredAssignees> for Vec<Assignee> {
+    fn from(stored_assignees: StoredAssignees) -> Self {
+        stored_assignees
+            .0
+            .into_iter()
+            .map(|stored_assignee| match stored_assignee {
+                StoredAssignee::Weighted(assignee) => assignee,
+                StoredAssignee::Uid(uid) => Assignee::from(uid),
+            })
+            .collect()
+    }
+}
 
 impl<TDatabase: Backend> ToSql<Text, TDatabase> for StoredAssignees
 where
     String: ToSql<Text, TDatabase>,
 {
-    /// Converts this model to a SQL type by serializing it as a JSON array of numbers
+    /// Converts this model to a SQL type by serializing it as a JSON array of objects
     fn to_sql<W: Write>(&self, out: &mut Output<W, TDatabase>) -> ToSqlResult {
         (serde_json::to_string(&self.0)?).to_sql(out)
     }
@@ -0,0 +1,35 @@
+//! Model for serialized schedule exclusions in persistent storage
+
+use crate::schedule::Exclusions;
+use diesel::deserialize::{FromSql, Result as FromSqlResult};
+use diesel::serialize::{Output, Result as ToSqlResult, ToSql};
+use diesel::{backend::Backend, sql_types::Text};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+
+/// Model for serialized schedule exclusions in persistent storage
+#[derive(AsExpression, Debug, Deserialize, Eq, FromSqlRow, PartialEq, Serialize)]
+#[sql_type = "Text"]
+pub struct StoredExclusions(pub Exclusions);
+
+impl<TDatabase: Backend> ToSql<Text, TDatabase> for StoredExclusions
+where
+    String: ToSql<Text, TDatabase>,
+{
+    /// Converts this model to a SQL type
+    /// Data is serialized as a JSON object of excluded date strings and the rotation setting
+    fn to_sql<W: Write>(&self, out: &mut Output<W, TDatabase>) -> ToSqlResult {
+        (serde_json::to_string(&self.0)?).to_sql(out)
+    }
+}
+
+impl<TDatabase: Backend> FromSql<Text, TDatabase> for StoredExclusions
+where
+    String: FromSql<Text, TDatabase>,
+{
+    /// Creates this model from a SQL type
+    /// Data is serialized as a JSON object for easy deserialization
+    fn from_sql(bytes: Option<&TDatabase::RawValue>) -> FromSqlResult<Self> {
+        Ok(serde_json::from_str(&String::from_sql(bytes)?)?)
+    }
+}
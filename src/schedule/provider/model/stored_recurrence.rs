@@ -0,0 +1,84 @@
+//! Model for serialized recurring times in persistent storage
+
+use super::{StoredMonthlyTimes, StoredWeeklyTimes};
+use crate::schedule::{model::Recurrence, CronExpression, RecurrenceRule};
+use diesel::deserialize::{FromSql, Result as FromSqlResult};
+use diesel::serialize::{Output, Result as ToSqlResult, ToSql};
+use diesel::{backend::Backend, sql_types::Text};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use time::OffsetDateTime;
+
+/// Model for serialized weekly or monthly times, a single datetime, a recurrence rule, or a cron
+/// expression in persistent storage
+#[derive(AsExpression, Debug, Deserialize, Eq, FromSqlRow, PartialEq, Serialize)]
+#[sql_type = "Text"]
+#[serde(untagged)]
+pub enum StoredRecurrence {
+    /// Times of day on weekdays
+    Weekly(StoredWeeklyTimes),
+    /// Times of day on days of the month
+    Monthly(StoredMonthlyTimes),
+    /// Single datetime
+    Once {
+        /// Datetime at which the schedule occurs
+        #[serde(with = "crate::schedule::rfc3339")]
+        once: OffsetDateTime,
+    },
+    /// Recurrence rule, stored exactly as written
+    Rule {
+        /// iCalendar recurrence rule
+        rrule: RecurrenceRule,
+    },
+    /// Cron expression, stored exactly as written
+    Cron {
+        /// Standard cron expression
+        cron: CronExpression,
+    },
+}
+
+impl From<Recurrence> for StoredRecurrence {
+    fn from(recurrence: Recurrence) -> Self {
+        match recurrence {
+            Recurrence::Weekly(weekly_times) => Self::Weekly(StoredWeeklyTimes(weekly_times)),
+            Recurrence::Monthly(monthly_times) => Self::Monthly(StoredMonthlyTimes(monthly_times)),
+            Recurrence::Once { time } => Self::Once { once: time },
+            Recurrence::Rule { rule } => Self::Rule { rrule: rule },
+            Recurrence::Cron { cron } => Self::Cron { cron },
+        }
+    }
+}
+
+impl From<StoredRecurrence> for Recurrence {
+    fn from(recurrence: StoredRecurrence) -> Self {
+        match recurrence {
+            StoredRecurrence::Weekly(weekly_times) => Self::Weekly(weekly_times.0),
+            StoredRecurrence::Monthly(monthly_times) => Self::Monthly(monthly_times.0),
+            StoredRecurrence::Once { once } => Self::Once { time: once },
+            StoredRecurrence::Rule { rrule } => Self::Rule { rule: rrule },
+            StoredRecurrence::Cron { cron } => Self::Cron { cron },
+        }
+    }
+}
+
+impl<TDatabase: Backend> ToSql<Text, TDatabase> for StoredRecurrence
+where
+    String: ToSql<Text, TDatabase>,
+{
+    /// Converts this model to a SQL type
+    /// Data is serialized as a JSON object of weekdays or days of the month to time strings
+    fn to_sql<W: Write>(&self, out: &mut Output<W, TDatabase>) -> ToSqlResult {
+        (serde_json::to_string(&self)?).to_sql(out)
+    }
+}
+
+impl<TDatabase: Backend> FromSql<Text, TDatabase> for StoredRecurrence
+where
+    String: FromSql<Text, TDatabase>,
+{
+    /// Creates this model from a SQL type
+    /// Data is serialized as a JSON object for easy deserialization
+    fn from_sql(bytes: Option<&TDatabase::RawValue>) -> FromSqlResult<Self> {
+        Ok(serde_json::from_str(&String::from_sql(bytes)?)?)
+    }
+}
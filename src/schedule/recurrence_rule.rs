@@ -0,0 +1,696 @@
+//! Recurrence rules in the iCalendar `RRULE` format of RFC 5545
+
+use super::Timezone;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+use time::format_description::FormatItem;
+use time::macros::format_description;
+use time::util::days_in_year_month;
+use time::{Date, Duration, Month, PrimitiveDateTime, Time, Weekday};
+
+/// Format of iCalendar dates, such as `20261225`
+const DATE_FORMAT: &[FormatItem<'static>] = format_description!("[year][month][day]");
+
+/// Format of iCalendar datetimes without the UTC designator, such as `20261225T090000`
+const DATETIME_FORMAT: &[FormatItem<'static>] =
+    format_description!("[year][month][day]T[hour][minute][second]");
+
+/// Two-letter iCalendar weekday codes in order from Monday
+const WEEKDAYS: [(&str, Weekday); 7] = [
+    ("MO", Weekday::Monday),
+    ("TU", Weekday::Tuesday),
+    ("WE", Weekday::Wednesday),
+    ("TH", Weekday::Thursday),
+    ("FR", Weekday::Friday),
+    ("SA", Weekday::Saturday),
+    ("SU", Weekday::Sunday),
+];
+
+/// Number of consecutive periods without any times after which a rule is considered to never
+/// occur again, which is enough to find a daily rule for the 29th of February
+const MAX_EMPTY_PERIODS: u32 = 4000;
+
+/// Period over which a recurrence rule repeats
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Frequency {
+    /// Repeats every day
+    Daily,
+    /// Repeats every week, starting on Monday
+    Weekly,
+    /// Repeats every month
+    Monthly,
+}
+
+/// Date or datetime value of `UNTIL` or `EXDATE`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum RuleTime {
+    /// Whole wall-clock date in the schedule's time zone
+    Date(Date),
+    /// Wall-clock datetime in the schedule's time zone
+    Local(PrimitiveDateTime),
+    /// Absolute datetime in UTC
+    Utc(PrimitiveDateTime),
+}
+
+impl RuleTime {
+    /// Orders a wall-clock time in the given time zone relative to this date or datetime
+    fn cmp_local(&self, local_time: PrimitiveDateTime, timezone: &Timezone) -> Ordering {
+        match self {
+            Self::Date(date) => local_time.date().cmp(date),
+            Self::Local(time) => local_time.cmp(time),
+            Self::Utc(time) => {
+                let time = timezone.to_local(time.assume_utc());
+                local_time.cmp(&PrimitiveDateTime::new(time.date(), time.time()))
+            }
+        }
+    }
+}
+
+impl FromStr for RuleTime {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = |_| format!("invalid date or datetime `{}`", value);
+
+        match value.strip_suffix('Z') {
+            Some(utc) => PrimitiveDateTime::parse(utc, DATETIME_FORMAT)
+                .map(Self::Utc)
+                .map_err(invalid),
+            None if value.contains('T') => PrimitiveDateTime::parse(value, DATETIME_FORMAT)
+                .map(Self::Local)
+                .map_err(invalid),
+            None => Date::parse(value, DATE_FORMAT)
+                .map(Self::Date)
+                .map_err(invalid),
+        }
+    }
+}
+
+/// Recurrence rule in the iCalendar `RRULE` format, optionally followed by `EXDATE` lines
+///
+/// Rules are written as `FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH;BYHOUR=9` and support the `FREQ`
+/// values `DAILY`, `WEEKLY` and `MONTHLY`, along with `INTERVAL`, `BYDAY`, `BYMONTHDAY`,
+/// `BYMONTH`, `BYHOUR`, `BYMINUTE`, `BYSECOND`, `UNTIL`, `COUNT` and `WKST=MO`.
+/// Excluded dates are written on a separate line, such as `EXDATE:20261228T090000`.
+///
+/// The rule starts when the schedule was created, so times and days which are not given by the
+/// rule default to that time and day, and `COUNT` and `INTERVAL` are counted from then.
+/// Dates and datetimes without a trailing `Z` are wall-clock times in the schedule's time zone.
+/// The rule is kept exactly as written, so it serializes back to the original text
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct RecurrenceRule {
+    /// Rule exactly as written
+    source: String,
+    /// Period over which the rule repeats
+    frequency: Frequency,
+    /// Number of periods between each period in which the rule occurs
+    interval: u32,
+    /// Weekdays on which the rule occurs, with an optional nth weekday of the month
+    by_day: Vec<(Option<i8>, Weekday)>,
+    /// Days of the month on which the rule occurs, negative from the end of the month
+    by_month_day: Vec<i8>,
+    /// Months in which the rule occurs
+    by_month: Vec<Month>,
+    /// Hours of the day at which the rule occurs
+    by_hour: Vec<u8>,
+    /// Minutes of the hour at which the rule occurs
+    by_minute: Vec<u8>,
+    /// Seconds of the minute at which the rule occurs
+    by_second: Vec<u8>,
+    /// Last date or datetime at which the rule occurs
+    until: Option<RuleTime>,
+    /// Number of times the rule occurs, including excluded dates
+    count: Option<u32>,
+    /// Dates and datetimes on which the rule does not occur
+    exdates: Vec<RuleTime>,
+}
+
+impl RecurrenceRule {
+    /// Whether the rule ends because of `UNTIL` or `COUNT`
+    #[must_use]
+    pub fn has_end(&self) -> bool {
+        self.until.is_some() || self.count.is_some()
+    }
+
+    /// Determines the next wall-clock time of the rule at or after the given wall-clock time,
+    /// for a rule starting at the given wall-clock time
+    pub(crate) fn get_next_time(
+        &self,
+        start_time: PrimitiveDateTime,
+        local_time: PrimitiveDateTime,
+        timezone: &Timezone,
+    ) -> Option<PrimitiveDateTime> {
+        // Counted rules must be evaluated from the start to know when they end
+        let first_period = match self.count {
+            Some(_) => 0,
+            None => self.get_period(start_time.date(), local_time.date()).max(0),
+        };
+
+        self.get_times(start_time, first_period, timezone)
+            .filter(|time| *time >= local_time)
+            .find(|time| !self.is_excluded(*time, timezone))
+    }
+
+    /// Counts the wall-clock times of the rule which were not excluded up to the given
+    /// wall-clock time, for a rule starting at the given wall-clock time
+    pub(crate) fn count_times(
+        &self,
+        start_time: PrimitiveDateTime,
+        local_time: PrimitiveDateTime,
+        timezone: &Timezone,
+    ) -> usize {
+        self.get_times(start_time, 0, timezone)
+            .take_while(|time| *time <= local_time)
+            .filter(|time| !self.is_excluded(*time, timezone))
+            .count()
+    }
+
+    /// Whether the given wall-clock time is excluded by `EXDATE`
+    fn is_excluded(&self, local_time: PrimitiveDateTime, timezone: &Timezone) -> bool {
+        self.exdates
+            .iter()
+            .any(|exdate| exdate.cmp_local(local_time, timezone) == Ordering::Equal)
+    }
+
+    /// Provides the wall-clock times of the rule in order, including excluded times,
+    /// beginning with the period with the given index
+    fn get_times<'a>(
+        &'a self,
+        start_time: PrimitiveDateTime,
+        first_period: i64,
+        timezone: &'a Timezone,
+    ) -> impl Iterator<Item = PrimitiveDateTime> + 'a {
+        let interval = i64::from(self.interval);
+        let first_period = first_period - first_period.rem_euclid(interval);
+        let count = self.count.map_or(usize::MAX, |count| {
+            usize::try_from(count).unwrap_or(usize::MAX)
+        });
+
+        (0..)
+            .map_while(move |index: i64| {
+                let period = index.checked_mul(interval)?.checked_add(first_period)?;
+                self.get_times_in_period(start_time, period)
+            })
+            // Stop searching once the rule has gone too long without occurring
+            .scan(0, |empty_periods, times| {
+                *empty_periods = if times.is_empty() {
+                    *empty_periods + 1
+                } else {
+                    0
+                };
+                Some(times).filter(|_| *empty_periods <= MAX_EMPTY_PERIODS)
+            })
+            .flatten()
+            .filter(move |time| *time >= start_time)
+            .take(count)
+            .take_while(move |time| {
+                self.until.map_or(true, |until| {
+                    until.cmp_local(*time, timezone) != Ordering::Greater
+                })
+            })
+    }
+
+    /// Determines the index of the period containing the given date, relative to the period
+    /// containing the start date
+    fn get_period(&self, start_date: Date, date: Date) -> i64 {
+        match self.frequency {
+            Frequency::Daily => (date - start_date).whole_days(),
+            Frequency::Weekly => (get_monday(date) - get_monday(start_date)).whole_weeks(),
+            Frequency::Monthly => get_month_index(date) - get_month_index(start_date),
+        }
+    }
+
+    /// Provides the sorted wall-clock times of the rule within the period with the given index,
+    /// or `None` if the period is out of range
+    fn get_times_in_period(
+        &self,
+        start_time: PrimitiveDateTime,
+        period: i64,
+    ) -> Option<Vec<PrimitiveDateTime>> {
+        let start_date = start_time.date();
+
+        let dates: Vec<Date> = match self.frequency {
+            Frequency::Daily => vec![start_date.checked_add(Duration::days(period))?],
+            Frequency::Weekly => {
+                let monday = get_monday(start_date).checked_add(Duration::weeks(period))?;
+                (0..7)
+                    .filter_map(|day| monday.checked_add(Duration::days(day)))
+                    .collect()
+            }
+            Frequency::Monthly => {
+                let month_index = get_month_index(start_date).checked_add(period)?;
+                let year = i32::try_from(month_index.div_euclid(12)).ok()?;
+                let month =
+                    Month::try_from(u8::try_from(month_index.rem_euclid(12) + 1).ok()?).ok()?;
+                let first_day = Date::from_calendar_date(year, month, 1).ok()?;
+                (0..days_in_year_month(year, month))
+                    .filter_map(|day| first_day.checked_add(Duration::days(i64::from(day))))
+                    .collect()
+            }
+        };
+
+        let hours = or_default(&self.by_hour, start_time.hour());
+        let minutes = or_default(&self.by_minute, start_time.minute());
+        let seconds = or_default(&self.by_second, start_time.second());
+
+        let mut times_of_day: Vec<Time> = hours
+            .iter()
+            .flat_map(|hour| minutes.iter().map(move |minute| (*hour, *minute)))
+            .flat_map(|(hour, minute)| {
+                seconds
+                    .iter()
+                    .filter_map(move |second| Time::from_hms(hour, minute, *second).ok())
+            })
+            .collect();
+        times_of_day.sort();
+
+        let mut times: Vec<PrimitiveDateTime> = dates
+            .into_iter()
+            .filter(|date| self.is_on_date(start_date, *date))
+            .flat_map(|date| times_of_day.iter().map(move |time| date.with_time(*time)))
+            .collect();
+
+        times.sort();
+        times.dedup();
+        Some(times)
+    }
+
+    /// Whether the rule occurs on the given date, for a rule starting on the given date
+    fn is_on_date(&self, start_date: Date, date: Date) -> bool {
+        let days_in_month = days_in_year_month(date.year(), date.month());
+
+        let is_in_month = self.by_month.is_empty() || self.by_month.contains(&date.month());
+
+        let is_on_month_day = self.by_month_day.is_empty()
+            || self.by_month_day.iter().any(|month_day| match month_day {
+                day if *day > 0 => i16::from(*day) == i16::from(date.day()),
+                day => i16::from(days_in_month) + 1 + i16::from(*day) == i16::from(date.day()),
+            });
+
+        let is_on_weekday = self.by_day.is_empty()
+            || self.by_day.iter().any(|(nth, weekday)| {
+                let nth_in_month = match nth {
+                    Some(nth) if *nth > 0 => (date.day() - 1) / 7 + 1,
+                    Some(_) => (days_in_month - date.day()) / 7 + 1,
+                    None => 0,
+                };
+
+                *weekday == date.weekday()
+                    && nth.map_or(true, |nth| nth.unsigned_abs() == nth_in_month)
+            });
+
+        // Rules without days occur on the same day of the week or month as the start date
+        let is_on_default_day = match self.frequency {
+            _ if !self.by_day.is_empty() || !self.by_month_day.is_empty() => true,
+            Frequency::Daily => true,
+            Frequency::Weekly => date.weekday() == start_date.weekday(),
+            Frequency::Monthly => date.day() == start_date.day(),
+        };
+
+        is_in_month && is_on_month_day && is_on_weekday && is_on_default_day
+    }
+}
+
+/// Determines the Monday at the start of the week containing the given date
+fn get_monday(date: Date) -> Date {
+    date - Duration::days(i64::from(date.weekday().number_days_from_monday()))
+}
+
+/// Determines the number of months between year 0 and the month containing the given date
+fn get_month_index(date: Date) -> i64 {
+    i64::from(date.year()) * 12 + i64::from(u8::from(date.month())) - 1
+}
+
+/// Provides the given values, or the given default if there are none
+fn or_default(values: &[u8], default: u8) -> Vec<u8> {
+    if values.is_empty() {
+        vec![default]
+    } else {
+        values.to_vec()
+    }
+}
+
+/// Parses a comma-separated list of values within the given inclusive range
+fn parse_list<T>(
+    name: &str,
+    value: &str,
+    range: std::ops::RangeInclusive<T>,
+) -> Result<Vec<T>, String>
+where
+    T: FromStr + PartialOrd,
+{
+    value
+        .split(',')
+        .map(|item| {
+            item.parse()
+                .ok()
+                .filter(|item| range.contains(item))
+                .ok_or_else(|| format!("invalid {} value `{}`", name, item))
+        })
+        .collect()
+}
+
+/// Parses a `BYDAY` value, such as `MO`, `2TU` or `-1FR`
+fn parse_by_day(value: &str) -> Result<(Option<i8>, Weekday), String> {
+    let invalid = || format!("invalid BYDAY value `{}`", value);
+
+    let split = value.len().checked_sub(2).ok_or_else(invalid)?;
+    let (nth, code) = (value.get(..split).ok_or_else(invalid)?, value.get(split..));
+
+    let weekday = WEEKDAYS
+        .iter()
+        .find(|(weekday_code, _)| Some(*weekday_code) == code)
+        .map(|(_, weekday)| *weekday)
+        .ok_or_else(invalid)?;
+
+    let nth = match nth {
+        "" => None,
+        nth => Some(
+            nth.parse::<i8>()
+                .ok()
+                .filter(|nth| (1..=5).contains(&nth.unsigned_abs()))
+                .ok_or_else(invalid)?,
+        ),
+    };
+
+    Ok((nth, weekday))
+}
+
+impl RecurrenceRule {
+    /// Parses a single part of the rule, such as `FREQ=WEEKLY`
+    fn parse_part(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "FREQ" => {
+                self.frequency = match value {
+                    "DAILY" => Frequency::Daily,
+                    "WEEKLY" => Frequency::Weekly,
+                    "MONTHLY" => Frequency::Monthly,
+                    _ => return Err(format!("unsupported FREQ value `{}`", value)),
+                };
+            }
+            "INTERVAL" => {
+                self.interval = value
+                    .parse()
+                    .ok()
+                    .filter(|interval| *interval > 0)
+                    .ok_or_else(|| format!("invalid INTERVAL value `{}`", value))?;
+            }
+            "BYDAY" => {
+                self.by_day = value
+                    .split(',')
+                    .map(parse_by_day)
+                    .collect::<Result<_, _>>()?;
+            }
+            "BYMONTHDAY" => {
+                self.by_month_day = parse_list(name, value, -31..=31)?;
+                if self.by_month_day.contains(&0) {
+                    return Err(String::from("invalid BYMONTHDAY value `0`"));
+                }
+            }
+            "BYMONTH" => {
+                self.by_month = parse_list(name, value, 1..=12)?
+                    .into_iter()
+                    .filter_map(|month| Month::try_from(month).ok())
+                    .collect();
+            }
+            "BYHOUR" => self.by_hour = parse_list(name, value, 0..=23)?,
+            "BYMINUTE" => self.by_minute = parse_list(name, value, 0..=59)?,
+            "BYSECOND" => self.by_second = parse_list(name, value, 0..=59)?,
+            "UNTIL" => self.until = Some(value.parse()?),
+            "COUNT" => {
+                self.count = Some(
+                    value
+                        .parse()
+                        .ok()
+                        .filter(|count| *count > 0)
+                        .ok_or_else(|| format!("invalid COUNT value `{}`", value))?,
+                );
+            }
+            "WKST" if value == "MO" => {}
+            "WKST" => return Err(String::from("only WKST=MO is supported")),
+            _ => return Err(format!("unsupported rule part `{}`", name)),
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for RecurrenceRule {
+    type Err = String;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let mut rule = None;
+        let mut exdates = Vec::new();
+
+        // Each property is on its own line, and a bare rule is treated as an `RRULE` property
+        for line in source.split_whitespace() {
+            match line.split_once(':') {
+                Some((name, value)) => {
+                    // Property parameters such as `VALUE=DATE` are implied by the value
+                    let name = name.split(';').next().unwrap_or_default();
+                    match name.to_uppercase().as_str() {
+                        "RRULE" if rule.is_none() => rule = Some(value),
+                        "EXDATE" => {
+                            for exdate in value.split(',') {
+                                exdates.push(exdate.parse()?);
+                            }
+                        }
+                        _ => return Err(format!("unsupported property `{}`", line)),
+                    }
+                }
+                None if rule.is_none() => rule = Some(line),
+                None => return Err(format!("unexpected `{}` after the rule", line)),
+            }
+        }
+
+        let mut recurrence_rule = Self {
+            source: source.to_string(),
+            frequency: Frequency::Daily,
+            interval: 1,
+            by_day: Vec::new(),
+            by_month_day: Vec::new(),
+            by_month: Vec::new(),
+            by_hour: Vec::new(),
+            by_minute: Vec::new(),
+            by_second: Vec::new(),
+            until: None,
+            count: None,
+            exdates,
+        };
+
+        let mut names = Vec::new();
+
+        for part in rule.ok_or("missing RRULE")?.split(';') {
+            let (name, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("invalid rule part `{}`", part))?;
+            let name = name.to_uppercase();
+
+            if names.contains(&name) {
+                return Err(format!("{} is given more than once", name));
+            }
+
+            recurrence_rule.parse_part(&name, &value.to_uppercase())?;
+            names.push(name);
+        }
+
+        if !names.iter().any(|name| name == "FREQ") {
+            return Err(String::from("missing FREQ"));
+        }
+
+        if recurrence_rule.until.is_some() && recurrence_rule.count.is_some() {
+            return Err(String::from("UNTIL and COUNT cannot both be given"));
+        }
+
+        if recurrence_rule.frequency != Frequency::Monthly {
+            if recurrence_rule.by_day.iter().any(|(nth, _)| nth.is_some()) {
+                return Err(String::from(
+                    "BYDAY can only have an nth weekday when monthly",
+                ));
+            }
+
+            if recurrence_rule.frequency == Frequency::Weekly
+                && !recurrence_rule.by_month_day.is_empty()
+            {
+                return Err(String::from("BYMONTHDAY cannot be given when weekly"));
+            }
+        }
+
+        Ok(recurrence_rule)
+    }
+}
+
+impl TryFrom<String> for RecurrenceRule {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value
+            .parse()
+            .map_err(|error| format!("Invalid recurrence rule `{}`: {}", value, error))
+    }
+}
+
+impl From<RecurrenceRule> for String {
+    fn from(recurrence_rule: RecurrenceRule) -> Self {
+        recurrence_rule.source
+    }
+}
+
+impl fmt::Display for RecurrenceRule {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{}", self.source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+    fn parse(value: &str) -> std::result::Result<RecurrenceRule, String> {
+        RecurrenceRule::try_from(value.to_string())
+    }
+
+    fn times(
+        rule: &str,
+        start_time: PrimitiveDateTime,
+        count: usize,
+    ) -> Result<Vec<PrimitiveDateTime>> {
+        let rule = parse(rule)?;
+        let timezone = Timezone::default();
+
+        Ok(std::iter::successors(
+            rule.get_next_time(start_time, start_time, &timezone),
+            |time| rule.get_next_time(start_time, *time + Duration::NANOSECOND, &timezone),
+        )
+        .take(count)
+        .collect())
+    }
+
+    #[test]
+    fn it_parses_rules() {
+        assert!(parse("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH;BYHOUR=9").is_ok());
+        assert!(parse("RRULE:FREQ=MONTHLY;BYDAY=-1FR;BYHOUR=18;COUNT=3").is_ok());
+        assert!(parse("FREQ=DAILY;UNTIL=20261231T235959Z\nEXDATE:20261225,20261226").is_ok());
+        assert!(parse("INTERVAL=2").is_err());
+        assert!(parse("FREQ=YEARLY").is_err());
+        assert!(parse("FREQ=WEEKLY;BYDAY=2MO").is_err());
+        assert!(parse("FREQ=DAILY;BYHOUR=24").is_err());
+        assert!(parse("FREQ=DAILY;COUNT=2;UNTIL=20261231").is_err());
+        assert!(parse("FREQ=DAILY;BYSETPOS=1").is_err());
+    }
+
+    #[test]
+    fn it_serializes_rules_exactly_as_written() -> Result<()> {
+        let rule = "RRULE:FREQ=weekly;BYDAY=MO,TH;BYHOUR=9\nEXDATE;VALUE=DATE:20261228";
+
+        assert_eq!(rule, String::from(parse(rule)?));
+        assert_eq!(
+            serde_json::to_string(rule)?,
+            serde_json::to_string(&parse(rule)?)?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_occurs_on_weekdays_every_other_week() -> Result<()> {
+        assert_eq!(
+            vec![
+                datetime!(2026-10-12 09:00),
+                datetime!(2026-10-15 09:00),
+                datetime!(2026-10-26 09:00),
+                datetime!(2026-10-29 09:00),
+            ],
+            times(
+                "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH;BYHOUR=9",
+                datetime!(2026-10-12 00:00),
+                4
+            )?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_occurs_on_nth_weekdays_of_the_month() -> Result<()> {
+        assert_eq!(
+            vec![
+                datetime!(2026-10-13 18:30),
+                datetime!(2026-10-30 18:30),
+                datetime!(2026-11-10 18:30),
+                datetime!(2026-11-27 18:30),
+            ],
+            times(
+                "FREQ=MONTHLY;BYDAY=2TU,-1FR;BYHOUR=18;BYMINUTE=30",
+                datetime!(2026-10-12 00:00),
+                4
+            )?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_ends_after_the_count_including_exdates() -> Result<()> {
+        assert_eq!(
+            vec![datetime!(2026-10-12 08:00), datetime!(2026-10-14 08:00)],
+            times(
+                "FREQ=DAILY;BYHOUR=8;COUNT=3\nEXDATE:20261013T080000",
+                datetime!(2026-10-12 00:00),
+                5
+            )?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_ends_after_the_until_datetime() -> Result<()> {
+        assert_eq!(
+            vec![datetime!(2026-10-12 08:00), datetime!(2026-10-13 08:00)],
+            times(
+                "FREQ=DAILY;BYHOUR=8;UNTIL=20261013T080000Z",
+                datetime!(2026-10-12 00:00),
+                5
+            )?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_counts_times_which_were_not_excluded() -> Result<()> {
+        let rule = parse("FREQ=DAILY;BYHOUR=8,20\nEXDATE:20261013")?;
+        let timezone = Timezone::default();
+
+        assert_eq!(
+            3,
+            rule.count_times(
+                datetime!(2026-10-12 00:00),
+                datetime!(2026-10-14 08:00),
+                &timezone
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_stops_searching_rules_which_never_occur() -> Result<()> {
+        assert!(times(
+            "FREQ=MONTHLY;BYMONTH=2;BYMONTHDAY=30",
+            datetime!(2026-10-12 00:00),
+            1
+        )?
+        .is_empty());
+
+        Ok(())
+    }
+}
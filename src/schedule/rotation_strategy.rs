@@ -0,0 +1,363 @@
+//! Strategies for rotating through a schedule's assignees
+
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::fmt;
+use std::num::NonZeroU32;
+use std::str::FromStr;
+
+/// Progress of a schedule at the time of an occurrence, counting from 0 at the start week
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Turn {
+    /// Number of occurrences counted towards the rotation before this one
+    pub occurrence: usize,
+    /// Number of weeks in which the schedule could occur before this one
+    pub week: usize,
+    /// Number of days before this one
+    pub day: usize,
+}
+
+/// Determines which assignees are assigned to each occurrence of a schedule
+///
+/// Assignees are chosen by walking a sequence of positions, starting from the position for the
+/// turn. Each position is mapped to an index into the assignees, and a schedule with multiple
+/// assignees per occurrence continues along the positions until it has enough assignees
+pub trait RotationStrategy {
+    /// Determines the first position in the rotation for the given turn
+    fn get_position(&self, turn: Turn) -> usize;
+
+    /// Determines the index of the assignee at the given position in the rotation
+    fn get_index(&self, position: usize, assignee_count: usize) -> usize {
+        position % assignee_count
+    }
+}
+
+/// Rotates to the next assignee on every occurrence
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RoundRobin;
+
+impl RotationStrategy for RoundRobin {
+    fn get_position(&self, turn: Turn) -> usize {
+        turn.occurrence
+    }
+}
+
+/// Rotates to the next assignee every week in which the schedule can occur, so the same
+/// assignee is assigned every occurrence within a week
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PerWeek;
+
+impl RotationStrategy for PerWeek {
+    fn get_position(&self, turn: Turn) -> usize {
+        turn.week
+    }
+}
+
+/// Rotates to the next assignee every day, so the same assignee is assigned every occurrence
+/// within a day
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PerDay;
+
+impl RotationStrategy for PerDay {
+    fn get_position(&self, turn: Turn) -> usize {
+        turn.day
+    }
+}
+
+/// Always assigns the first assignees, without rotating
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Fixed;
+
+impl RotationStrategy for Fixed {
+    fn get_position(&self, _turn: Turn) -> usize {
+        0
+    }
+}
+
+/// Rotates through the assignees in a random order on every occurrence
+///
+/// Each assignee is assigned once before anyone is assigned again, and the order is reshuffled
+/// for each round without assigning the same person twice in a row. The order is determined
+/// entirely by the seed, so it's the same every time the schedule is evaluated
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SeededRandom {
+    /// Seed for the order of assignees
+    pub seed: u64,
+}
+
+impl SeededRandom {
+    /// Shuffles the assignee indices for the given round of the rotation
+    fn shuffle(self, round: usize, assignee_count: usize) -> Vec<usize> {
+        let mut indices: Vec<usize> = (0..assignee_count).collect();
+        let mut state = self.seed ^ (u64::try_from(round).unwrap_or(u64::MAX)).rotate_left(32);
+
+        // Fisher-Yates shuffle
+        for index in (1..assignee_count).rev() {
+            let random = usize::try_from(split_mix(&mut state) % (index as u64 + 1)).unwrap_or(0);
+            indices.swap(index, random);
+        }
+
+        indices
+    }
+
+    /// Determines the order of assignee indices for the given round of the rotation
+    ///
+    /// Each round's shuffle is adjusted so it doesn't start with the assignee who was last in the
+    /// previous round, which depends on how every round before it was adjusted
+    fn order(self, round: usize, assignee_count: usize) -> Vec<usize> {
+        let mut indices = Vec::new();
+        let mut previous_last = None;
+
+        for current in 0..=round {
+            indices = self.shuffle(current, assignee_count);
+            if assignee_count > 1 && indices.first().copied() == previous_last {
+                indices.swap(0, 1);
+            }
+            previous_last = indices.last().copied();
+        }
+
+        indices
+    }
+}
+
+impl RotationStrategy for SeededRandom {
+    fn get_position(&self, turn: Turn) -> usize {
+        turn.occurrence
+    }
+
+    fn get_index(&self, position: usize, assignee_count: usize) -> usize {
+        self.order(position / assignee_count, assignee_count)[position % assignee_count]
+    }
+}
+
+/// Assigns whoever has been assigned the fewest occurrences recently, based on the
+/// recorded history of the schedule
+///
+/// Ties go to whoever was assigned least recently, then to whoever comes first in the
+/// assignees. Positions follow the occurrences like `RoundRobin`, which is only used when
+/// no history is available
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Fair {
+    /// Number of days before an occurrence in which past assignments are counted
+    pub window_days: u32,
+}
+
+impl Default for Fair {
+    fn default() -> Self {
+        Self { window_days: 28 }
+    }
+}
+
+impl RotationStrategy for Fair {
+    fn get_position(&self, turn: Turn) -> usize {
+        turn.occurrence
+    }
+}
+
+/// Advances the state of a `SplitMix64` generator and returns the next random number
+fn split_mix(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut value = *state;
+    value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    value ^ (value >> 31)
+}
+
+/// Built-in rotation strategy for a schedule
+///
+/// Written as `round-robin`, `per-week`, `per-day`, `fixed`, `random:<seed>` such as
+/// `random:42`, or `fair` with an optional window in days such as `fair:14`
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Rotation {
+    /// Rotates on every occurrence
+    RoundRobin(RoundRobin),
+    /// Rotates every week
+    PerWeek(PerWeek),
+    /// Rotates every day
+    PerDay(PerDay),
+    /// Never rotates
+    Fixed(Fixed),
+    /// Rotates in a seeded random order
+    SeededRandom(SeededRandom),
+    /// Assigns whoever has been assigned the least recently
+    Fair(Fair),
+}
+
+impl Rotation {
+    /// Provides the strategy for this rotation
+    #[must_use]
+    pub fn strategy(&self) -> &dyn RotationStrategy {
+        match self {
+            Self::RoundRobin(strategy) => strategy,
+            Self::PerWeek(strategy) => strategy,
+            Self::PerDay(strategy) => strategy,
+            Self::Fixed(strategy) => strategy,
+            Self::SeededRandom(strategy) => strategy,
+            Self::Fair(strategy) => strategy,
+        }
+    }
+}
+
+impl Default for Rotation {
+    fn default() -> Self {
+        Self::RoundRobin(RoundRobin)
+    }
+}
+
+impl FromStr for Rotation {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "Invalid rotation `{}`, should be round-robin, per-week, per-day, fixed, random:<seed> or fair[:<days>]",
+                value
+            )
+        };
+
+        match value.trim().to_lowercase().as_str() {
+            "round-robin" => Ok(Self::RoundRobin(RoundRobin)),
+            "per-week" => Ok(Self::PerWeek(PerWeek)),
+            "per-day" => Ok(Self::PerDay(PerDay)),
+            "fixed" => Ok(Self::Fixed(Fixed)),
+            "fair" => Ok(Self::Fair(Fair::default())),
+            value => match value.split_once(':') {
+                Some(("random", seed)) => Ok(Self::SeededRandom(SeededRandom {
+                    seed: seed.parse().map_err(|_| invalid())?,
+                })),
+                Some(("fair", window_days)) => Ok(Self::Fair(Fair {
+                    window_days: window_days
+                        .parse::<NonZeroU32>()
+                        .map_err(|_| invalid())?
+                        .get(),
+                })),
+                _ => Err(invalid()),
+            },
+        }
+    }
+}
+
+impl TryFrom<String> for Rotation {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Rotation> for String {
+    fn from(rotation: Rotation) -> Self {
+        rotation.to_string()
+    }
+}
+
+impl fmt::Display for Rotation {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RoundRobin(_) => write!(formatter, "round-robin"),
+            Self::PerWeek(_) => write!(formatter, "per-week"),
+            Self::PerDay(_) => write!(formatter, "per-day"),
+            Self::Fixed(_) => write!(formatter, "fixed"),
+            Self::SeededRandom(SeededRandom { seed }) => write!(formatter, "random:{}", seed),
+            Self::Fair(Fair { window_days }) => write!(formatter, "fair:{}", window_days),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_and_displays_rotations() {
+        for rotation in &[
+            "round-robin",
+            "per-week",
+            "per-day",
+            "fixed",
+            "random:42",
+            "fair:14",
+        ] {
+            assert_eq!(
+                Ok(String::from(*rotation)),
+                rotation
+                    .parse::<Rotation>()
+                    .map(|rotation| rotation.to_string())
+            );
+        }
+
+        assert_eq!(
+            Ok(String::from("fair:28")),
+            "fair"
+                .parse::<Rotation>()
+                .map(|rotation| rotation.to_string())
+        );
+
+        assert!("random".parse::<Rotation>().is_err());
+        assert!("fair:0".parse::<Rotation>().is_err());
+        assert!("alphabetical".parse::<Rotation>().is_err());
+    }
+
+    #[test]
+    fn it_assigns_everyone_once_per_round_in_a_random_order() {
+        let strategy = SeededRandom { seed: 42 };
+
+        let indices: Vec<usize> = (0..40)
+            .map(|position| strategy.get_index(position, 4))
+            .collect();
+
+        for round in indices.chunks(4) {
+            let mut sorted_round = round.to_vec();
+            sorted_round.sort_unstable();
+            assert_eq!(vec![0, 1, 2, 3], sorted_round);
+        }
+
+        // Nobody is assigned twice in a row between rounds
+        assert!(indices.windows(2).all(|pair| pair[0] != pair[1]));
+
+        // The order is not simply round-robin
+        assert_ne!(
+            (0..40).map(|position| position % 4).collect::<Vec<_>>(),
+            indices
+        );
+    }
+
+    /// Asserts that nobody is assigned twice in a row over 20 rounds, for a range of seeds
+    fn assert_no_repeats(assignee_count: usize) {
+        for seed in 0..20 {
+            let strategy = SeededRandom { seed };
+
+            let indices: Vec<usize> = (0..assignee_count * 20)
+                .map(|position| strategy.get_index(position, assignee_count))
+                .collect();
+
+            assert!(
+                indices.windows(2).all(|pair| pair[0] != pair[1]),
+                "{:?} repeats with seed {}",
+                indices,
+                seed
+            );
+        }
+    }
+
+    #[test]
+    fn it_never_assigns_the_same_person_twice_in_a_row_between_two_assignees() {
+        assert_no_repeats(2);
+    }
+
+    #[test]
+    fn it_never_assigns_the_same_person_twice_in_a_row_between_three_assignees() {
+        assert_no_repeats(3);
+    }
+
+    #[test]
+    fn it_repeats_the_same_random_order_for_the_same_seed() {
+        let first = SeededRandom { seed: 7 };
+        let second = SeededRandom { seed: 7 };
+
+        assert!(
+            (0..20).all(|position| first.get_index(position, 5) == second.get_index(position, 5))
+        );
+    }
+}
@@ -0,0 +1,321 @@
+//! Shorthand for weekly schedules, such as `weekdays at 08:00`
+
+use super::model::WeeklyTimes;
+use super::month_day::{parse_weekday, WEEKDAYS};
+use std::ops::Range;
+use time::{Time, Weekday};
+
+/// Word or separator within a shorthand schedule, along with its position
+struct Token<'a> {
+    /// Text of the token, in lowercase
+    text: String,
+    /// Text of the token as written
+    written: &'a str,
+    /// Byte range of the token within the schedule
+    span: Range<usize>,
+}
+
+/// Splits a schedule into words and the separators `,` and `;`
+fn tokenize<'a>(value: &'a str) -> Vec<Token<'a>> {
+    let mut tokens = Vec::new();
+    let mut word_start = None;
+
+    let end_word = |tokens: &mut Vec<Token<'a>>, word_start: &mut Option<usize>, end: usize| {
+        if let Some(start) = word_start.take() {
+            tokens.push(Token {
+                text: value[start..end].to_lowercase(),
+                written: &value[start..end],
+                span: start..end,
+            });
+        }
+    };
+
+    for (index, character) in value.char_indices() {
+        match character {
+            ',' | ';' => {
+                end_word(&mut tokens, &mut word_start, index);
+                end_word(&mut tokens, &mut Some(index), index + 1);
+            }
+            character if character.is_whitespace() => {
+                end_word(&mut tokens, &mut word_start, index);
+            }
+            _ => {
+                word_start.get_or_insert(index);
+            }
+        }
+    }
+
+    end_word(&mut tokens, &mut word_start, value.len());
+    tokens
+}
+
+/// Parses a day or range of days, such as `mon`, `mon-fri`, `weekdays` or `daily`
+fn parse_days(text: &str) -> Option<Vec<Weekday>> {
+    match text {
+        "daily" | "everyday" => return Some(WEEKDAYS.to_vec()),
+        "weekdays" => return Some(WEEKDAYS[..5].to_vec()),
+        "weekends" | "weekend" => return Some(WEEKDAYS[5..].to_vec()),
+        _ => {}
+    }
+
+    match text.split_once('-') {
+        Some((first, last)) => {
+            let first = parse_weekday(first)?.number_days_from_monday();
+            let last = parse_weekday(last)?.number_days_from_monday();
+
+            // Ranges such as `fri-mon` wrap around the end of the week
+            let length = (7 + last - first) % 7 + 1;
+            Some(
+                WEEKDAYS
+                    .iter()
+                    .cycle()
+                    .skip(usize::from(first))
+                    .take(usize::from(length))
+                    .copied()
+                    .collect(),
+            )
+        }
+        None => parse_weekday(text).map(|weekday| vec![weekday]),
+    }
+}
+
+/// Parses a time of day, such as `08:00`, `21:00:30`, `8am`, `8:30pm`, `noon` or `midnight`
+fn parse_time(text: &str) -> Option<Time> {
+    match text {
+        "noon" => return Time::from_hms(12, 0, 0).ok(),
+        "midnight" => return Some(Time::MIDNIGHT),
+        _ => {}
+    }
+
+    let (text, hour_offset) = match (text.strip_suffix("am"), text.strip_suffix("pm")) {
+        (Some(text), _) => (text, Some(0)),
+        (_, Some(text)) => (text, Some(12)),
+        _ => (text, None),
+    };
+
+    let mut parts = text.split(':');
+    let hour: u8 = parts.next()?.parse().ok()?;
+    let minute: u8 = parts.next().map_or(Some(0), |minute| {
+        Some(minute)
+            .filter(|minute| minute.len() == 2)?
+            .parse()
+            .ok()
+    })?;
+    let second: u8 = parts.next().map_or(Some(0), |second| {
+        Some(second)
+            .filter(|second| second.len() == 2)?
+            .parse()
+            .ok()
+    })?;
+
+    if parts.next().is_some() {
+        return None;
+    }
+
+    let hour = match hour_offset {
+        // 12am is midnight and 12pm is noon
+        Some(offset) if (1..=12).contains(&hour) => hour % 12 + offset,
+        // Times without am or pm need minutes, so a lone number isn't mistaken for a time
+        None if text.contains(':') => hour,
+        _ => return None,
+    };
+
+    Time::from_hms(hour, minute, second).ok()
+}
+
+/// Formats an error message which points at the given token within the schedule
+fn error_at(value: &str, span: Range<usize>, message: &str) -> String {
+    let indent = value[..span.start].chars().count();
+    let width = value[span].chars().count().max(1);
+
+    format!(
+        "{}\n    {}\n    {}{}",
+        message,
+        value,
+        " ".repeat(indent),
+        "^".repeat(width)
+    )
+}
+
+/// Parses a shorthand schedule into weekly times
+///
+/// Schedules are written as days followed by times, such as `weekdays at 08:00`,
+/// `mon,wed 10:30 and 22:30` or `daily 9pm`, with multiple schedules separated by `;` such as
+/// `mon 10:30 and 22:30; wed 12:30`. Days may be weekday names, ranges such as `mon-fri`,
+/// `weekdays`, `weekends` or `daily`. Times may be written as `08:00`, `8am` or `8:30pm`
+///
+/// # Errors
+///
+/// When the schedule is invalid, with a message pointing at the invalid word
+pub fn parse_weekly_times(value: &str) -> Result<WeeklyTimes, String> {
+    let tokens = tokenize(value);
+    let mut tokens = tokens.iter().peekable();
+    let mut weekly_times = WeeklyTimes::new();
+
+    let end_of_schedule = value.len()..value.len();
+    let expected = |token: Option<&&Token<'_>>, message: &str| match token {
+        Some(token) => error_at(
+            value,
+            token.span.clone(),
+            &format!("{}, but found `{}`", message, token.written),
+        ),
+        None => error_at(value, end_of_schedule.clone(), message),
+    };
+
+    loop {
+        // Days, separated by commas
+        let mut days = Vec::new();
+        loop {
+            let token = tokens.next();
+            let token_days = token
+                .and_then(|token| match token.text.as_str() {
+                    "every" => tokens
+                        .next_if(|token| token.text == "day")
+                        .map(|_| WEEKDAYS.to_vec()),
+                    text => parse_days(text),
+                })
+                .ok_or_else(|| {
+                    expected(
+                        token.as_ref(),
+                        "Expected days such as `mon`, `mon-fri` or `daily`",
+                    )
+                })?;
+
+            days.extend(token_days);
+
+            let is_followed_by_day = tokens.peek().map_or(false, |token| token.text == ",")
+                && tokens
+                    .clone()
+                    .nth(1)
+                    .map_or(false, |token| parse_days(&token.text).is_some());
+
+            if !is_followed_by_day {
+                break;
+            }
+
+            tokens.next();
+        }
+
+        tokens.next_if(|token| token.text == "at");
+
+        // Times, separated by commas or `and`
+        let mut times = Vec::new();
+        loop {
+            let token = tokens.next();
+            times.push(
+                token
+                    .and_then(|token| parse_time(&token.text))
+                    .ok_or_else(|| expected(token.as_ref(), "Expected a time such as `08:00`"))?,
+            );
+
+            if tokens
+                .next_if(|token| token.text == "and" || token.text == ",")
+                .is_none()
+            {
+                break;
+            }
+        }
+
+        for day in days {
+            weekly_times
+                .entry(day)
+                .or_default()
+                .extend(times.iter().copied());
+        }
+
+        match tokens.next() {
+            None => break,
+            Some(token) if token.text == ";" => continue,
+            token => {
+                return Err(expected(
+                    token.as_ref(),
+                    "Expected `and`, `;` or the end of the schedule",
+                ))
+            }
+        }
+    }
+
+    for times in weekly_times.values_mut() {
+        times.sort();
+        times.dedup();
+    }
+
+    Ok(weekly_times)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::time;
+
+    #[test]
+    fn it_parses_days_and_times() {
+        assert_eq!(
+            Ok(vec![
+                (Weekday::Monday, vec![time!(10:30), time!(22:30)]),
+                (Weekday::Wednesday, vec![time!(10:30), time!(22:30)]),
+            ]
+            .into_iter()
+            .collect()),
+            parse_weekly_times("mon,wed 10:30 and 22:30")
+        );
+    }
+
+    #[test]
+    fn it_parses_groups_of_days() {
+        let weekdays = parse_weekly_times("weekdays at 08:00");
+        assert_eq!(Ok(5), weekdays.map(|weekly_times| weekly_times.len()));
+
+        let daily = parse_weekly_times("daily 21:00");
+        assert_eq!(Ok(7), daily.map(|weekly_times| weekly_times.len()));
+
+        assert_eq!(
+            Ok(vec![
+                (Weekday::Friday, vec![time!(18:00)]),
+                (Weekday::Saturday, vec![time!(18:00)]),
+                (Weekday::Sunday, vec![time!(18:00)]),
+            ]
+            .into_iter()
+            .collect()),
+            parse_weekly_times("Fri-Sun at 6pm")
+        );
+    }
+
+    #[test]
+    fn it_parses_multiple_schedules() {
+        assert_eq!(
+            Ok(vec![
+                (Weekday::Monday, vec![time!(10:30), time!(22:30)]),
+                (Weekday::Wednesday, vec![time!(00:00), time!(12:30)]),
+            ]
+            .into_iter()
+            .collect()),
+            parse_weekly_times("mon 10:30, 22:30; wed 12:30 and midnight")
+        );
+    }
+
+    #[test]
+    fn it_points_at_the_invalid_word() {
+        assert_eq!(
+            Err(String::from(
+                "Expected a time such as `08:00`, but found `10:3`\n    mon,wed 10:3\n            ^^^^"
+            )),
+            parse_weekly_times("mon,wed 10:3")
+        );
+        assert_eq!(
+            Err(String::from(
+                "Expected days such as `mon`, `mon-fri` or `daily`, but found `mo`\n    mo 10:30\n    ^^"
+            )),
+            parse_weekly_times("mo 10:30")
+        );
+        assert_eq!(
+            Err(String::from(
+                "Expected a time such as `08:00`\n    daily at\n            ^"
+            )),
+            parse_weekly_times("daily at")
+        );
+        assert!(parse_weekly_times("daily 10:30 or 11:30").is_err());
+        assert!(parse_weekly_times("daily 13pm").is_err());
+        assert!(parse_weekly_times("daily 10").is_err());
+    }
+}
@@ -0,0 +1,188 @@
+//! IANA time zones for interpreting scheduled wall-clock times
+
+use super::Error;
+use serde::{Serialize, Serializer};
+use time::{OffsetDateTime, PrimitiveDateTime, UtcOffset};
+use tz::TimeZone;
+
+/// Number of seconds in a day, used to find the offsets surrounding a transition
+const SECONDS_PER_DAY: i64 = 60 * 60 * 24;
+
+/// Name of the default time zone
+const UTC: &str = "UTC";
+
+/// IANA time zone, such as `Europe/Berlin`, loaded from the system time zone database
+#[derive(Clone, Debug)]
+pub struct Timezone {
+    /// IANA name of the time zone
+    name: String,
+    /// Transition rules for the time zone
+    zone: TimeZone,
+}
+
+impl Timezone {
+    /// Loads the time zone with the given IANA name
+    ///
+    /// # Errors
+    ///
+    /// When the time zone does not exist in the system time zone database
+    pub fn new(name: &str) -> Result<Self, Error> {
+        let zone = if name == UTC {
+            TimeZone::utc()
+        } else {
+            TimeZone::from_posix_tz(name).map_err(|_| Error::InvalidTimezone(name.to_string()))?
+        };
+
+        Ok(Self {
+            name: name.to_string(),
+            zone,
+        })
+    }
+
+    /// Provides the IANA name of the time zone
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Converts a datetime to the wall-clock time of this time zone
+    #[must_use]
+    pub fn to_local(&self, datetime: OffsetDateTime) -> OffsetDateTime {
+        datetime.to_offset(self.get_offset(datetime.unix_timestamp()))
+    }
+
+    /// Converts a wall-clock time in this time zone to an absolute datetime
+    ///
+    /// Wall-clock times which occur twice when clocks fall back resolve to their first instance.
+    /// Wall-clock times which are skipped when clocks spring forward are shifted forward by the
+    /// length of the gap, so 02:30 becomes 03:30 when clocks skip from 02:00 to 03:00
+    #[must_use]
+    pub fn from_local(&self, local_datetime: PrimitiveDateTime) -> OffsetDateTime {
+        let wall_clock = local_datetime.assume_utc();
+
+        // The offsets a day either side cover any transition at this wall-clock time
+        let offset_before = self.get_offset(wall_clock.unix_timestamp() - SECONDS_PER_DAY);
+        let offset_after = self.get_offset(wall_clock.unix_timestamp() + SECONDS_PER_DAY);
+
+        [offset_before, offset_after]
+            .iter()
+            .map(|offset| local_datetime.assume_offset(*offset))
+            .filter(|datetime| self.get_offset(datetime.unix_timestamp()) == datetime.offset())
+            .min()
+            .unwrap_or_else(|| local_datetime.assume_offset(offset_before))
+    }
+
+    /// Determines the offset from UTC in effect at the given unix timestamp
+    fn get_offset(&self, unix_timestamp: i64) -> UtcOffset {
+        self.zone
+            .find_local_time_type(unix_timestamp)
+            .ok()
+            .and_then(|local_time_type| {
+                UtcOffset::from_whole_seconds(local_time_type.ut_offset()).ok()
+            })
+            .unwrap_or(UtcOffset::UTC)
+    }
+}
+
+impl Default for Timezone {
+    fn default() -> Self {
+        Self {
+            name: UTC.to_string(),
+            zone: TimeZone::utc(),
+        }
+    }
+}
+
+impl PartialEq for Timezone {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Eq for Timezone {}
+
+impl Serialize for Timezone {
+    /// Serializes the time zone as its IANA name
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::{datetime, offset};
+
+    #[test]
+    fn it_defaults_to_utc() {
+        assert_eq!(Ok(Timezone::default()), Timezone::new("UTC"));
+        assert_eq!("UTC", Timezone::default().name());
+    }
+
+    #[test]
+    fn it_fails_to_load_unknown_timezones() {
+        assert_eq!(
+            Err(Error::InvalidTimezone(String::from("Black/Lodge"))),
+            Timezone::new("Black/Lodge")
+        );
+    }
+
+    #[test]
+    fn it_converts_to_local_time() -> Result<(), Error> {
+        let berlin = Timezone::new("Europe/Berlin")?;
+
+        // Winter time is UTC+1 and summer time is UTC+2
+        assert_eq!(
+            offset!(+1),
+            berlin.to_local(datetime!(2020-01-06 12:00 UTC)).offset()
+        );
+        assert_eq!(
+            offset!(+2),
+            berlin.to_local(datetime!(2020-07-06 12:00 UTC)).offset()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_converts_from_local_time() -> Result<(), Error> {
+        let berlin = Timezone::new("Europe/Berlin")?;
+
+        assert_eq!(
+            datetime!(2020-01-06 07:00 UTC),
+            berlin.from_local(datetime!(2020-01-06 08:00))
+        );
+        assert_eq!(
+            datetime!(2020-07-06 06:00 UTC),
+            berlin.from_local(datetime!(2020-07-06 08:00))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_shifts_skipped_local_times_forward() -> Result<(), Error> {
+        let berlin = Timezone::new("Europe/Berlin")?;
+
+        // Clocks spring forward from 02:00 to 03:00, so 02:30 becomes 03:30 summer time
+        assert_eq!(
+            datetime!(2020-03-29 03:30 +2),
+            berlin.from_local(datetime!(2020-03-29 02:30))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_uses_the_first_instance_of_repeated_local_times() -> Result<(), Error> {
+        let berlin = Timezone::new("Europe/Berlin")?;
+
+        // Clocks fall back from 03:00 to 02:00, so 02:30 summer time happens first
+        assert_eq!(
+            datetime!(2020-10-25 02:30 +2),
+            berlin.from_local(datetime!(2020-10-25 02:30))
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+//! Sources of the current time for a real-time scheduler
+
+use std::convert::TryFrom;
+use time::OffsetDateTime;
+use tokio::time::Instant;
+
+/// Source of the current time, which determines when scheduled events occur
+pub trait Clock {
+    /// Returns the current time
+    fn now(&self) -> OffsetDateTime;
+
+    /// Returns the instant at which the given time occurs, for waiting until it.
+    /// Times which already passed occur now
+    fn instant_at(&self, time: OffsetDateTime) -> Instant;
+}
+
+/// Clock following the system time
+#[derive(Clone, Copy, Debug, Default)]
+pub struct System;
+
+impl Clock for System {
+    fn now(&self) -> OffsetDateTime {
+        OffsetDateTime::now_utc()
+    }
+
+    fn instant_at(&self, time: OffsetDateTime) -> Instant {
+        let duration = time - self.now();
+        Instant::now() + std::time::Duration::try_from(duration).unwrap_or_default()
+    }
+}
+
+/// Clock starting from a given time and advancing along with tokio's clock
+///
+/// When tokio's clock is paused, such as with `#[tokio::test(start_paused = true)]`, time only
+/// advances as far as the next scheduled event, so days of scheduling take no time at all
+#[derive(Clone, Copy, Debug)]
+pub struct Virtual {
+    /// Time when the clock was created
+    start_time: OffsetDateTime,
+    /// Instant when the clock was created
+    start_instant: Instant,
+}
+
+impl Virtual {
+    /// Creates a clock starting from the given time
+    #[must_use]
+    pub fn new(start_time: OffsetDateTime) -> Self {
+        Self {
+            start_time,
+            start_instant: Instant::now(),
+        }
+    }
+}
+
+impl Clock for Virtual {
+    fn now(&self) -> OffsetDateTime {
+        self.start_time + self.start_instant.elapsed()
+    }
+
+    fn instant_at(&self, time: OffsetDateTime) -> Instant {
+        let duration = time - self.start_time;
+        self.start_instant + std::time::Duration::try_from(duration).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    #[tokio::test(start_paused = true)]
+    async fn it_advances_virtual_time_with_tokio() {
+        let clock = Virtual::new(datetime!(2020-01-06 07:00 UTC));
+        let instant = clock.instant_at(datetime!(2020-01-07 07:00 UTC));
+
+        tokio::time::sleep_until(instant).await;
+
+        assert_eq!(datetime!(2020-01-07 07:00 UTC), clock.now());
+        assert_eq!(
+            clock.instant_at(datetime!(2020-01-01 07:00 UTC)),
+            clock.start_instant
+        );
+    }
+}
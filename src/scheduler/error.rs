@@ -11,9 +11,24 @@ pub enum Error {
     /// A scheduled entity is unavailable for notification
     #[error("The scheduled reminder {0} could not be obtained from the queue")]
     Unavailable(i32),
-    /// Assignees are unavailable for the scheduled reminder
-    #[error("Assignees could not be obtained for the scheduled reminder")]
+    /// The availability of assignees could not be obtained for the scheduled reminder
+    #[error("The availability of assignees could not be obtained for the scheduled reminder")]
     Assignees(#[from] crate::user::Error),
+    /// The status of a finished reminder could not be saved
+    #[error("The status of the finished reminder could not be saved")]
+    Status(#[from] crate::reminder::Error),
+    /// The assignment overrides for the scheduled reminder could not be obtained
+    #[error("Assignment overrides could not be obtained for the scheduled reminder")]
+    Overrides(#[source] crate::reminder::Error),
+    /// The assignment history of the scheduled reminder could not be obtained or recorded
+    #[error("Assignment history could not be obtained or recorded for the scheduled reminder")]
+    History(#[source] crate::reminder::Error),
+    /// The last occurrence of a reminder could not be obtained or recorded
+    #[error("The last occurrence could not be obtained or recorded for the scheduled reminder")]
+    LastOccurrence(#[source] crate::reminder::Error),
+    /// The stored reminders could not be reloaded while the scheduler is running
+    #[error("Reminders could not be reloaded")]
+    Reload(#[source] crate::reminder::Error),
     /// An integration failed to notify of a scheduled reminder
     #[error("Integration failed to notify of a scheduled reminder")]
     Integration(#[from] Box<dyn std::error::Error>),
@@ -1,6 +1,8 @@
 //! Models and functionality for real-time scheduling
 
+pub mod clock;
 mod error;
 pub mod model;
 
+pub use clock::Clock;
 pub use error::Error;
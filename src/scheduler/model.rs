@@ -1,23 +1,78 @@
 //! Data models for a real-time reminder scheduler
 
+use super::clock::{self, Clock};
 use super::Error;
-use crate::{Integrations, Providers, Reminder};
-use std::collections::BTreeMap;
-use time::OffsetDateTime;
+use crate::integration::{Delivery, Outcome, Outcomes};
+use crate::reminder::model::{PastAssignment, Status};
+use crate::schedule::model::{History, Overrides};
+use crate::schedule::{Availability, CatchUp, Notice};
+use crate::{Integrations, Providers, Reminder, User};
+use std::collections::{BTreeMap, VecDeque};
+use std::future::Future;
+use time::{Duration, OffsetDateTime};
 use tokio::time::Instant;
 use tokio_stream::StreamExt;
 use tokio_util::time::{delay_queue, DelayQueue};
 
+/// An event scheduled for a reminder
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Event {
+    /// An occurrence of the reminder with the given uid
+    Occurrence(i32),
+    /// An announcement of the next change of assignees for the reminder with the given uid
+    Handoff(i32),
+}
+
 /// A reminder with an associated scheduler key
 struct ScheduledReminder {
     /// Scheduled reminder
     reminder: Reminder,
     /// Scheduler key for this reminder
     key: delay_queue::Key,
+    /// Time of the scheduled occurrence
+    time: OffsetDateTime,
+    /// Next change of assignees to announce, if one is scheduled
+    handoff: Option<ScheduledHandoff>,
+}
+
+/// An announcement of a reminder's next change of assignees with an associated scheduler key
+struct ScheduledHandoff {
+    /// Scheduler key for this announcement
+    key: delay_queue::Key,
+    /// Time from which the upcoming assignees were determined
+    from: OffsetDateTime,
+    /// First occurrence assigned to the incoming assignees
+    time: OffsetDateTime,
+}
+
+/// An occurrence which was missed while the scheduler wasn't running
+struct MissedOccurrence {
+    /// Reminder which missed the occurrence
+    reminder: Reminder,
+    /// Time of the missed occurrence
+    time: OffsetDateTime,
 }
 
-/// A real-time scheduler for scheduled reminers
-pub struct Scheduler<'a> {
+/// An event which is due to be processed
+enum Due {
+    /// A scheduled event from the queue
+    Scheduled(Event),
+    /// An occurrence which was missed while the scheduler wasn't running
+    Missed(Box<MissedOccurrence>),
+}
+
+/// A reminder's event which was processed by the scheduler
+#[derive(Debug)]
+pub struct Processed {
+    /// Uid of the reminder
+    pub uid: i32,
+    /// Outcome of notifying each integration of the event, by integration name
+    pub outcomes: Outcomes,
+}
+
+/// A real-time scheduler for scheduled reminers, following the system time unless given another
+/// clock
+pub struct Scheduler<'a, C: Clock = clock::System> {
     /// A mapping of reminder uid to the scheduled reminder
     reminders: BTreeMap<i32, ScheduledReminder>,
     /// Providers for scheduling data
@@ -25,77 +80,243 @@ pub struct Scheduler<'a> {
     /// Integrations for the scheduler
     integrations: Integrations,
     /// Queue of durations until the next scheduled event
-    queue: DelayQueue<i32>,
+    queue: DelayQueue<Event>,
+    /// Interval between checks for changes to the stored reminders, if they're reloaded
+    reload_interval: Option<std::time::Duration>,
+    /// Instant of the next check for changes to the stored reminders
+    next_reload: Option<Instant>,
+    /// Number of changes to the stored reminders when they were last loaded
+    change_count: Option<i32>,
+    /// Reminders to check for occurrences missed before the given time, which is when the
+    /// scheduler started
+    unchecked: Option<(OffsetDateTime, Vec<Reminder>)>,
+    /// Missed occurrences which are yet to be notified late, in order
+    missed: VecDeque<MissedOccurrence>,
+    /// Reminders which ended before they could be scheduled, with the status to record for them
+    ended: Vec<(i32, Status)>,
+    /// Source of the current time
+    clock: C,
 }
 
 impl<'a> Scheduler<'a> {
     /// Creates a new real-time scheduler for the given reminders
+    ///
+    /// Reminders with a handoff notice also have their next change of assignees announced to
+    /// the integrations ahead of time, and reminders with a catch-up policy have the
+    /// occurrences they missed since their last notification notified late
     #[must_use]
     pub fn new(
         reminders: Vec<Reminder>,
         providers: Providers<'a>,
         integrations: Integrations,
     ) -> Self {
-        let mut queue = DelayQueue::with_capacity(reminders.len());
+        Self::with_clock(reminders, providers, integrations, clock::System)
+    }
+}
 
-        let scheduled_reminders = reminders
-            .into_iter()
-            .filter_map(|reminder| {
-                get_next_instant(&reminder).map(|instant| {
-                    let key = queue.insert_at(reminder.uid, instant);
-                    (reminder.uid, ScheduledReminder { reminder, key })
-                })
+impl<'a, C: Clock> Scheduler<'a, C> {
+    /// Creates a new scheduler for the given reminders, as with `new`, which follows the time
+    /// of the given clock
+    #[must_use]
+    pub fn with_clock(
+        reminders: Vec<Reminder>,
+        providers: Providers<'a>,
+        integrations: Integrations,
+        clock: C,
+    ) -> Self {
+        let started = clock.now();
+        let catching_up = reminders
+            .iter()
+            .filter(|reminder| {
+                reminder.status == Status::Active && reminder.schedule.catch_up != CatchUp::Skip
             })
+            .cloned()
             .collect();
 
-        Self {
-            queue,
+        let mut scheduler = Self {
+            queue: DelayQueue::with_capacity(reminders.len()),
             providers,
             integrations,
-            reminders: scheduled_reminders,
+            reminders: BTreeMap::new(),
+            reload_interval: None,
+            next_reload: None,
+            change_count: None,
+            unchecked: Some((started, catching_up)),
+            missed: VecDeque::new(),
+            ended: Vec::new(),
+            clock,
+        };
+
+        for reminder in reminders {
+            scheduler.schedule(reminder);
         }
+
+        scheduler
+    }
+
+    /// Checks for added, updated and removed reminders at the given interval, rescheduling them
+    /// while the scheduler is running
+    ///
+    /// The scheduler keeps running while waiting for changes, even when no reminders are left
+    #[must_use]
+    pub fn with_reload_interval(mut self, reload_interval: std::time::Duration) -> Self {
+        self.reload_interval = Some(reload_interval);
+        self
     }
 
-    /// Processes the next scheduled reminder.
+    /// Processes the next scheduled reminder or handoff, after notifying any occurrences which
+    /// were missed while the scheduler wasn't running.
     /// Applications will likely want to call `run` instead
     ///
-    /// Returns the uid of the scheduled reminder that was processed
+    /// Returns the uid of the scheduled reminder that was processed, along with the outcome of
+    /// notifying each integration
     ///
     /// # Errors
     ///
-    /// When a reminder is scheduled but the scheduler queue is empty
-    pub async fn next(&mut self) -> Result<Option<i32>, Error> {
-        if let Some(scheduled_entity) = self.queue.next().await {
-            let uid = *scheduled_entity?.get_ref();
-            let entity = self
-                .reminders
-                .get_mut(&uid)
-                .ok_or(Error::Unavailable(uid))?;
+    /// When a reminder is scheduled but the scheduler queue is empty,
+    /// when a finished reminder can't be marked as finished,
+    /// or when the last occurrence of a reminder can't be obtained or recorded
+    pub async fn next(&mut self) -> Result<Option<Processed>, Error> {
+        match self.next_due().await? {
+            Some(due) => self.process(due).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Waits for the next event which is due, without processing it
+    ///
+    /// Nothing is processed while waiting, so this can be cancelled at any time
+    async fn next_due(&mut self) -> Result<Option<Due>, Error> {
+        while let Some((uid, status)) = self.ended.pop() {
+            self.providers.reminder.set_status(uid, status)?;
+        }
+
+        if let Some((started, reminders)) = self.unchecked.take() {
+            self.find_missed_occurrences(started, reminders)?;
+        }
+
+        if let Some(missed) = self.missed.pop_front() {
+            return Ok(Some(Due::Missed(Box::new(missed))));
+        }
+
+        loop {
+            let reload_interval = match self.reload_interval {
+                Some(reload_interval) => reload_interval,
+                None => {
+                    return match self.queue.next().await {
+                        Some(scheduled_entity) => {
+                            Ok(Some(Due::Scheduled(*scheduled_entity?.get_ref())))
+                        }
+                        None => Ok(None),
+                    }
+                }
+            };
+
+            let next_reload = *self
+                .next_reload
+                .get_or_insert_with(|| Instant::now() + reload_interval);
 
-            // Notify integrations
-            if !self.integrations.is_empty() {
-                let timestamp = OffsetDateTime::now_utc();
-                let assignees = vec![self
-                    .providers
-                    .user
-                    .get_by_uid(entity.reminder.schedule.get_assignee(timestamp))?];
-
-                for integration in self.integrations.values_mut() {
-                    integration
-                        .notify(&self.providers, &entity.reminder, &assignees, &timestamp)
-                        .unwrap_or_else(|error| eprintln!("Integration failed: {:?}", error));
+            tokio::select! {
+                Some(scheduled_entity) = self.queue.next() => {
+                    return Ok(Some(Due::Scheduled(*scheduled_entity?.get_ref())));
+                }
+                _ = tokio::time::sleep_until(next_reload) => {
+                    self.next_reload = None;
+                    self.reload()?;
                 }
             }
+        }
+    }
+
+    /// Reschedules any reminders which were added, updated or removed since they were loaded.
+    /// Applications will likely want to set a reload interval instead
+    ///
+    /// Returns whether any stored reminders changed
+    ///
+    /// # Errors
+    ///
+    /// When the stored reminders can't be retrieved
+    pub fn reload(&mut self) -> Result<bool, Error> {
+        let change_count = self
+            .providers
+            .reminder
+            .get_change_count()
+            .map_err(Error::Reload)?;
+
+        if self.change_count == Some(change_count) {
+            return Ok(false);
+        }
+
+        let reminders: BTreeMap<_, _> = self
+            .providers
+            .reminder
+            .get_all()
+            .map_err(Error::Reload)?
+            .into_iter()
+            .filter(|reminder| reminder.status == Status::Active)
+            .map(|reminder| (reminder.uid, reminder))
+            .collect();
+
+        // Reminders which were removed or are no longer active are taken off the queue
+        let removed: Vec<_> = self
+            .reminders
+            .keys()
+            .filter(|uid| !reminders.contains_key(uid))
+            .copied()
+            .collect();
+
+        for uid in removed {
+            self.unschedule(uid);
+        }
+
+        for (uid, reminder) in reminders {
+            let entity = match self.reminders.get_mut(&uid) {
+                Some(entity) if entity.reminder == reminder => continue,
+                Some(entity) => entity,
+                None => {
+                    self.schedule(reminder);
+                    continue;
+                }
+            };
 
-            // Insert this reminder's next scheduled time into the queue
-            if let Some(instant) = get_next_instant(&entity.reminder) {
-                entity.key = self.queue.insert_at(uid, instant);
+            // Updated reminders keep their place in the queue, moved to their next time
+            entity.reminder = reminder;
+            if let Some((time, instant)) =
+                get_next_instant(&self.clock, &entity.reminder, self.clock.now())
+            {
+                self.queue.reset_at(&entity.key, instant);
+                entity.time = time;
+                self.schedule_handoff(uid, None);
+            } else {
+                let reminder = entity.reminder.clone();
+                self.unschedule(uid);
+                self.end(&reminder);
             }
+        }
 
-            Ok(Some(uid))
-        } else {
-            Ok(None)
+        self.change_count = Some(change_count);
+
+        Ok(true)
+    }
+
+    /// Replaces the integrations, such as when the config is loaded again, and reloads every
+    /// stored reminder whether or not it changed
+    ///
+    /// # Errors
+    ///
+    /// When the stored reminders can't be retrieved
+    pub fn reload_with(&mut self, integrations: Integrations) -> Result<(), Error> {
+        self.integrations = integrations;
+        self.change_count = None;
+        self.reload()?;
+
+        // Handoffs are only announced when there are integrations to announce them to
+        let uids: Vec<_> = self.reminders.keys().copied().collect();
+        for uid in uids {
+            self.schedule_handoff(uid, None);
         }
+
+        Ok(())
     }
 
     /// Runs the scheduler for as long as there are scheduled reminders
@@ -104,26 +325,465 @@ impl<'a> Scheduler<'a> {
     ///
     /// When a reminder is scheduled but the scheduler queue is empty
     pub async fn run(&mut self) -> Result<(), Error> {
-        while self.next().await?.is_some() {}
+        self.run_until(std::future::pending::<()>()).await?;
+        Ok(())
+    }
+
+    /// Runs the scheduler until the given future completes, or until there are no scheduled
+    /// reminders left
+    ///
+    /// Notifications which are being sent when the future completes are finished first, since
+    /// the scheduler only stops while waiting for the next event. Integrations which fail or
+    /// run out of time are reported on stderr
+    ///
+    /// Returns the output of the future if it stopped the scheduler
+    ///
+    /// # Errors
+    ///
+    /// When a reminder is scheduled but the scheduler queue is empty
+    pub async fn run_until<F: Future>(&mut self, shutdown: F) -> Result<Option<F::Output>, Error> {
+        tokio::pin!(shutdown);
+
+        loop {
+            let due = tokio::select! {
+                biased;
+                output = &mut shutdown => return Ok(Some(output)),
+                due = self.next_due() => due?,
+            };
+
+            match due {
+                Some(due) => report(&self.process(due).await?),
+                None => return Ok(None),
+            }
+        }
+    }
+
+    /// Queues the occurrences of reminders which were missed before the scheduler started,
+    /// according to their catch-up policy
+    ///
+    /// Reminders which were never notified have nothing to catch up on
+    fn find_missed_occurrences(
+        &mut self,
+        started: OffsetDateTime,
+        reminders: Vec<Reminder>,
+    ) -> Result<(), Error> {
+        let mut missed = Vec::new();
+
+        for reminder in reminders {
+            let last_occurrence = self
+                .providers
+                .reminder
+                .get_last_occurrence(reminder.uid)
+                .map_err(Error::LastOccurrence)?;
+
+            if let Some(last_occurrence) = last_occurrence {
+                for time in reminder
+                    .schedule
+                    .get_missed_occurrences(last_occurrence, started)
+                {
+                    missed.push(MissedOccurrence {
+                        reminder: reminder.clone(),
+                        time,
+                    });
+                }
+            }
+        }
+
+        missed.sort_by_key(|occurrence| (occurrence.time, occurrence.reminder.uid));
+        self.missed.extend(missed);
+
         Ok(())
     }
+
+    /// Schedules the next occurrence of an active reminder, and the announcement of its next
+    /// handoff, if it has an upcoming occurrence
+    fn schedule(&mut self, reminder: Reminder) {
+        if reminder.status != Status::Active {
+            return;
+        }
+
+        if let Some((time, instant)) = get_next_instant(&self.clock, &reminder, self.clock.now()) {
+            let uid = reminder.uid;
+            let key = self.queue.insert_at(Event::Occurrence(uid), instant);
+
+            self.reminders.insert(
+                uid,
+                ScheduledReminder {
+                    reminder,
+                    key,
+                    time,
+                    handoff: None,
+                },
+            );
+            self.schedule_handoff(uid, None);
+        } else {
+            // Reminders which ended while the scheduler wasn't running are recorded as ended
+            self.end(&reminder);
+        }
+    }
+
+    /// Records a reminder which can't be scheduled as ended, if its schedule has ended
+    fn end(&mut self, reminder: &Reminder) {
+        let now = self.clock.now();
+        if reminder.schedule.is_exhausted(now) {
+            self.ended
+                .push((reminder.uid, Status::at(&reminder.schedule, now)));
+        }
+    }
+
+    /// Takes a reminder and its handoff off the queue
+    fn unschedule(&mut self, uid: i32) {
+        if let Some(entity) = self.reminders.remove(&uid) {
+            self.queue.remove(&entity.key);
+
+            if let Some(handoff) = entity.handoff {
+                self.queue.remove(&handoff.key);
+            }
+        }
+    }
+
+    /// Processes an event which is due
+    async fn process(&mut self, due: Due) -> Result<Processed, Error> {
+        match due {
+            Due::Scheduled(Event::Occurrence(uid)) => self.process_occurrence(uid).await,
+            Due::Scheduled(Event::Handoff(uid)) => self.process_handoff(uid).await,
+            Due::Missed(missed) => Ok(Processed {
+                uid: missed.reminder.uid,
+                outcomes: notify_occurrence(
+                    &self.providers,
+                    &mut self.integrations,
+                    &missed.reminder,
+                    missed.time,
+                    true,
+                )
+                .await?,
+            }),
+        }
+    }
+
+    /// Notifies integrations of a scheduled occurrence and schedules the reminder's next one
+    async fn process_occurrence(&mut self, uid: i32) -> Result<Processed, Error> {
+        let outcomes = {
+            let entity = self
+                .reminders
+                .get_mut(&uid)
+                .ok_or(Error::Unavailable(uid))?;
+
+            let outcomes = notify_occurrence(
+                &self.providers,
+                &mut self.integrations,
+                &entity.reminder,
+                entity.time,
+                false,
+            )
+            .await?;
+
+            // Insert this reminder's next scheduled time into the queue,
+            // or mark it as finished when there are no more scheduled times
+            let from = self.clock.now().max(entity.time + Duration::NANOSECOND);
+            if let Some((time, instant)) = get_next_instant(&self.clock, &entity.reminder, from) {
+                entity.key = self.queue.insert_at(Event::Occurrence(uid), instant);
+                entity.time = time;
+            } else {
+                let status = if entity.reminder.schedule.recurrence.is_once() {
+                    Status::Finished
+                } else {
+                    Status::Completed
+                };
+
+                if let Some(handoff) = &entity.handoff {
+                    self.queue.remove(&handoff.key);
+                }
+
+                self.reminders.remove(&uid);
+                self.providers.reminder.set_status(uid, status)?;
+
+                return Ok(Processed { uid, outcomes });
+            }
+
+            outcomes
+        };
+
+        // The assignees may change after all, now that this occurrence is in the history
+        if self
+            .reminders
+            .get(&uid)
+            .map_or(false, |entity| entity.handoff.is_none())
+        {
+            self.schedule_handoff(uid, None);
+        }
+
+        Ok(Processed { uid, outcomes })
+    }
+
+    /// Announces a reminder's change of assignees to integrations, if the assignees are still
+    /// changing at that occurrence, and schedules the reminder's next handoff
+    async fn process_handoff(&mut self, uid: i32) -> Result<Processed, Error> {
+        let entity = self
+            .reminders
+            .get_mut(&uid)
+            .ok_or(Error::Unavailable(uid))?;
+
+        let ScheduledHandoff { from, time, .. } = match entity.handoff.take() {
+            Some(handoff) => handoff,
+            None => {
+                return Ok(Processed {
+                    uid,
+                    outcomes: Outcomes::new(),
+                })
+            }
+        };
+
+        // Absences and swaps since the handoff was scheduled may have changed the assignees
+        let (availability, overrides, history) = get_adjustments(&self.providers, uid)?;
+        let handoff = entity
+            .reminder
+            .schedule
+            .get_next_handoff(
+                from,
+                time - Duration::NANOSECOND,
+                &availability,
+                &overrides,
+                history,
+            )
+            .filter(|handoff| handoff.time == time);
+
+        let mut outcomes = Outcomes::new();
+        if let Some(handoff) = handoff {
+            let incoming = get_users(&self.providers, &handoff.incoming);
+            let outgoing = get_users(&self.providers, &handoff.outgoing);
+
+            let deliveries: Vec<(&'static str, Delivery)> = self
+                .integrations
+                .iter_mut()
+                .map(|(name, integration)| {
+                    let delivery = integration.handoff(
+                        &self.providers,
+                        &entity.reminder,
+                        &incoming,
+                        &outgoing,
+                        &handoff.time,
+                    );
+                    (*name, delivery)
+                })
+                .collect();
+
+            outcomes = self.integrations.deliver(deliveries).await;
+        }
+
+        self.schedule_handoff(uid, Some(time));
+
+        Ok(Processed { uid, outcomes })
+    }
+
+    /// Schedules the announcement of a reminder's next change of assignees after the given
+    /// occurrence, if the reminder has a handoff notice and there are integrations to notify
+    ///
+    /// Changes which are sooner than the notice are not announced
+    fn schedule_handoff(&mut self, uid: i32, after: Option<OffsetDateTime>) {
+        let entity = match self.reminders.get_mut(&uid) {
+            Some(entity) => entity,
+            None => return,
+        };
+
+        // A scheduled announcement keeps its place in the queue, moved to the next handoff
+        let scheduled_handoff = entity.handoff.take();
+        let notice = entity
+            .reminder
+            .schedule
+            .handoff_notice
+            .filter(|_| !self.integrations.is_empty())
+            .map(Notice::duration);
+
+        let now = self.clock.now();
+        let handoff = notice.and_then(|notice| {
+            let after = after.map_or(now + notice, |after| after.max(now + notice));
+
+            match get_adjustments(&self.providers, uid) {
+                Ok((availability, overrides, history)) => entity
+                    .reminder
+                    .schedule
+                    .get_next_handoff(now, after, &availability, &overrides, history)
+                    .map(|handoff| (handoff.time, self.clock.instant_at(handoff.time - notice))),
+                Err(error) => {
+                    eprintln!("Handoff could not be scheduled: {:?}", error);
+                    None
+                }
+            }
+        });
+
+        entity.handoff = match (handoff, scheduled_handoff) {
+            (Some((time, instant)), Some(scheduled_handoff)) => {
+                self.queue.reset_at(&scheduled_handoff.key, instant);
+                Some(ScheduledHandoff {
+                    key: scheduled_handoff.key,
+                    from: now,
+                    time,
+                })
+            }
+            (Some((time, instant)), None) => Some(ScheduledHandoff {
+                key: self.queue.insert_at(Event::Handoff(uid), instant),
+                from: now,
+                time,
+            }),
+            (None, Some(scheduled_handoff)) => {
+                self.queue.remove(&scheduled_handoff.key);
+                None
+            }
+            (None, None) => None,
+        };
+    }
+}
+
+/// Notifies integrations of an occurrence of a reminder, labeled as late if it was missed while
+/// the scheduler wasn't running, and records it as the reminder's last occurrence
+///
+/// Returns the outcome of notifying each integration
+///
+/// Who was assigned is recorded for rotations based on history, unless every integration failed
+/// to notify them
+async fn notify_occurrence(
+    providers: &Providers<'_>,
+    integrations: &mut Integrations,
+    reminder: &Reminder,
+    timestamp: OffsetDateTime,
+    late: bool,
+) -> Result<Outcomes, Error> {
+    let mut outcomes = Outcomes::new();
+
+    let (availability, overrides, history) = get_adjustments(providers, reminder.uid)?;
+    let assignment =
+        reminder
+            .schedule
+            .get_assignment(timestamp, &availability, &overrides, &history);
+
+    if !integrations.is_empty() {
+        let assignees = get_users(providers, &assignment.assignees);
+
+        let deliveries: Vec<(&'static str, Delivery)> = integrations
+            .iter_mut()
+            .map(|(name, integration)| {
+                let delivery =
+                    integration.notify(providers, reminder, &assignees, &timestamp, late);
+                (*name, delivery)
+            })
+            .collect();
+
+        outcomes = integrations.deliver(deliveries).await;
+    }
+
+    let is_delivered = outcomes.is_empty()
+        || outcomes
+            .values()
+            .any(|outcome| matches!(outcome, Outcome::Delivered));
+
+    if is_delivered {
+        providers
+            .reminder
+            .add_history(PastAssignment {
+                reminder_uid: reminder.uid,
+                occurrence: timestamp,
+                assignees: assignment.assignees,
+            })
+            .map_err(Error::History)?;
+    }
+
+    // Record the occurrence, for catching up on occurrences missed while not running
+    providers
+        .reminder
+        .set_last_occurrence(reminder.uid, timestamp)
+        .map_err(Error::LastOccurrence)?;
+
+    Ok(outcomes)
+}
+
+/// Reports integrations which failed to be notified of a processed event on stderr
+fn report(processed: &Processed) {
+    for (name, outcome) in &processed.outcomes {
+        match outcome {
+            Outcome::Delivered => {}
+            Outcome::Failed(error) => eprintln!(
+                "Integration {} failed for reminder {}: {}",
+                name, processed.uid, error
+            ),
+            Outcome::TimedOut(timeout) => eprintln!(
+                "Integration {} timed out after {:?} for reminder {}",
+                name, timeout, processed.uid
+            ),
+        }
+    }
+}
+
+/// Retrieves the absences of users and the overrides and history for a reminder, which adjust
+/// who is assigned to its occurrences
+fn get_adjustments(
+    providers: &Providers,
+    uid: i32,
+) -> Result<(Availability, Overrides, History), Error> {
+    let availability = providers.user.get_absences()?.into_iter().collect();
+    let overrides = providers
+        .reminder
+        .get_overrides(uid)
+        .map_err(Error::Overrides)?
+        .into_iter()
+        .map(|assignment_override| {
+            (
+                assignment_override.occurrence,
+                assignment_override.assignees,
+            )
+        })
+        .collect();
+    let history = providers
+        .reminder
+        .get_history(uid)
+        .map_err(Error::History)?
+        .into_iter()
+        .map(|past_assignment| (past_assignment.occurrence, past_assignment.assignees))
+        .collect();
+
+    Ok((availability, overrides, history))
+}
+
+/// Retrieves the users with the given uids
+///
+/// Users who no longer exist are left out, rather than failing the reminder
+fn get_users(providers: &Providers, uids: &[i32]) -> Vec<User> {
+    uids.iter()
+        .filter_map(|uid| match providers.user.get_by_uid(*uid) {
+            Ok(user) => Some(user),
+            Err(error) => {
+                eprintln!("Assignee {} is unavailable: {:?}", uid, error);
+                None
+            }
+        })
+        .collect()
 }
 
-/// Determines the next instant for a reminder to be scheduled, if possible
-fn get_next_instant(reminder: &Reminder) -> Option<Instant> {
-    reminder
-        .schedule
-        .get_next_duration(OffsetDateTime::now_utc())
-        .map(|duration| (time::Instant::now() + duration).into_inner().into())
+/// Determines the next time and instant for a reminder to be scheduled at or after the given
+/// time, if possible
+fn get_next_instant<C: Clock>(
+    clock: &C,
+    reminder: &Reminder,
+    from: OffsetDateTime,
+) -> Option<(OffsetDateTime, Instant)> {
+    reminder.schedule.get_next_duration(from).map(|duration| {
+        let time = from + duration;
+        (time, clock.instant_at(time))
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::integration::{Integrations, MockIntegration};
+    use crate::reminder::model::AssignmentOverride;
+    use crate::schedule::{model::Recurrence, DateRange};
+    use crate::user::model::Absence;
     use crate::{Schedule, User};
     use mockall::predicate::*;
-    use time::macros::datetime;
+    use std::num::NonZeroU32;
+    use time::macros::{date, datetime, time};
+    use time::Weekday;
 
     type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
 
@@ -159,6 +819,7 @@ mod tests {
             uid: 1,
             name: String::from("Reminder"),
             schedule,
+            status: Status::Active,
         };
 
         let providers = Providers {
@@ -169,7 +830,7 @@ mod tests {
 
         let mut scheduler = Scheduler::new(vec![reminder], providers, Integrations::default());
 
-        assert_eq!(None, scheduler.next().await?);
+        assert_eq!(None, next_uid(&mut scheduler).await?);
 
         Ok(())
     }
@@ -191,25 +852,31 @@ mod tests {
                 uid: 1,
                 name: String::from("Reminder 1"),
                 schedule: schedule_1,
+                status: Status::Active,
             },
             Reminder {
                 uid: 2,
                 name: String::from("Reminder 2"),
                 schedule: schedule_2,
+                status: Status::Active,
             },
         ];
 
+        let mut mock_reminder_provider = crate::reminder::provider::MockProvidable::new();
+        let mut mock_user_provider = crate::user::provider::MockProvidable::new();
+        expect_no_adjustments(&mut mock_user_provider, &mut mock_reminder_provider);
+
         let providers = Providers {
-            user: &crate::user::provider::MockProvidable::new(),
-            reminder: &crate::reminder::provider::MockProvidable::new(),
+            user: &mock_user_provider,
+            reminder: &mock_reminder_provider,
             integration: &crate::integration::provider::MockProvidable::new(),
         };
 
         let mut scheduler = Scheduler::new(reminders, providers, Integrations::default());
 
         // The first reminder should be scheduled, then the second
-        assert_eq!(Some(1), scheduler.next().await?);
-        assert_eq!(Some(2), scheduler.next().await?);
+        assert_eq!(Some(1), next_uid(&mut scheduler).await?);
+        assert_eq!(Some(2), next_uid(&mut scheduler).await?);
 
         Ok(())
     }
@@ -228,79 +895,400 @@ mod tests {
             uid: 1,
             name: String::from("Reminder"),
             schedule,
+            status: Status::Active,
         }];
 
+        let mut mock_reminder_provider = crate::reminder::provider::MockProvidable::new();
+        let mut mock_user_provider = crate::user::provider::MockProvidable::new();
+        expect_no_adjustments(&mut mock_user_provider, &mut mock_reminder_provider);
+
         let providers = Providers {
-            user: &crate::user::provider::MockProvidable::new(),
-            reminder: &crate::reminder::provider::MockProvidable::new(),
+            user: &mock_user_provider,
+            reminder: &mock_reminder_provider,
             integration: &crate::integration::provider::MockProvidable::new(),
         };
 
         let mut scheduler = Scheduler::new(reminders, providers, Integrations::default());
 
         // The reminder should be rescheduled to occur a second time
-        assert_eq!(Some(1), scheduler.next().await?);
-        assert_eq!(Some(1), scheduler.next().await?);
+        assert_eq!(Some(1), next_uid(&mut scheduler).await?);
+        assert_eq!(Some(1), next_uid(&mut scheduler).await?);
 
         Ok(())
     }
 
-    /// Returns a user with uid 1 for testing
-    fn test_user() -> User {
-        User {
+    #[tokio::test]
+    async fn it_finishes_one_off_reminders_after_they_occur() -> Result<()> {
+        let reminders = vec![Reminder {
             uid: 1,
-            name: String::from("Laura"),
-        }
-    }
+            name: String::from("Dentist"),
+            schedule: Schedule::from_recurrence(
+                Recurrence::Once {
+                    time: OffsetDateTime::now_utc() + time::Duration::milliseconds(5),
+                },
+                datetime!(2020-01-06 00:00:00 UTC), // First Monday of January
+                vec![1],
+            ),
+            status: Status::Active,
+        }];
 
-    /// Returns a reminder named "Reminder" with uid 1 assigned
-    fn test_reminder(timestamp: OffsetDateTime) -> Reminder {
-        let schedule = schedule_from_timestamp(timestamp, vec![time::Duration::milliseconds(5)]);
+        let mut mock_reminder_provider = crate::reminder::provider::MockProvidable::new();
+        let mut mock_user_provider = crate::user::provider::MockProvidable::new();
+        expect_no_adjustments(&mut mock_user_provider, &mut mock_reminder_provider);
+        mock_reminder_provider
+            .expect_set_status()
+            .with(eq(1), eq(Status::Finished))
+            .returning(|_, _| Ok(()))
+            .times(1);
 
-        Reminder {
-            uid: 1,
-            name: String::from("Reminder"),
-            schedule,
-        }
+        let providers = Providers {
+            user: &mock_user_provider,
+            reminder: &mock_reminder_provider,
+            integration: &crate::integration::provider::MockProvidable::new(),
+        };
+
+        let mut scheduler = Scheduler::new(reminders, providers, Integrations::default());
+
+        // The reminder should occur once and not be rescheduled
+        assert_eq!(Some(1), next_uid(&mut scheduler).await?);
+        assert_eq!(None, next_uid(&mut scheduler).await?);
+
+        Ok(())
     }
 
     #[tokio::test]
-    async fn it_notifies_integrations_with_reminders() -> Result<()> {
-        let current_timestamp = OffsetDateTime::now_utc();
+    async fn it_completes_reminders_after_their_last_occurrence() -> Result<()> {
+        let timestamp = OffsetDateTime::now_utc();
+        let mut reminder = test_reminder(timestamp);
+        reminder.schedule = reminder.schedule.with_until(timestamp.date());
 
+        let mut mock_reminder_provider = crate::reminder::provider::MockProvidable::new();
         let mut mock_user_provider = crate::user::provider::MockProvidable::new();
-        mock_user_provider
-            .expect_get_by_uid()
-            .with(eq(1))
-            .returning(|_| Ok(test_user()))
+        expect_no_adjustments(&mut mock_user_provider, &mut mock_reminder_provider);
+        mock_reminder_provider
+            .expect_set_status()
+            .with(eq(1), eq(Status::Completed))
+            .returning(|_, _| Ok(()))
             .times(1);
 
         let providers = Providers {
             user: &mock_user_provider,
-            reminder: &crate::reminder::provider::MockProvidable::new(),
+            reminder: &mock_reminder_provider,
             integration: &crate::integration::provider::MockProvidable::new(),
         };
 
-        let mut mock_integration = MockIntegration::new();
+        let mut scheduler = Scheduler::new(vec![reminder], providers, Integrations::default());
 
-        // Expect a timestamp in the future because we run the scheduler next
-        mock_integration
-            .expect_notify()
-            .with(
-                always(),
-                eq(test_reminder(current_timestamp)),
-                function(|users: &[User]| users[0] == test_user()),
-                gt(OffsetDateTime::now_utc()),
-            )
-            .returning(|_, _, _, _| Ok(()))
-            .times(1);
+        // The reminder should occur once more before reaching its end date
+        assert_eq!(Some(1), next_uid(&mut scheduler).await?);
+        assert_eq!(None, next_uid(&mut scheduler).await?);
 
-        let mut integrations = Integrations::default();
-        integrations.insert("mock", Box::new(mock_integration));
+        Ok(())
+    }
 
-        let mut scheduler = Scheduler::new(
-            vec![test_reminder(current_timestamp)],
-            providers,
+    #[tokio::test]
+    async fn it_runs_until_shut_down() -> Result<()> {
+        let schedule = schedule_from_timestamp(
+            OffsetDateTime::now_utc(),
+            vec![time::Duration::milliseconds(5)],
+        );
+
+        let reminders = vec![Reminder {
+            uid: 1,
+            name: String::from("Reminder"),
+            schedule,
+            status: Status::Active,
+        }];
+
+        let mut mock_reminder_provider = crate::reminder::provider::MockProvidable::new();
+        let mut mock_user_provider = crate::user::provider::MockProvidable::new();
+        expect_no_adjustments(&mut mock_user_provider, &mut mock_reminder_provider);
+        mock_reminder_provider
+            .expect_get_change_count()
+            .returning(|| Ok(1));
+        mock_reminder_provider
+            .expect_get_all()
+            .return_once(|| Ok(Vec::new()));
+
+        let providers = Providers {
+            user: &mock_user_provider,
+            reminder: &mock_reminder_provider,
+            integration: &crate::integration::provider::MockProvidable::new(),
+        };
+
+        let mut scheduler = Scheduler::new(reminders, providers, Integrations::default())
+            .with_reload_interval(std::time::Duration::from_millis(5));
+
+        // The scheduler keeps waiting for reminders after the occurrence, until it's shut down
+        let shutdown = tokio::time::sleep(std::time::Duration::from_millis(30));
+        assert_eq!(Some(()), scheduler.run_until(shutdown).await?);
+
+        // Without a reload interval, the scheduler stops once the queue is empty
+        scheduler.reload_interval = None;
+        let shutdown = tokio::time::sleep(std::time::Duration::from_secs(60));
+        assert_eq!(None, scheduler.run_until(shutdown).await?);
+
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn it_fires_a_week_of_occurrences_in_order() -> Result<()> {
+        let start = datetime!(2020-01-06 00:00:00 UTC); // First Monday of January
+        let schedule = Schedule::new(
+            [
+                Weekday::Monday,
+                Weekday::Tuesday,
+                Weekday::Wednesday,
+                Weekday::Thursday,
+                Weekday::Friday,
+                Weekday::Saturday,
+                Weekday::Sunday,
+            ]
+            .into_iter()
+            .map(|weekday| (weekday, vec![time!(07:00), time!(19:00)]))
+            .collect(),
+            start,
+            vec![1],
+        );
+
+        // Every occurrence is recorded once, in order, at its exact scheduled time
+        let mut sequence = mockall::Sequence::new();
+        let mut mock_reminder_provider = crate::reminder::provider::MockProvidable::new();
+        for occurrence in 0..14 {
+            mock_reminder_provider
+                .expect_set_last_occurrence()
+                .with(
+                    eq(1),
+                    eq(start + time::Duration::hours(7 + 12 * occurrence)),
+                )
+                .returning(|_, _| Ok(()))
+                .times(1)
+                .in_sequence(&mut sequence);
+        }
+
+        // Who was assigned is recorded, without affecting the order of occurrences
+        let mut mock_user_provider = crate::user::provider::MockProvidable::new();
+        mock_user_provider
+            .expect_get_absences()
+            .returning(|| Ok(Vec::new()));
+        mock_reminder_provider
+            .expect_get_overrides()
+            .returning(|_| Ok(Vec::new()));
+        mock_reminder_provider
+            .expect_get_history()
+            .returning(|_| Ok(Vec::new()));
+        mock_reminder_provider
+            .expect_add_history()
+            .returning(|_| Ok(()))
+            .times(14);
+
+        let providers = Providers {
+            user: &mock_user_provider,
+            reminder: &mock_reminder_provider,
+            integration: &crate::integration::provider::MockProvidable::new(),
+        };
+
+        let mut scheduler = Scheduler::with_clock(
+            vec![Reminder {
+                uid: 1,
+                name: String::from("Reminder"),
+                schedule,
+                status: Status::Active,
+            }],
+            providers,
+            Integrations::default(),
+            clock::Virtual::new(start),
+        );
+
+        for _ in 0..14 {
+            assert_eq!(Some(1), next_uid(&mut scheduler).await?);
+        }
+
+        assert!(scheduler.clock.now() >= datetime!(2020-01-12 19:00:00 UTC));
+        assert!(scheduler.clock.now() < datetime!(2020-01-12 19:00:01 UTC));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_does_not_schedule_finished_reminders() -> Result<()> {
+        let mut reminder = test_reminder(OffsetDateTime::now_utc());
+        reminder.status = Status::Finished;
+
+        let providers = Providers {
+            user: &crate::user::provider::MockProvidable::new(),
+            reminder: &crate::reminder::provider::MockProvidable::new(),
+            integration: &crate::integration::provider::MockProvidable::new(),
+        };
+
+        let mut scheduler = Scheduler::new(vec![reminder], providers, Integrations::default());
+
+        assert_eq!(None, next_uid(&mut scheduler).await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_records_reminders_which_ended_while_not_running() -> Result<()> {
+        let start = datetime!(2020-01-06 00:00 UTC);
+        let one_off = Reminder {
+            uid: 1,
+            name: String::from("One-off"),
+            schedule: Schedule::from_recurrence(
+                Recurrence::Once {
+                    time: datetime!(2020-01-06 07:00 UTC),
+                },
+                start,
+                vec![1],
+            ),
+            status: Status::Active,
+        };
+        let recurring = Reminder {
+            uid: 2,
+            name: String::from("Recurring"),
+            schedule: Schedule::new(
+                vec![(Weekday::Monday, vec![time!(07:00)])]
+                    .into_iter()
+                    .collect(),
+                start,
+                vec![1],
+            )
+            .with_until(date!(2020 - 01 - 31)),
+            status: Status::Active,
+        };
+
+        let mut mock_reminder_provider = crate::reminder::provider::MockProvidable::new();
+        mock_reminder_provider
+            .expect_set_status()
+            .with(eq(1), eq(Status::Finished))
+            .returning(|_, _| Ok(()))
+            .times(1);
+        mock_reminder_provider
+            .expect_set_status()
+            .with(eq(2), eq(Status::Completed))
+            .returning(|_, _| Ok(()))
+            .times(1);
+
+        let providers = Providers {
+            user: &crate::user::provider::MockProvidable::new(),
+            reminder: &mock_reminder_provider,
+            integration: &crate::integration::provider::MockProvidable::new(),
+        };
+
+        let mut scheduler =
+            Scheduler::new(vec![one_off, recurring], providers, Integrations::default());
+
+        assert_eq!(None, next_uid(&mut scheduler).await?);
+
+        Ok(())
+    }
+
+    /// Returns a user with uid 1 for testing
+    fn test_user() -> User {
+        User {
+            uid: 1,
+            name: String::from("Laura"),
+        }
+    }
+
+    /// Expects the history of reminders to be empty, and to be recorded
+    fn expect_empty_history(provider: &mut crate::reminder::provider::MockProvidable) {
+        provider.expect_get_history().returning(|_| Ok(Vec::new()));
+        provider.expect_add_history().returning(|_| Ok(()));
+        expect_last_occurrences(provider);
+    }
+
+    /// Expects users to have no absences and reminders to have no overrides or history, and who
+    /// was assigned to be recorded along with the last occurrences
+    fn expect_no_adjustments(
+        user_provider: &mut crate::user::provider::MockProvidable,
+        reminder_provider: &mut crate::reminder::provider::MockProvidable,
+    ) {
+        user_provider
+            .expect_get_absences()
+            .returning(|| Ok(Vec::new()));
+        reminder_provider
+            .expect_get_overrides()
+            .returning(|_| Ok(Vec::new()));
+        expect_empty_history(reminder_provider);
+    }
+
+    /// Expects the last occurrences of reminders to be recorded
+    fn expect_last_occurrences(provider: &mut crate::reminder::provider::MockProvidable) {
+        provider
+            .expect_set_last_occurrence()
+            .returning(|_, _| Ok(()));
+    }
+
+    /// Returns a notification which was delivered immediately
+    fn delivered() -> Delivery {
+        Box::pin(std::future::ready(Ok(())))
+    }
+
+    /// Processes the next event and returns the uid of its reminder
+    async fn next_uid<C: Clock>(scheduler: &mut Scheduler<'_, C>) -> Result<Option<i32>> {
+        Ok(scheduler.next().await?.map(|processed| processed.uid))
+    }
+
+    /// Returns a reminder named "Reminder" with uid 1 assigned
+    fn test_reminder(timestamp: OffsetDateTime) -> Reminder {
+        let schedule = schedule_from_timestamp(timestamp, vec![time::Duration::milliseconds(5)]);
+
+        Reminder {
+            uid: 1,
+            name: String::from("Reminder"),
+            schedule,
+            status: Status::Active,
+        }
+    }
+
+    #[tokio::test]
+    async fn it_notifies_integrations_with_reminders() -> Result<()> {
+        let current_timestamp = OffsetDateTime::now_utc();
+
+        let mut mock_user_provider = crate::user::provider::MockProvidable::new();
+        mock_user_provider
+            .expect_get_by_uid()
+            .with(eq(1))
+            .returning(|_| Ok(test_user()))
+            .times(1);
+        mock_user_provider
+            .expect_get_absences()
+            .returning(|| Ok(Vec::new()));
+
+        let mut mock_reminder_provider = crate::reminder::provider::MockProvidable::new();
+        expect_empty_history(&mut mock_reminder_provider);
+        mock_reminder_provider
+            .expect_get_overrides()
+            .returning(|_| Ok(Vec::new()));
+
+        let providers = Providers {
+            user: &mock_user_provider,
+            reminder: &mock_reminder_provider,
+            integration: &crate::integration::provider::MockProvidable::new(),
+        };
+
+        let mut mock_integration = MockIntegration::new();
+
+        // Expect a timestamp in the future because we run the scheduler next
+        mock_integration
+            .expect_notify()
+            .with(
+                always(),
+                eq(test_reminder(current_timestamp)),
+                function(|users: &[User]| users[0] == test_user()),
+                gt(OffsetDateTime::now_utc()),
+                eq(false),
+            )
+            .returning(|_, _, _, _, _| delivered())
+            .times(1);
+
+        let mut integrations = Integrations::default();
+        integrations.insert("mock", Box::new(mock_integration));
+
+        let mut scheduler = Scheduler::new(
+            vec![test_reminder(current_timestamp)],
+            providers,
             integrations,
         );
 
@@ -310,6 +1298,307 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn it_notifies_integrations_with_every_assignee() -> Result<()> {
+        let current_timestamp = OffsetDateTime::now_utc();
+        let mut reminder = test_reminder(current_timestamp);
+        reminder.schedule.assignees = vec![1, 2, 3];
+        reminder.schedule = reminder
+            .schedule
+            .with_assignees_per_occurrence(NonZeroU32::new(2).ok_or("Invalid assignees")?);
+
+        let mut mock_user_provider = crate::user::provider::MockProvidable::new();
+        mock_user_provider
+            .expect_get_by_uid()
+            .returning(|uid| {
+                Ok(User {
+                    uid,
+                    name: format!("User {}", uid),
+                })
+            })
+            .times(2);
+        mock_user_provider
+            .expect_get_absences()
+            .returning(|| Ok(Vec::new()));
+
+        let mut mock_reminder_provider = crate::reminder::provider::MockProvidable::new();
+        expect_empty_history(&mut mock_reminder_provider);
+        mock_reminder_provider
+            .expect_get_overrides()
+            .returning(|_| Ok(Vec::new()));
+
+        let providers = Providers {
+            user: &mock_user_provider,
+            reminder: &mock_reminder_provider,
+            integration: &crate::integration::provider::MockProvidable::new(),
+        };
+
+        let mut mock_integration = MockIntegration::new();
+        mock_integration
+            .expect_notify()
+            .with(
+                always(),
+                always(),
+                function(|users: &[User]| users.len() == 2 && users[0] != users[1]),
+                always(),
+                eq(false),
+            )
+            .returning(|_, _, _, _, _| delivered())
+            .times(1);
+
+        let mut integrations = Integrations::default();
+        integrations.insert("mock", Box::new(mock_integration));
+
+        let mut scheduler = Scheduler::new(vec![reminder], providers, integrations);
+
+        // Run the scheduler for one tick
+        scheduler.next().await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_notifies_integrations_with_substitutes_for_absent_assignees() -> Result<()> {
+        let current_timestamp = OffsetDateTime::now_utc();
+        let mut reminder = test_reminder(current_timestamp);
+        reminder.schedule.assignees = vec![1, 2];
+
+        let mut mock_user_provider = crate::user::provider::MockProvidable::new();
+        mock_user_provider
+            .expect_get_by_uid()
+            .with(eq(2))
+            .returning(|uid| {
+                Ok(User {
+                    uid,
+                    name: format!("User {}", uid),
+                })
+            })
+            .times(1);
+        mock_user_provider.expect_get_absences().returning(move || {
+            let today = current_timestamp.date();
+
+            Ok(vec![Absence {
+                uid: 1,
+                user_uid: 1,
+                dates: DateRange {
+                    from: today.previous_day().unwrap_or(today),
+                    to: today.next_day().unwrap_or(today),
+                },
+                defer_turn: false,
+            }])
+        });
+
+        let mut mock_reminder_provider = crate::reminder::provider::MockProvidable::new();
+        expect_empty_history(&mut mock_reminder_provider);
+        mock_reminder_provider
+            .expect_get_overrides()
+            .returning(|_| Ok(Vec::new()));
+
+        let providers = Providers {
+            user: &mock_user_provider,
+            reminder: &mock_reminder_provider,
+            integration: &crate::integration::provider::MockProvidable::new(),
+        };
+
+        let mut mock_integration = MockIntegration::new();
+        mock_integration
+            .expect_notify()
+            .with(
+                always(),
+                always(),
+                function(|users: &[User]| users.len() == 1 && users[0].uid == 2),
+                always(),
+                eq(false),
+            )
+            .returning(|_, _, _, _, _| delivered())
+            .times(1);
+
+        let mut integrations = Integrations::default();
+        integrations.insert("mock", Box::new(mock_integration));
+
+        let mut scheduler = Scheduler::new(vec![reminder], providers, integrations);
+
+        // Run the scheduler for one tick
+        scheduler.next().await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_notifies_integrations_with_overridden_assignees() -> Result<()> {
+        let current_timestamp = OffsetDateTime::now_utc();
+        let mut reminder = test_reminder(current_timestamp);
+        reminder.schedule.assignees = vec![1, 2];
+
+        let occurrence = reminder
+            .schedule
+            .occurrences(current_timestamp)
+            .next()
+            .map(|(time, _)| time)
+            .ok_or("Expected an occurrence")?;
+
+        let mut mock_user_provider = crate::user::provider::MockProvidable::new();
+        mock_user_provider
+            .expect_get_by_uid()
+            .with(eq(3))
+            .returning(|uid| {
+                Ok(User {
+                    uid,
+                    name: format!("User {}", uid),
+                })
+            })
+            .times(1);
+        mock_user_provider
+            .expect_get_absences()
+            .returning(|| Ok(Vec::new()));
+
+        let mut mock_reminder_provider = crate::reminder::provider::MockProvidable::new();
+        expect_empty_history(&mut mock_reminder_provider);
+        mock_reminder_provider
+            .expect_get_overrides()
+            .with(eq(1))
+            .returning(move |_| {
+                Ok(vec![AssignmentOverride {
+                    reminder_uid: 1,
+                    occurrence,
+                    assignees: vec![3],
+                }])
+            });
+
+        let providers = Providers {
+            user: &mock_user_provider,
+            reminder: &mock_reminder_provider,
+            integration: &crate::integration::provider::MockProvidable::new(),
+        };
+
+        let mut mock_integration = MockIntegration::new();
+        mock_integration
+            .expect_notify()
+            .with(
+                always(),
+                always(),
+                function(|users: &[User]| users.len() == 1 && users[0].uid == 3),
+                eq(occurrence),
+                eq(false),
+            )
+            .returning(|_, _, _, _, _| delivered())
+            .times(1);
+
+        let mut integrations = Integrations::default();
+        integrations.insert("mock", Box::new(mock_integration));
+
+        let mut scheduler = Scheduler::new(vec![reminder], providers, integrations);
+
+        // Run the scheduler for one tick
+        scheduler.next().await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_notifies_integrations_without_assignees_who_no_longer_exist() -> Result<()> {
+        let current_timestamp = OffsetDateTime::now_utc();
+        let mut reminder = test_reminder(current_timestamp);
+        reminder.schedule.assignees = vec![1, 2];
+        reminder.schedule = reminder
+            .schedule
+            .with_assignees_per_occurrence(NonZeroU32::new(2).ok_or("Invalid assignees")?);
+
+        let mut mock_user_provider = crate::user::provider::MockProvidable::new();
+        mock_user_provider
+            .expect_get_by_uid()
+            .with(eq(1))
+            .returning(|_| Ok(test_user()));
+        mock_user_provider
+            .expect_get_by_uid()
+            .with(eq(2))
+            .returning(|_| {
+                Err(crate::user::Error::Database {
+                    source: diesel::result::Error::NotFound,
+                })
+            });
+        mock_user_provider
+            .expect_get_absences()
+            .returning(|| Ok(Vec::new()));
+
+        let mut mock_reminder_provider = crate::reminder::provider::MockProvidable::new();
+        expect_empty_history(&mut mock_reminder_provider);
+        mock_reminder_provider
+            .expect_get_overrides()
+            .returning(|_| Ok(Vec::new()));
+
+        let providers = Providers {
+            user: &mock_user_provider,
+            reminder: &mock_reminder_provider,
+            integration: &crate::integration::provider::MockProvidable::new(),
+        };
+
+        let mut mock_integration = MockIntegration::new();
+        mock_integration
+            .expect_notify()
+            .with(
+                always(),
+                always(),
+                function(|users: &[User]| users == [test_user()]),
+                always(),
+                eq(false),
+            )
+            .returning(|_, _, _, _, _| delivered())
+            .times(1);
+
+        let mut integrations = Integrations::default();
+        integrations.insert("mock", Box::new(mock_integration));
+
+        let mut scheduler = Scheduler::new(vec![reminder], providers, integrations);
+
+        // Run the scheduler for one tick, which should return Ok
+        scheduler.next().await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_records_who_was_assigned_without_integrations() -> Result<()> {
+        let current_timestamp = OffsetDateTime::now_utc();
+
+        let mut mock_user_provider = crate::user::provider::MockProvidable::new();
+        mock_user_provider
+            .expect_get_absences()
+            .returning(|| Ok(Vec::new()));
+
+        let mut mock_reminder_provider = crate::reminder::provider::MockProvidable::new();
+        mock_reminder_provider
+            .expect_get_overrides()
+            .returning(|_| Ok(Vec::new()));
+        mock_reminder_provider
+            .expect_get_history()
+            .returning(|_| Ok(Vec::new()));
+        mock_reminder_provider
+            .expect_add_history()
+            .withf(|past_assignment| {
+                past_assignment.reminder_uid == 1 && past_assignment.assignees == vec![1]
+            })
+            .returning(|_| Ok(()))
+            .times(1);
+        expect_last_occurrences(&mut mock_reminder_provider);
+
+        let providers = Providers {
+            user: &mock_user_provider,
+            reminder: &mock_reminder_provider,
+            integration: &crate::integration::provider::MockProvidable::new(),
+        };
+
+        let mut scheduler = Scheduler::new(
+            vec![test_reminder(current_timestamp)],
+            providers,
+            Integrations::default(),
+        );
+
+        assert_eq!(Some(1), next_uid(&mut scheduler).await?);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn it_continues_when_an_integration_fails() -> Result<()> {
         let current_timestamp = OffsetDateTime::now_utc();
@@ -320,10 +1609,24 @@ mod tests {
             .with(eq(1))
             .returning(|_| Ok(test_user()))
             .times(1);
+        mock_user_provider
+            .expect_get_absences()
+            .returning(|| Ok(Vec::new()));
+
+        // Nobody was notified, so who was assigned isn't recorded
+        let mut mock_reminder_provider = crate::reminder::provider::MockProvidable::new();
+        mock_reminder_provider
+            .expect_get_history()
+            .returning(|_| Ok(Vec::new()));
+        mock_reminder_provider.expect_add_history().never();
+        expect_last_occurrences(&mut mock_reminder_provider);
+        mock_reminder_provider
+            .expect_get_overrides()
+            .returning(|_| Ok(Vec::new()));
 
         let providers = Providers {
             user: &mock_user_provider,
-            reminder: &crate::reminder::provider::MockProvidable::new(),
+            reminder: &mock_reminder_provider,
             integration: &crate::integration::provider::MockProvidable::new(),
         };
 
@@ -337,8 +1640,13 @@ mod tests {
                 eq(test_reminder(current_timestamp)),
                 function(|users: &[User]| users[0] == test_user()),
                 gt(OffsetDateTime::now_utc()),
+                eq(false),
             )
-            .returning(|_, _, _, _| Err(Box::new(Error::Unavailable(1))))
+            .returning(|_, _, _, _, _| {
+                Box::pin(std::future::ready(Err(
+                    Box::new(Error::Unavailable(1)).into()
+                )))
+            })
             .times(1);
 
         let mut integrations = Integrations::default();
@@ -350,8 +1658,324 @@ mod tests {
             integrations,
         );
 
-        // Run the scheduler for one tick, which should return Ok
-        scheduler.next().await?;
+        // Run the scheduler for one tick, which should return Ok with the failure reported
+        let processed = scheduler.next().await?;
+        let outcomes = processed.map(|processed| processed.outcomes);
+
+        assert!(matches!(
+            outcomes.as_ref().and_then(|outcomes| outcomes.get("mock")),
+            Some(Outcome::Failed(_))
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn it_times_out_slow_integrations_without_delaying_others() -> Result<()> {
+        let start = datetime!(2020-01-06 07:00 UTC);
+
+        let mut mock_user_provider = crate::user::provider::MockProvidable::new();
+        mock_user_provider
+            .expect_get_by_uid()
+            .returning(|_| Ok(test_user()));
+        mock_user_provider
+            .expect_get_absences()
+            .returning(|| Ok(Vec::new()));
+
+        let mut mock_reminder_provider = crate::reminder::provider::MockProvidable::new();
+        expect_empty_history(&mut mock_reminder_provider);
+        mock_reminder_provider
+            .expect_get_overrides()
+            .returning(|_| Ok(Vec::new()));
+
+        let providers = Providers {
+            user: &mock_user_provider,
+            reminder: &mock_reminder_provider,
+            integration: &crate::integration::provider::MockProvidable::new(),
+        };
+
+        let mut fast_integration = MockIntegration::new();
+        fast_integration
+            .expect_notify()
+            .returning(|_, _, _, _, _| delivered())
+            .times(1);
+
+        let mut slow_integration = MockIntegration::new();
+        slow_integration
+            .expect_notify()
+            .returning(|_, _, _, _, _| {
+                Box::pin(async {
+                    tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+                    Ok(())
+                })
+            })
+            .times(1);
+
+        let mut integrations =
+            Integrations::default().with_timeout("slow", std::time::Duration::from_secs(5));
+        integrations.insert("fast", Box::new(fast_integration));
+        integrations.insert("slow", Box::new(slow_integration));
+
+        let mut scheduler = Scheduler::with_clock(
+            vec![test_reminder(start)],
+            providers,
+            integrations,
+            clock::Virtual::new(start),
+        );
+
+        let processed = scheduler.next().await?;
+        let outcomes = processed.map(|processed| processed.outcomes);
+
+        assert!(matches!(
+            outcomes.as_ref().and_then(|outcomes| outcomes.get("fast")),
+            Some(Outcome::Delivered)
+        ));
+        assert!(matches!(
+            outcomes.as_ref().and_then(|outcomes| outcomes.get("slow")),
+            Some(Outcome::TimedOut(timeout)) if timeout.as_secs() == 5
+        ));
+
+        // The slow integration only held up the scheduler until its timeout
+        assert!(scheduler.clock.now() < start + time::Duration::seconds(6));
+
+        Ok(())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn it_notifies_missed_occurrences_late_before_scheduled_ones() -> Result<()> {
+        let current_timestamp = datetime!(2020-01-08 12:00:00 UTC);
+        let reminder = Reminder {
+            uid: 1,
+            name: String::from("Reminder"),
+            schedule: schedule_from_timestamp(
+                current_timestamp,
+                vec![
+                    time::Duration::milliseconds(-30),
+                    time::Duration::milliseconds(-20),
+                    time::Duration::milliseconds(-10),
+                    time::Duration::milliseconds(5),
+                ],
+            )
+            .with_catch_up(CatchUp::All),
+            status: Status::Active,
+        };
+
+        let mut mock_user_provider = crate::user::provider::MockProvidable::new();
+        mock_user_provider
+            .expect_get_by_uid()
+            .returning(|_| Ok(test_user()));
+        mock_user_provider
+            .expect_get_absences()
+            .returning(|| Ok(Vec::new()));
+
+        // The occurrence 30ms ago was the last to be notified before the scheduler started
+        let last_occurrence = current_timestamp - time::Duration::milliseconds(30);
+        let mut mock_reminder_provider = crate::reminder::provider::MockProvidable::new();
+        expect_empty_history(&mut mock_reminder_provider);
+        mock_reminder_provider
+            .expect_get_overrides()
+            .returning(|_| Ok(Vec::new()));
+        mock_reminder_provider
+            .expect_get_last_occurrence()
+            .with(eq(1))
+            .returning(move |_| Ok(Some(last_occurrence)))
+            .times(1);
+
+        let providers = Providers {
+            user: &mock_user_provider,
+            reminder: &mock_reminder_provider,
+            integration: &crate::integration::provider::MockProvidable::new(),
+        };
+
+        let mut mock_integration = MockIntegration::new();
+        mock_integration
+            .expect_notify()
+            .with(
+                always(),
+                always(),
+                always(),
+                lt(current_timestamp),
+                eq(true),
+            )
+            .returning(|_, _, _, _, _| delivered())
+            .times(2);
+        mock_integration
+            .expect_notify()
+            .with(
+                always(),
+                always(),
+                always(),
+                gt(current_timestamp),
+                eq(false),
+            )
+            .returning(|_, _, _, _, _| delivered())
+            .times(1);
+
+        let mut integrations = Integrations::default();
+        integrations.insert("mock", Box::new(mock_integration));
+
+        let mut scheduler = Scheduler::with_clock(
+            vec![reminder],
+            providers,
+            integrations,
+            clock::Virtual::new(current_timestamp),
+        );
+
+        // Both missed occurrences are notified before the upcoming one
+        assert_eq!(Some(1), next_uid(&mut scheduler).await?);
+        assert_eq!(Some(1), next_uid(&mut scheduler).await?);
+        assert_eq!(Some(1), next_uid(&mut scheduler).await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_announces_handoffs_to_integrations_ahead_of_time() -> Result<()> {
+        let current_timestamp = OffsetDateTime::now_utc();
+
+        // The second occurrence is handed off to the other assignee a minute ahead of time
+        let mut reminder = test_reminder(current_timestamp);
+        reminder.schedule = schedule_from_timestamp(
+            current_timestamp,
+            vec![
+                time::Duration::milliseconds(5),
+                time::Duration::minutes(1) + time::Duration::milliseconds(10),
+            ],
+        )
+        .with_handoff_notice(Notice::from_minutes(1));
+        reminder.schedule.assignees = vec![1, 2];
+
+        let mut mock_user_provider = crate::user::provider::MockProvidable::new();
+        mock_user_provider.expect_get_by_uid().returning(|uid| {
+            Ok(User {
+                uid,
+                name: format!("User {}", uid),
+            })
+        });
+        mock_user_provider
+            .expect_get_absences()
+            .returning(|| Ok(Vec::new()));
+
+        let mut mock_reminder_provider = crate::reminder::provider::MockProvidable::new();
+        expect_empty_history(&mut mock_reminder_provider);
+        mock_reminder_provider
+            .expect_get_overrides()
+            .returning(|_| Ok(Vec::new()));
+
+        let providers = Providers {
+            user: &mock_user_provider,
+            reminder: &mock_reminder_provider,
+            integration: &crate::integration::provider::MockProvidable::new(),
+        };
+
+        let mut mock_integration = MockIntegration::new();
+        mock_integration
+            .expect_notify()
+            .returning(|_, _, _, _, _| delivered())
+            .times(1);
+        mock_integration
+            .expect_handoff()
+            .with(
+                always(),
+                always(),
+                function(|users: &[User]| users.len() == 1),
+                function(|users: &[User]| users.len() == 1),
+                gt(current_timestamp + time::Duration::minutes(1)),
+            )
+            .withf(|_, _, incoming, outgoing, _| incoming[0] != outgoing[0])
+            .returning(|_, _, _, _, _| delivered())
+            .times(1);
+
+        let mut integrations = Integrations::default();
+        integrations.insert("mock", Box::new(mock_integration));
+
+        let mut scheduler = Scheduler::new(vec![reminder], providers, integrations);
+
+        // The first occurrence is notified, then the handoff of the second is announced
+        assert_eq!(Some(1), next_uid(&mut scheduler).await?);
+        assert_eq!(Some(1), next_uid(&mut scheduler).await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_reloads_added_updated_and_removed_reminders() -> Result<()> {
+        let current_timestamp = OffsetDateTime::now_utc();
+        let reminder = move |uid: i32, duration: time::Duration| Reminder {
+            uid,
+            name: format!("Reminder {}", uid),
+            schedule: schedule_from_timestamp(current_timestamp, vec![duration]),
+            status: Status::Active,
+        };
+
+        let mut mock_reminder_provider = crate::reminder::provider::MockProvidable::new();
+        let mut mock_user_provider = crate::user::provider::MockProvidable::new();
+        expect_no_adjustments(&mut mock_user_provider, &mut mock_reminder_provider);
+        mock_reminder_provider
+            .expect_get_change_count()
+            .returning(|| Ok(1));
+        mock_reminder_provider
+            .expect_get_all()
+            .return_once(move || {
+                Ok(vec![
+                    reminder(1, time::Duration::milliseconds(15)),
+                    reminder(3, time::Duration::milliseconds(5)),
+                ])
+            })
+            .times(1);
+
+        let providers = Providers {
+            user: &mock_user_provider,
+            reminder: &mock_reminder_provider,
+            integration: &crate::integration::provider::MockProvidable::new(),
+        };
+
+        let mut scheduler = Scheduler::new(
+            vec![
+                reminder(1, time::Duration::minutes(1)),
+                reminder(2, time::Duration::milliseconds(10)),
+            ],
+            providers,
+            Integrations::default(),
+        );
+
+        // Reminders are only reloaded when they changed since they were last loaded
+        assert!(scheduler.reload()?);
+        assert!(!scheduler.reload()?);
+
+        // The added reminder occurs first, and the removed reminder doesn't occur at all
+        assert_eq!(Some(3), next_uid(&mut scheduler).await?);
+        assert_eq!(Some(1), next_uid(&mut scheduler).await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn it_waits_for_reminders_to_be_added_while_reloading() -> Result<()> {
+        let current_timestamp = OffsetDateTime::now_utc();
+
+        let mut mock_reminder_provider = crate::reminder::provider::MockProvidable::new();
+        let mut mock_user_provider = crate::user::provider::MockProvidable::new();
+        expect_no_adjustments(&mut mock_user_provider, &mut mock_reminder_provider);
+        mock_reminder_provider
+            .expect_get_change_count()
+            .returning(|| Ok(1));
+        mock_reminder_provider
+            .expect_get_all()
+            .return_once(move || Ok(vec![test_reminder(current_timestamp)]))
+            .times(1);
+
+        let providers = Providers {
+            user: &mock_user_provider,
+            reminder: &mock_reminder_provider,
+            integration: &crate::integration::provider::MockProvidable::new(),
+        };
+
+        let mut scheduler = Scheduler::new(Vec::new(), providers, Integrations::default())
+            .with_reload_interval(std::time::Duration::from_millis(1));
+
+        // The empty scheduler keeps running until the added reminder occurs
+        assert_eq!(Some(1), next_uid(&mut scheduler).await?);
 
         Ok(())
     }
@@ -13,4 +13,11 @@ pub enum Error {
         #[from]
         source: DieselError,
     },
+    /// The reminders of a removed user could not be updated
+    #[error("Failed to update the reminders of a removed user: {source}")]
+    Reminder {
+        /// Underlying error type
+        #[from]
+        source: crate::reminder::Error,
+    },
 }
@@ -1,7 +1,12 @@
 //! Data models for users of the service
 
-use crate::database::schema::users;
+use crate::database::schema::{absences, users};
+use crate::schedule::{self, Availability, DateRange};
+use diesel::backend::Backend;
+use diesel::deserialize::{FromSqlRow, Queryable, Result as FromSqlResult};
+use diesel::sql_types::{Bool, Integer, Text};
 use serde::Serialize;
+use std::iter::FromIterator;
 
 /// Record for an individual user of the service
 #[derive(AsChangeset, Debug, Eq, PartialEq, Queryable, Serialize)]
@@ -23,3 +28,93 @@ pub struct NewUser {
 
 /// Necessary data to update a new user
 pub type UpdatedUser = User;
+
+/// Record for dates on which a user is away
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct Absence {
+    /// Unique identifier for the absence record
+    pub uid: i32,
+    /// Uid of the user who is away
+    pub user_uid: i32,
+    /// Dates on which the user is away
+    pub dates: DateRange,
+    /// Whether the user keeps their turn until they return, instead of losing it
+    pub defer_turn: bool,
+}
+
+impl<TDatabase> FromSqlRow<(Integer, Integer, Text, Bool), TDatabase> for Absence
+where
+    TDatabase: Backend,
+    i32: FromSqlRow<Integer, TDatabase>,
+    String: FromSqlRow<Text, TDatabase>,
+    bool: FromSqlRow<Bool, TDatabase>,
+{
+    const FIELDS_NEEDED: usize = 4;
+
+    fn build_from_row<TRow: diesel::row::Row<TDatabase>>(row: &mut TRow) -> FromSqlResult<Self> {
+        Ok(Self {
+            uid: i32::build_from_row(row)?,
+            user_uid: i32::build_from_row(row)?,
+            dates: String::build_from_row(row)?.parse()?,
+            defer_turn: bool::build_from_row(row)?,
+        })
+    }
+}
+
+impl Queryable<absences::SqlType, diesel::sqlite::Sqlite> for Absence {
+    type Row = Absence;
+
+    fn build(row: Self::Row) -> Self {
+        row
+    }
+}
+
+impl FromIterator<Absence> for Availability {
+    fn from_iter<T: IntoIterator<Item = Absence>>(absences: T) -> Self {
+        absences
+            .into_iter()
+            .map(|absence| {
+                (
+                    absence.user_uid,
+                    schedule::Absence {
+                        dates: absence.dates,
+                        defer_turn: absence.defer_turn,
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
+/// Necessary data to mark a user as away
+#[derive(Debug, Eq, PartialEq)]
+pub struct NewAbsence {
+    /// Uid of the user who is away
+    pub user_uid: i32,
+    /// Dates on which the user is away
+    pub dates: DateRange,
+    /// Whether the user keeps their turn until they return, instead of losing it
+    pub defer_turn: bool,
+}
+
+/// Insertable `NewAbsence` for use with `diesel`
+#[derive(Debug, Insertable, Eq, PartialEq)]
+#[table_name = "absences"]
+pub(crate) struct InsertableNewAbsence {
+    /// Uid of the user who is away
+    pub user_uid: i32,
+    /// Dates on which the user is away, such as `2026-08-10..2026-08-16`
+    pub dates: String,
+    /// Whether the user keeps their turn until they return, instead of losing it
+    pub defer_turn: bool,
+}
+
+impl From<NewAbsence> for InsertableNewAbsence {
+    fn from(new_absence: NewAbsence) -> Self {
+        Self {
+            user_uid: new_absence.user_uid,
+            dates: new_absence.dates.to_string(),
+            defer_turn: new_absence.defer_turn,
+        }
+    }
+}
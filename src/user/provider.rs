@@ -1,8 +1,9 @@
 //! Provider for user data
 
-use super::model::{NewUser, UpdatedUser, User};
+use super::model::{Absence, InsertableNewAbsence, NewAbsence, NewUser, UpdatedUser, User};
 use super::Error;
 use crate::database::{self, Database};
+use crate::reminder::{self, model::UpdatedReminder, provider::Providable as _};
 use diesel::prelude::*;
 use std::sync::Arc;
 
@@ -26,12 +27,13 @@ pub trait Providable {
     /// When the update fails
     fn update(&self, user: UpdatedUser) -> Result<User, Error>;
 
-    /// Removes an existing user from the database
+    /// Removes an existing user from the database, along with updating the reminders they were
+    /// unassigned from. The user is only removed if all of the reminders are updated
     ///
     /// # Errors
     ///
     /// When the removal fails
-    fn remove(&self, uid: i32) -> Result<(), Error>;
+    fn remove(&self, uid: i32, unassigned: Vec<UpdatedReminder>) -> Result<(), Error>;
 
     /// Retrieves all users from the database
     ///
@@ -46,6 +48,20 @@ pub trait Providable {
     ///
     /// When user retrieval fails
     fn get_by_uid(&self, uid: i32) -> Result<User, Error>;
+
+    /// Marks a user as away in the database
+    ///
+    /// # Errors
+    ///
+    /// When the insertion fails
+    fn add_absence(&self, absence: NewAbsence) -> Result<Absence, Error>;
+
+    /// Retrieves the absences of all users from the database
+    ///
+    /// # Errors
+    ///
+    /// When absence retrieval fails
+    fn get_absences(&self) -> Result<Vec<Absence>, Error>;
 }
 
 /// Provides access to user data in persistent storage
@@ -82,11 +98,21 @@ impl Providable for Provider {
         self.get_by_uid(uid)
     }
 
-    fn remove(&self, uid: i32) -> Result<(), Error> {
-        diesel::delete(database::schema::users::table.find(uid))
+    fn remove(&self, uid: i32, unassigned: Vec<UpdatedReminder>) -> Result<(), Error> {
+        self.database.connection().transaction(|| {
+            reminder::Provider::new(Arc::clone(&self.database)).update_all(unassigned)?;
+
+            diesel::delete(
+                database::schema::absences::table
+                    .filter(database::schema::absences::user_uid.eq(uid)),
+            )
             .execute(self.database.connection())?;
 
-        Ok(())
+            diesel::delete(database::schema::users::table.find(uid))
+                .execute(self.database.connection())?;
+
+            Ok(())
+        })
     }
 
     fn get_all(&self) -> Result<Vec<User>, Error> {
@@ -98,4 +124,20 @@ impl Providable for Provider {
             .find(uid)
             .first(self.database.connection())?)
     }
+
+    fn add_absence(&self, absence: NewAbsence) -> Result<Absence, Error> {
+        let insertable_absence: InsertableNewAbsence = absence.into();
+
+        diesel::insert_into(database::schema::absences::table)
+            .values(insertable_absence)
+            .execute(self.database.connection())?;
+
+        Ok(database::schema::absences::table
+            .order(database::schema::absences::uid.desc())
+            .first(self.database.connection())?)
+    }
+
+    fn get_absences(&self) -> Result<Vec<Absence>, Error> {
+        Ok(database::schema::absences::table.load(self.database.connection())?)
+    }
 }
@@ -6,8 +6,11 @@ mod common_database;
 
 use common::Result;
 use common_command::Executor;
+use remembear::reminder::model::Status;
+use remembear::schedule::{model::Recurrence, Exclusions, Timezone};
 use remembear::{Reminder, Schedule};
-use time::{Date, OffsetDateTime, Weekday};
+use std::num::NonZeroU32;
+use time::{Date, Duration, OffsetDateTime, Weekday};
 
 /// Returns the start of the current week
 fn get_start_of_this_week() -> Result<OffsetDateTime> {
@@ -44,6 +47,7 @@ async fn it_outputs_added_reminder() -> Result<()> {
             get_start_of_this_week()?,
             vec![1, 2],
         ),
+        status: Status::Active,
     })?;
 
     assert_eq!(expected_output, output);
@@ -51,6 +55,488 @@ async fn it_outputs_added_reminder() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn it_adds_reminders_in_a_timezone() -> Result<()> {
+    let executor = Executor::new()?;
+    let schedule = r#"{"Monday":["08:00:00.0"]}"#;
+
+    let output = executor
+        .execute(&[
+            "remembear",
+            "reminder",
+            "add",
+            "Take medication",
+            schedule,
+            "1",
+            "--timezone",
+            "Europe/Berlin",
+        ])
+        .await?;
+
+    let expected_output = serde_json::to_string_pretty(&Reminder {
+        uid: 1,
+        name: String::from("Take medication"),
+        schedule: Schedule::new(
+            serde_json::from_str(schedule)?,
+            get_start_of_this_week()?,
+            vec![1],
+        )
+        .with_timezone(Timezone::new("Europe/Berlin")?),
+        status: Status::Active,
+    })?;
+
+    assert_eq!(expected_output, output);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_adds_monthly_reminders() -> Result<()> {
+    let executor = Executor::new()?;
+    let schedule = r#"{"last friday":["18:00:00.0"]}"#;
+
+    let output = executor
+        .execute(&[
+            "remembear",
+            "reminder",
+            "add",
+            "Water the plants",
+            schedule,
+            "1",
+            "2",
+        ])
+        .await?;
+
+    let expected_output = serde_json::to_string_pretty(&Reminder {
+        uid: 1,
+        name: String::from("Water the plants"),
+        schedule: Schedule::from_recurrence(
+            serde_json::from_str(schedule)?,
+            get_start_of_this_week()?,
+            vec![1, 2],
+        ),
+        status: Status::Active,
+    })?;
+
+    assert_eq!(expected_output, output);
+    assert!(output.contains(r#""last Friday""#));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_adds_reminders_with_a_week_interval() -> Result<()> {
+    let executor = Executor::new()?;
+    let schedule = r#"{"Tuesday":["07:00:00.0"]}"#;
+
+    let output = executor
+        .execute(&[
+            "remembear",
+            "reminder",
+            "add",
+            "Take out the bins",
+            schedule,
+            "1",
+            "2",
+            "--week-interval",
+            "2",
+        ])
+        .await?;
+
+    let expected_output = serde_json::to_string_pretty(&Reminder {
+        uid: 1,
+        name: String::from("Take out the bins"),
+        schedule: Schedule::new(
+            serde_json::from_str(schedule)?,
+            get_start_of_this_week()?,
+            vec![1, 2],
+        )
+        .with_week_interval(NonZeroU32::new(2).ok_or("Invalid week interval")?),
+        status: Status::Active,
+    })?;
+
+    assert_eq!(expected_output, output);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_adds_one_off_reminders() -> Result<()> {
+    let executor = Executor::new()?;
+    let schedule = r#"{"once":"2026-11-03T14:00:00+01:00"}"#;
+
+    let output = executor
+        .execute(&["remembear", "reminder", "add", "Dentist", schedule, "1"])
+        .await?;
+
+    let expected_output = serde_json::to_string_pretty(&Reminder {
+        uid: 1,
+        name: String::from("Dentist"),
+        schedule: Schedule::from_recurrence(
+            serde_json::from_str(schedule)?,
+            get_start_of_this_week()?,
+            vec![1],
+        ),
+        status: Status::Active,
+    })?;
+
+    assert_eq!(expected_output, output);
+    assert!(output.contains(r#""status": "active""#));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_adds_reminders_with_recurrence_rules() -> Result<()> {
+    let executor = Executor::new()?;
+    let rule = "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH;BYHOUR=9\nEXDATE:20261228T090000";
+
+    let output = executor
+        .execute(&["remembear", "reminder", "add", "Standup", rule, "1", "2"])
+        .await?;
+
+    let expected_output = serde_json::to_string_pretty(&Reminder {
+        uid: 1,
+        name: String::from("Standup"),
+        schedule: Schedule::from_recurrence(
+            Recurrence::Rule {
+                rule: rule.parse()?,
+            },
+            get_start_of_this_week()?,
+            vec![1, 2],
+        ),
+        status: Status::Active,
+    })?;
+
+    assert_eq!(expected_output, output);
+    assert!(output.contains(&serde_json::to_string(rule)?));
+
+    let output = executor
+        .execute(&[
+            "remembear",
+            "reminder",
+            "add",
+            "Standup",
+            "FREQ=HOURLY",
+            "1",
+        ])
+        .await;
+
+    assert!(output.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_adds_reminders_with_cron_expressions() -> Result<()> {
+    let executor = Executor::new()?;
+    let cron = "30 7 * * 1-5";
+
+    let output = executor
+        .execute(&["remembear", "reminder", "add", "Standup", cron, "1", "2"])
+        .await?;
+
+    let expected_output = serde_json::to_string_pretty(&Reminder {
+        uid: 1,
+        name: String::from("Standup"),
+        schedule: Schedule::from_recurrence(
+            Recurrence::Cron {
+                cron: cron.parse()?,
+            },
+            get_start_of_this_week()?,
+            vec![1, 2],
+        ),
+        status: Status::Active,
+    })?;
+
+    assert_eq!(expected_output, output);
+    assert!(output.contains(r#""cron": "30 7 * * 1-5""#));
+
+    let output = executor
+        .execute(&["remembear", "reminder", "add", "Standup", "30 7 * * 8", "1"])
+        .await;
+
+    assert!(output.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_adds_reminders_with_rotation_strategies() -> Result<()> {
+    let executor = Executor::new()?;
+
+    let output = executor
+        .execute(&[
+            "remembear",
+            "reminder",
+            "add",
+            "Laundry",
+            r#"{"Sunday":["09:00:00.0"]}"#,
+            "1",
+            "2",
+            "--rotation",
+            "per-week",
+        ])
+        .await?;
+
+    let expected_output = serde_json::to_string_pretty(&Reminder {
+        uid: 1,
+        name: String::from("Laundry"),
+        schedule: Schedule::new(
+            serde_json::from_str(r#"{"Sunday":["09:00:00.0"]}"#)?,
+            get_start_of_this_week()?,
+            vec![1, 2],
+        )
+        .with_rotation("per-week".parse()?),
+        status: Status::Active,
+    })?;
+
+    assert_eq!(expected_output, output);
+    assert!(output.contains(r#""rotation": "per-week""#));
+
+    // Random rotations are given a seed so the order can be repeated
+    let output = executor
+        .execute(&["remembear", "reminder", "update", "1", "-r", "random"])
+        .await?;
+
+    assert!(output.contains(r#""rotation": "random:"#));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_adds_reminders_with_weighted_assignees() -> Result<()> {
+    let executor = Executor::new()?;
+
+    let output = executor
+        .execute(&[
+            "remembear",
+            "reminder",
+            "add",
+            "Cooking",
+            r#"{"Sunday":["18:00:00.0"]}"#,
+            "1:2",
+            "2",
+        ])
+        .await?;
+
+    let expected_output = serde_json::to_string_pretty(&Reminder {
+        uid: 1,
+        name: String::from("Cooking"),
+        schedule: Schedule::new(
+            serde_json::from_str(r#"{"Sunday":["18:00:00.0"]}"#)?,
+            get_start_of_this_week()?,
+            vec![1, 2],
+        )
+        .with_weights(vec![(1, 2)].into_iter().collect()),
+        status: Status::Active,
+    })?;
+
+    assert_eq!(expected_output, output);
+    assert!(output.contains(r#""weights": {"#));
+
+    // Weights are kept when updating other settings
+    let output = executor
+        .execute(&["remembear", "reminder", "update", "1", "-n", "Dinner"])
+        .await?;
+
+    assert!(output.contains(r#""1": 2"#));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_adds_reminders_with_handoff_notices() -> Result<()> {
+    let executor = Executor::new()?;
+
+    let output = executor
+        .execute(&[
+            "remembear",
+            "reminder",
+            "add",
+            "Trash",
+            "mon 07:00",
+            "1",
+            "2",
+            "--rotation",
+            "per-week",
+            "--handoff-notice",
+            "2d",
+        ])
+        .await?;
+
+    assert!(output.contains(r#""handoff_notice": "2d""#));
+
+    // The notice is kept when updating other settings, until it's cleared
+    let output = executor
+        .execute(&["remembear", "reminder", "update", "1", "-n", "Recycling"])
+        .await?;
+
+    assert!(output.contains(r#""handoff_notice": "2d""#));
+
+    let output = executor
+        .execute(&[
+            "remembear",
+            "reminder",
+            "update",
+            "1",
+            "--clear-handoff-notice",
+        ])
+        .await?;
+
+    assert!(!output.contains("handoff_notice"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_adds_reminders_with_catch_up_policies() -> Result<()> {
+    let executor = Executor::new()?;
+
+    let output = executor
+        .execute(&["remembear", "reminder", "add", "Trash", "mon 07:00", "1"])
+        .await?;
+
+    assert!(output.contains(r#""catch_up": "skip""#));
+
+    let output = executor
+        .execute(&[
+            "remembear",
+            "reminder",
+            "update",
+            "1",
+            "--catch-up",
+            "latest",
+        ])
+        .await?;
+
+    assert!(output.contains(r#""catch_up": "latest""#));
+
+    // The policy is kept when updating other settings
+    let output = executor
+        .execute(&["remembear", "reminder", "update", "1", "-n", "Recycling"])
+        .await?;
+
+    assert!(output.contains(r#""catch_up": "latest""#));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_adds_reminders_with_shorthand_schedules() -> Result<()> {
+    let executor = Executor::new()?;
+
+    let output = executor
+        .execute(&[
+            "remembear",
+            "reminder",
+            "add",
+            "Roadhouse",
+            "mon 10:30 and 22:30; wed 12:30",
+            "1",
+        ])
+        .await?;
+
+    let expected_output = serde_json::to_value(&Reminder {
+        uid: 1,
+        name: String::from("Roadhouse"),
+        schedule: Schedule::new(
+            serde_json::from_str(
+                r#"{"Monday":["10:30:00.0","22:30:00.0"],"Wednesday":["12:30:00.0"]}"#,
+            )?,
+            get_start_of_this_week()?,
+            vec![1],
+        ),
+        status: Status::Active,
+    })?;
+
+    // Weekdays are serialized in an arbitrary order, so compare the values instead of the text
+    assert_eq!(
+        expected_output,
+        serde_json::from_str::<serde_json::Value>(&output)?
+    );
+
+    let error = executor
+        .execute(&["remembear", "reminder", "add", "Roadhouse", "mon 10:3", "1"])
+        .await
+        .err()
+        .map(|error| error.to_string());
+
+    assert_eq!(
+        Some(String::from(
+            "Expected a time such as `08:00`, but found `10:3`\n    mon 10:3\n        ^^^^"
+        )),
+        error
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_adds_reminders_with_exclusions() -> Result<()> {
+    let executor = Executor::new()?;
+    let schedule = r#"{"Friday":["19:00:00.0"]}"#;
+
+    let output = executor
+        .execute(&[
+            "remembear",
+            "reminder",
+            "add",
+            "Movie night",
+            schedule,
+            "1",
+            "2",
+            "--exclude",
+            "2026-12-25",
+            "--exclude",
+            "2026-08-10..2026-08-16",
+        ])
+        .await?;
+
+    let expected_output = serde_json::to_string_pretty(&Reminder {
+        uid: 1,
+        name: String::from("Movie night"),
+        schedule: Schedule::new(
+            serde_json::from_str(schedule)?,
+            get_start_of_this_week()?,
+            vec![1, 2],
+        )
+        .with_exclusions(Exclusions {
+            dates: vec!["2026-12-25".parse()?, "2026-08-10..2026-08-16".parse()?],
+            advance_rotation: false,
+        }),
+        status: Status::Active,
+    })?;
+
+    assert_eq!(expected_output, output);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_errors_when_adding_reminders_in_an_unknown_timezone() -> Result<()> {
+    let executor = Executor::new()?;
+    let output = executor
+        .execute(&[
+            "remembear",
+            "reminder",
+            "add",
+            "Take medication",
+            r#"{"Monday":["08:00:00.0"]}"#,
+            "--timezone",
+            "Black/Lodge",
+        ])
+        .await
+        .map_err(|error| error.to_string());
+
+    assert_eq!(
+        Some(String::from("Unknown time zone Black/Lodge")),
+        output.err()
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn it_lists_all_reminders() -> Result<()> {
     let executor = Executor::new()?;
@@ -75,6 +561,7 @@ async fn it_lists_all_reminders() -> Result<()> {
                 get_start_of_this_week()?,
                 vec![1],
             ),
+            status: Status::Active,
         },
         Reminder {
             uid: 2,
@@ -84,6 +571,7 @@ async fn it_lists_all_reminders() -> Result<()> {
                 get_start_of_this_week()?,
                 vec![2],
             ),
+            status: Status::Active,
         },
     ])?;
 
@@ -92,6 +580,31 @@ async fn it_lists_all_reminders() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn it_lists_ended_reminders_as_completed() -> Result<()> {
+    let executor = Executor::new()?;
+
+    executor
+        .execute(&[
+            "remembear",
+            "reminder",
+            "add",
+            "Take antibiotics",
+            r#"{"Monday":["08:00:00.0"]}"#,
+            "1",
+            "--until",
+            "2020-01-10",
+        ])
+        .await?;
+
+    let output = executor.execute(&["remembear", "reminder", "list"]).await?;
+
+    assert!(output.contains(r#""until": "2020-01-10""#));
+    assert!(output.contains(r#""status": "completed""#));
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn it_updates_reminders() -> Result<()> {
     let executor = Executor::new()?;
@@ -125,6 +638,7 @@ async fn it_updates_reminders() -> Result<()> {
             get_start_of_this_week()?,
             vec![3, 4],
         ),
+        status: Status::Active,
     };
 
     let expected_output = serde_json::to_string_pretty(&expected_reminder)?;
@@ -174,6 +688,7 @@ async fn it_removes_reminders() -> Result<()> {
             get_start_of_this_week()?,
             vec![1],
         ),
+        status: Status::Active,
     })?;
 
     assert_eq!(expected_output, output);
@@ -199,3 +714,332 @@ async fn it_errors_when_removing_invalid_uid() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn it_keeps_the_rotation_when_updating_reminders() -> Result<()> {
+    let executor = Executor::new()?;
+
+    executor
+        .execute(&[
+            "remembear",
+            "reminder",
+            "add",
+            "Dishes",
+            "0 9 * * *",
+            "1",
+            "2",
+            "3",
+        ])
+        .await?;
+
+    let upcoming_assignees = || async {
+        let output = executor
+            .execute(&["remembear", "reminder", "upcoming", "1", "--count", "2"])
+            .await?;
+        let occurrences: serde_json::Value = serde_json::from_str(&output)?;
+
+        Result::Ok(vec![
+            occurrences[0]["assignees"].clone(),
+            occurrences[1]["assignees"].clone(),
+        ])
+    };
+
+    let expected_assignees = upcoming_assignees().await?;
+
+    executor
+        .execute(&["remembear", "reminder", "update", "1", "-n", "Chores"])
+        .await?;
+
+    assert_eq!(expected_assignees, upcoming_assignees().await?);
+
+    executor
+        .execute(&["remembear", "reminder", "update", "1", "-s", "0 21 * * *"])
+        .await?;
+
+    assert_eq!(expected_assignees, upcoming_assignees().await?);
+
+    // Changing the assignees keeps whoever is up next
+    executor
+        .execute(&[
+            "remembear",
+            "reminder",
+            "update",
+            "1",
+            "-a",
+            "1",
+            "2",
+            "3",
+            "4",
+        ])
+        .await?;
+
+    assert_eq!(expected_assignees[0], upcoming_assignees().await?[0]);
+
+    // The rotation can be restarted from this week instead
+    let output = executor
+        .execute(&["remembear", "reminder", "update", "1", "--reset-rotation"])
+        .await?;
+
+    assert!(!output.contains("rotation_offset"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_previews_substitutes_for_assignees_who_are_away() -> Result<()> {
+    let executor = Executor::new()?;
+    let today = OffsetDateTime::now_utc().date();
+    let from = today.to_string();
+    let to = (today + Duration::days(10)).to_string();
+
+    executor
+        .execute(&["remembear", "user", "add", "Laura"])
+        .await?;
+    executor
+        .execute(&["remembear", "user", "add", "Leland"])
+        .await?;
+    executor
+        .execute(&["remembear", "user", "away", "1", &from, &to])
+        .await?;
+    executor
+        .execute(&[
+            "remembear",
+            "reminder",
+            "add",
+            "Dishes",
+            "0 9 * * *",
+            "1",
+            "2",
+        ])
+        .await?;
+
+    let output = executor
+        .execute(&["remembear", "reminder", "upcoming", "1", "--count", "4"])
+        .await?;
+    let occurrences: serde_json::Value = serde_json::from_str(&output)?;
+    let occurrences = occurrences.as_array().ok_or("Expected an array")?;
+
+    assert_eq!(4, occurrences.len());
+
+    for occurrence in occurrences {
+        assert_eq!(
+            serde_json::json!([{"uid": 2, "name": "Leland"}]),
+            occurrence["assignees"]
+        );
+    }
+
+    assert!(occurrences.iter().any(|occurrence| {
+        occurrence["substitutions"] == serde_json::json!([{"absent": 1, "substitute": 2}])
+    }));
+
+    let output = executor
+        .execute(&[
+            "remembear",
+            "reminder",
+            "upcoming",
+            "1",
+            "--count",
+            "4",
+            "--table",
+        ])
+        .await?;
+
+    assert!(output
+        .lines()
+        .any(|line| line.ends_with("Leland (covering for Laura)")));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_lists_upcoming_occurrences_with_assignee_names() -> Result<()> {
+    let executor = Executor::new()?;
+
+    executor
+        .execute(&["remembear", "user", "add", "Laura"])
+        .await?;
+    executor
+        .execute(&["remembear", "user", "add", "Leland"])
+        .await?;
+    executor
+        .execute(&[
+            "remembear",
+            "reminder",
+            "add",
+            "Dishes",
+            "0 9 * * *",
+            "1",
+            "2",
+        ])
+        .await?;
+    executor
+        .execute(&["remembear", "reminder", "add", "Laundry", "0 18 * * *", "2"])
+        .await?;
+
+    let output = executor
+        .execute(&["remembear", "reminder", "upcoming", "--days", "3"])
+        .await?;
+    let occurrences: serde_json::Value = serde_json::from_str(&output)?;
+    let occurrences = occurrences.as_array().ok_or("Expected an array")?;
+
+    assert_eq!(6, occurrences.len());
+    assert!(occurrences
+        .iter()
+        .any(|occurrence| occurrence["assignees"]
+            == serde_json::json!([{"uid": 1, "name": "Laura"}])));
+    assert!(occurrences.iter().all(|occurrence| {
+        occurrence["reminder"] != "Laundry"
+            || occurrence["assignees"] == serde_json::json!([{"uid": 2, "name": "Leland"}])
+    }));
+
+    let output = executor
+        .execute(&["remembear", "reminder", "upcoming", "1", "--count", "2"])
+        .await?;
+    let occurrences: serde_json::Value = serde_json::from_str(&output)?;
+
+    assert_eq!(Some(2), occurrences.as_array().map(Vec::len));
+
+    let output = executor
+        .execute(&["remembear", "reminder", "upcoming", "2", "--table"])
+        .await?;
+
+    assert!(output.starts_with("Time"));
+    assert_eq!(15, output.lines().count());
+    assert!(output
+        .lines()
+        .skip(1)
+        .all(|line| line.contains("Laundry") && line.ends_with("Leland")));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_swaps_and_trades_occurrences() -> Result<()> {
+    let executor = Executor::new()?;
+
+    for name in &["Laura", "Leland", "Sarah"] {
+        executor
+            .execute(&["remembear", "user", "add", name])
+            .await?;
+    }
+
+    executor
+        .execute(&[
+            "remembear",
+            "reminder",
+            "add",
+            "Dishes",
+            "0 9 * * *",
+            "1",
+            "2",
+        ])
+        .await?;
+
+    let preview = |output: &str| -> Result<Vec<serde_json::Value>> {
+        let occurrences: serde_json::Value = serde_json::from_str(output)?;
+        Ok(occurrences.as_array().ok_or("Expected an array")?.clone())
+    };
+
+    let output = executor
+        .execute(&["remembear", "reminder", "upcoming", "1", "--count", "2"])
+        .await?;
+    let occurrences = preview(&output)?;
+    let first_time = occurrences[0]["time"].as_str().ok_or("Expected a time")?;
+    let second_time = occurrences[1]["time"].as_str().ok_or("Expected a time")?;
+    let first_assignee = occurrences[0]["assignees"][0]["uid"].clone();
+    let second_assignee = occurrences[1]["assignees"][0]["uid"].clone();
+
+    // Trade the first two occurrences between their assignees
+    let output = executor
+        .execute(&[
+            "remembear",
+            "reminder",
+            "swap",
+            "1",
+            first_time,
+            &second_assignee.to_string(),
+            "--trade",
+            second_time,
+        ])
+        .await?;
+
+    assert_eq!(2, preview(&output)?.len());
+
+    let output = executor
+        .execute(&["remembear", "reminder", "upcoming", "1", "--count", "2"])
+        .await?;
+    let occurrences = preview(&output)?;
+
+    assert_eq!(second_assignee, occurrences[0]["assignees"][0]["uid"]);
+    assert_eq!(first_assignee, occurrences[1]["assignees"][0]["uid"]);
+    assert_eq!(serde_json::json!(true), occurrences[0]["override"]);
+
+    // Hand the first occurrence to someone outside the rotation
+    executor
+        .execute(&["remembear", "reminder", "swap", "1", first_time, "3"])
+        .await?;
+
+    let output = executor
+        .execute(&["remembear", "reminder", "upcoming", "1", "--count", "1"])
+        .await?;
+
+    assert_eq!(
+        serde_json::json!([{"uid": 3, "name": "Sarah"}]),
+        preview(&output)?[0]["assignees"]
+    );
+
+    let error = executor
+        .execute(&[
+            "remembear",
+            "reminder",
+            "swap",
+            "1",
+            "2020-01-01T09:30:00Z",
+            "3",
+        ])
+        .await
+        .err()
+        .map(|error| error.to_string());
+
+    assert_eq!(
+        Some(String::from(
+            "The reminder does not occur at 2020-01-01T09:30:00Z"
+        )),
+        error
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn it_errors_when_swapping_occurrences_without_assignees() -> Result<()> {
+    let executor = Executor::new()?;
+
+    executor
+        .execute(&["remembear", "user", "add", "Laura"])
+        .await?;
+    executor
+        .execute(&["remembear", "reminder", "add", "Dishes", "0 9 * * *"])
+        .await?;
+
+    let tomorrow = OffsetDateTime::now_utc()
+        .date()
+        .next_day()
+        .ok_or("Invalid date")?
+        .to_string();
+
+    let output = executor
+        .execute(&["remembear", "reminder", "swap", "1", &tomorrow, "1"])
+        .await
+        .map_err(|error| error.to_string());
+
+    assert_eq!(
+        Some(format!(
+            "Occurrence {} has no assignee to replace",
+            tomorrow
+        )),
+        output.err()
+    );
+
+    Ok(())
+}
@@ -10,7 +10,9 @@ use common_command::Executor;
 #[tokio::test]
 async fn it_starts_scheduler() -> Result<()> {
     let executor = Executor::new()?;
-    let output = executor.execute(&["remembear", "start"]).await?;
+    let output = executor
+        .execute(&["remembear", "start", "--no-reload"])
+        .await?;
 
     // There are no reminders in the test database, so the queue will be empty
     assert_eq!(String::from("Scheduler queue is empty"), output);
@@ -6,6 +6,7 @@ mod common_database;
 
 use common::Result;
 use common_command::Executor;
+use remembear::user::model::Absence;
 use remembear::User;
 
 #[tokio::test]
@@ -130,6 +131,99 @@ async fn it_removes_users() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn it_only_removes_assigned_users_when_they_are_unassigned() -> Result<()> {
+    let executor = Executor::new()?;
+
+    for name in &["Laura", "Leland", "Sarah"] {
+        executor
+            .execute(&["remembear", "user", "add", name])
+            .await?;
+    }
+
+    executor
+        .execute(&[
+            "remembear",
+            "reminder",
+            "add",
+            "Dishes",
+            "0 9 * * *",
+            "1",
+            "2",
+        ])
+        .await?;
+    executor
+        .execute(&[
+            "remembear",
+            "reminder",
+            "add",
+            "Laundry",
+            "0 9 * * 0",
+            "2:2",
+            "3",
+        ])
+        .await?;
+
+    let assignees = || async {
+        let output = executor.execute(&["remembear", "reminder", "list"]).await?;
+        let reminders: serde_json::Value = serde_json::from_str(&output)?;
+
+        Result::Ok(vec![
+            reminders[0]["assignees"].clone(),
+            reminders[1]["assignees"].clone(),
+        ])
+    };
+
+    // Users assigned to reminders aren't removed by default
+    let error = executor
+        .execute(&["remembear", "user", "remove", "2"])
+        .await
+        .err()
+        .map(|error| error.to_string());
+
+    assert_eq!(
+        Some(String::from(
+            "User 2 is assigned to reminders 1 (Dishes), 2 (Laundry), remove them with --reassign <uid> or --drop-from-reminders"
+        )),
+        error
+    );
+
+    // Reassigning to a user who is already assigned drops the removed user instead
+    executor
+        .execute(&["remembear", "user", "remove", "2", "--reassign", "3"])
+        .await?;
+
+    assert_eq!(
+        vec![serde_json::json!([1, 3]), serde_json::json!([3])],
+        assignees().await?
+    );
+
+    // Reminders are left untouched when any of them would have no assignees left
+    let error = executor
+        .execute(&["remembear", "user", "remove", "3", "--drop-from-reminders"])
+        .await
+        .err()
+        .map(|error| error.to_string());
+
+    assert_eq!(
+        Some(String::from(
+            "Reminder 2 (Laundry) would have no assignees left, use --reassign <uid> instead"
+        )),
+        error
+    );
+
+    executor
+        .execute(&["remembear", "user", "remove", "1", "--drop-from-reminders"])
+        .await?;
+
+    assert_eq!(
+        vec![serde_json::json!([3]), serde_json::json!([3])],
+        assignees().await?
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn it_errors_when_removing_invalid_uid() -> Result<()> {
     let executor = Executor::new()?;
@@ -142,3 +236,54 @@ async fn it_errors_when_removing_invalid_uid() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn it_marks_users_as_away() -> Result<()> {
+    let executor = Executor::new()?;
+
+    executor
+        .execute(&["remembear", "user", "add", "Laura"])
+        .await?;
+
+    let output = executor
+        .execute(&[
+            "remembear",
+            "user",
+            "away",
+            "1",
+            "2026-08-10",
+            "2026-08-16",
+            "--defer",
+        ])
+        .await?;
+
+    let expected_output = serde_json::to_string_pretty(&Absence {
+        uid: 1,
+        user_uid: 1,
+        dates: "2026-08-10..2026-08-16".parse()?,
+        defer_turn: true,
+    })?;
+
+    assert_eq!(expected_output, output);
+
+    let output = executor
+        .execute(&["remembear", "user", "away", "1", "2026-08-16", "2026-08-10"])
+        .await
+        .map_err(|error| error.to_string());
+
+    assert_eq!(
+        Some(String::from(
+            "Invalid dates, 2026-08-16 is after 2026-08-10"
+        )),
+        output.err()
+    );
+
+    let output = executor
+        .execute(&["remembear", "user", "away", "2", "2026-08-10", "2026-08-16"])
+        .await
+        .map_err(|error| error.to_string());
+
+    assert_eq!(Some(String::from("Invalid uid 2")), output.err());
+
+    Ok(())
+}
@@ -5,8 +5,8 @@ mod common_database;
 
 use common::Result;
 use remembear::integration::model::{Record, Uid};
-use remembear::integration::Integration;
 use remembear::integration::{provider::Providable, Provider};
+use remembear::integration::{Delivery, Integration};
 use remembear::{Providers, Reminder, User};
 use time::OffsetDateTime;
 
@@ -27,8 +27,20 @@ impl Integration for MockIntegration {
         _reminder: &Reminder,
         _assignees: &[User],
         _timestamp: &OffsetDateTime,
-    ) -> Result<()> {
-        Ok(())
+        _late: bool,
+    ) -> Delivery {
+        Box::pin(std::future::ready(Ok(())))
+    }
+
+    fn handoff(
+        &mut self,
+        _providers: &Providers,
+        _reminder: &Reminder,
+        _incoming: &[User],
+        _outgoing: &[User],
+        _timestamp: &OffsetDateTime,
+    ) -> Delivery {
+        Box::pin(std::future::ready(Ok(())))
     }
 }
 
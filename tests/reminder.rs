@@ -3,10 +3,15 @@
 mod common;
 mod common_database;
 
-use time::{macros::time, Date, Weekday};
+use time::{
+    macros::{datetime, time},
+    Date, Weekday,
+};
 
 use common::Result;
-use remembear::reminder::model::{NewReminder, Reminder, UpdatedReminder};
+use remembear::reminder::model::{
+    AssignmentOverride, NewReminder, PastAssignment, Reminder, Status, UpdatedReminder,
+};
 use remembear::reminder::{provider::Providable, Provider};
 use remembear::Schedule;
 
@@ -87,11 +92,13 @@ fn it_returns_inserted_reminders_on_insertion() -> Result<()> {
         uid: 1,
         name: String::from("Meet at Roadhouse"),
         schedule: get_roadhouse_schedule()?,
+        status: Status::Active,
     };
     let expected_reminder_2 = Reminder {
         uid: 2,
         name: String::from("2:53"),
         schedule: get_253_schedule()?,
+        status: Status::Active,
     };
 
     assert_eq!(expected_reminder_1, new_reminder_1);
@@ -120,11 +127,13 @@ fn it_gets_inserted_reminders() -> Result<()> {
             uid: 1,
             name: String::from("Meet at Roadhouse"),
             schedule: get_roadhouse_schedule()?,
+            status: Status::Active,
         },
         Reminder {
             uid: 2,
             name: String::from("2:53"),
             schedule: get_253_schedule()?,
+            status: Status::Active,
         },
     ];
 
@@ -152,11 +161,13 @@ fn it_gets_inserted_reminders_by_uid() -> Result<()> {
         uid: 1,
         name: String::from("Meet at Roadhouse"),
         schedule: get_roadhouse_schedule()?,
+        status: Status::Active,
     };
     let expected_reminder_2 = Reminder {
         uid: 2,
         name: String::from("2:53"),
         schedule: get_253_schedule()?,
+        status: Status::Active,
     };
 
     assert_eq!(expected_reminder_1, provider.get_by_uid(1)?);
@@ -188,12 +199,14 @@ fn it_updates_existing_reminders() -> Result<()> {
         uid: 1,
         name: String::from("Meet Donna at Roadhouse"),
         schedule: get_roadhouse_schedule()?,
+        status: Status::Active,
     })?;
 
     provider.update(UpdatedReminder {
         uid: 2,
         name: String::from("2:54"),
         schedule: get_254_schedule()?,
+        status: Status::Finished,
     })?;
 
     let expected_reminders = vec![
@@ -201,11 +214,13 @@ fn it_updates_existing_reminders() -> Result<()> {
             uid: 1,
             name: String::from("Meet Donna at Roadhouse"),
             schedule: get_roadhouse_schedule()?,
+            status: Status::Active,
         },
         Reminder {
             uid: 2,
             name: String::from("2:54"),
             schedule: get_254_schedule()?,
+            status: Status::Finished,
         },
     ];
 
@@ -228,12 +243,14 @@ fn it_returns_updated_reminder() -> Result<()> {
         uid: 1,
         name: String::from("2:54"),
         schedule: get_254_schedule()?,
+        status: Status::Active,
     })?;
 
     let expected_reminder = Reminder {
         uid: 1,
         name: String::from("2:54"),
         schedule: get_254_schedule()?,
+        status: Status::Active,
     };
 
     assert_eq!(expected_reminder, updated_reminder);
@@ -241,6 +258,30 @@ fn it_returns_updated_reminder() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn it_sets_reminder_statuses() -> Result<()> {
+    let database = common_database::new()?;
+    let provider = Provider::new(database);
+
+    provider.add(NewReminder {
+        name: String::from("Meet at Roadhouse"),
+        schedule: get_roadhouse_schedule()?,
+    })?;
+
+    provider.set_status(1, Status::Finished)?;
+
+    let expected_reminder = Reminder {
+        uid: 1,
+        name: String::from("Meet at Roadhouse"),
+        schedule: get_roadhouse_schedule()?,
+        status: Status::Finished,
+    };
+
+    assert_eq!(expected_reminder, provider.get_by_uid(1)?);
+
+    Ok(())
+}
+
 #[test]
 fn it_removes_existing_reminders() -> Result<()> {
     let database = common_database::new()?;
@@ -268,9 +309,164 @@ fn it_removes_existing_reminders() -> Result<()> {
         uid: 2,
         name: String::from("2:53"),
         schedule: get_253_schedule()?,
+        status: Status::Active,
     }];
 
     assert_eq!(expected_reminders, provider.get_all()?);
 
     Ok(())
 }
+
+#[test]
+fn it_replaces_assignment_overrides_for_the_same_occurrence() -> Result<()> {
+    let database = common_database::new()?;
+    let provider = Provider::new(database);
+
+    provider.add(NewReminder {
+        name: String::from("Roadhouse"),
+        schedule: get_roadhouse_schedule()?,
+    })?;
+
+    let occurrence = datetime!(2020-01-13 21:00:00 UTC);
+
+    provider.set_override(AssignmentOverride {
+        reminder_uid: 1,
+        occurrence,
+        assignees: vec![2],
+    })?;
+    provider.set_override(AssignmentOverride {
+        reminder_uid: 1,
+        occurrence: datetime!(2020-01-13 22:00:00 +01:00),
+        assignees: vec![3],
+    })?;
+
+    let expected_overrides = vec![AssignmentOverride {
+        reminder_uid: 1,
+        occurrence,
+        assignees: vec![3],
+    }];
+
+    assert_eq!(expected_overrides, provider.get_overrides(1)?);
+    assert!(provider.get_overrides(2)?.is_empty());
+
+    // Several overrides are set together
+    let traded_overrides = vec![
+        AssignmentOverride {
+            reminder_uid: 1,
+            occurrence,
+            assignees: vec![1],
+        },
+        AssignmentOverride {
+            reminder_uid: 1,
+            occurrence: datetime!(2020-01-20 21:00:00 UTC),
+            assignees: vec![2],
+        },
+    ];
+
+    provider.set_overrides(traded_overrides.clone())?;
+    assert_eq!(traded_overrides, provider.get_overrides(1)?);
+
+    // Overrides are removed along with their reminder
+    provider.remove(1)?;
+    assert!(provider.get_overrides(1)?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn it_records_the_assignment_history_of_reminders() -> Result<()> {
+    let database = common_database::new()?;
+    let provider = Provider::new(database);
+
+    provider.add(NewReminder {
+        name: String::from("Roadhouse"),
+        schedule: get_roadhouse_schedule()?,
+    })?;
+
+    let first_assignment = PastAssignment {
+        reminder_uid: 1,
+        occurrence: datetime!(2020-01-13 21:00:00 UTC),
+        assignees: vec![1],
+    };
+    let second_assignment = PastAssignment {
+        reminder_uid: 1,
+        occurrence: datetime!(2020-01-14 21:00:00 UTC),
+        assignees: vec![2, 3],
+    };
+
+    provider.add_history(first_assignment.clone())?;
+    provider.add_history(second_assignment.clone())?;
+
+    assert_eq!(
+        vec![first_assignment, second_assignment],
+        provider.get_history(1)?
+    );
+
+    // History is removed along with its reminder
+    provider.remove(1)?;
+    assert!(provider.get_history(1)?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn it_records_the_last_occurrence_of_reminders() -> Result<()> {
+    let database = common_database::new()?;
+    let provider = Provider::new(database);
+
+    provider.add(NewReminder {
+        name: String::from("Roadhouse"),
+        schedule: get_roadhouse_schedule()?,
+    })?;
+
+    assert_eq!(None, provider.get_last_occurrence(1)?);
+
+    provider.set_last_occurrence(1, datetime!(2020-01-13 22:00 +1))?;
+    provider.set_last_occurrence(1, datetime!(2020-01-14 21:00 UTC))?;
+    assert_eq!(
+        Some(datetime!(2020-01-14 21:00 UTC)),
+        provider.get_last_occurrence(1)?
+    );
+
+    // The last occurrence is removed along with its reminder
+    provider.remove(1)?;
+    assert_eq!(None, provider.get_last_occurrence(1)?);
+
+    Ok(())
+}
+
+#[test]
+fn it_counts_changes_to_reminders() -> Result<()> {
+    let database = common_database::new()?;
+    let provider = Provider::new(database);
+
+    assert_eq!(0, provider.get_change_count()?);
+
+    provider.add(NewReminder {
+        name: String::from("Meet at Roadhouse"),
+        schedule: get_roadhouse_schedule()?,
+    })?;
+    assert_eq!(1, provider.get_change_count()?);
+
+    provider.update(UpdatedReminder {
+        uid: 1,
+        name: String::from("Meet at the Double R"),
+        schedule: get_roadhouse_schedule()?,
+        status: Status::Active,
+    })?;
+    provider.set_status(1, Status::Completed)?;
+    assert_eq!(3, provider.get_change_count()?);
+
+    // Assignments of occurrences are not changes to the reminder
+    provider.add_history(PastAssignment {
+        reminder_uid: 1,
+        occurrence: datetime!(2020-01-13 21:00 UTC),
+        assignees: vec![1],
+    })?;
+    assert_eq!(3, provider.get_change_count()?);
+
+    provider.remove(1)?;
+    assert_eq!(4, provider.get_change_count()?);
+
+    Ok(())
+}
@@ -12,10 +12,11 @@ use thiserror::Error;
 use time::{macros::time, Date, Weekday};
 
 use common::Result;
-use remembear::{schedule, Schedule};
+use remembear::schedule::{self, model::Recurrence, MonthDay, Timezone};
+use remembear::Schedule;
 
 table! {
-    use diesel::sql_types::{Text, Integer};
+    use diesel::sql_types::{Text, Integer, Nullable};
 
     /// Mock table to test schedule reads from
     schedule_test(start_week) {
@@ -23,6 +24,17 @@ table! {
         #[sql_name = "startweek"]
         start_week -> Integer,
         assignees -> Text,
+        timezone -> Text,
+        week_interval -> Integer,
+        exclusions -> Text,
+        until -> Nullable<Text>,
+        occurrence_limit -> Nullable<Integer>,
+        assignees_per_occurrence -> Integer,
+        rotation -> Text,
+        rotation_offset -> Integer,
+        handoff_notice -> Nullable<Integer>,
+        catch_up -> Text,
+        created -> Nullable<Text>,
     }
 }
 
@@ -40,6 +52,7 @@ fn insert(
     schedule: &str,
     start_week: i32,
     assignees: &str,
+    timezone: &str,
 ) -> std::result::Result<schedule::Provider, Error> {
     let database = common_database::new().map_err(Error::Database)?;
 
@@ -48,7 +61,18 @@ fn insert(
         CREATE TABLE schedule_test (
             schedule Text NOT NULL,
             startweek Integer NOT NULL,
-            assignees Text NOT NULL
+            assignees Text NOT NULL,
+            timezone Text NOT NULL,
+            week_interval Integer NOT NULL DEFAULT 1,
+            exclusions Text NOT NULL DEFAULT '{"dates":[],"advance_rotation":false}',
+            until Text,
+            occurrence_limit Integer,
+            assignees_per_occurrence Integer NOT NULL DEFAULT 1,
+            rotation Text NOT NULL DEFAULT 'round-robin',
+            rotation_offset Integer NOT NULL DEFAULT 0,
+            handoff_notice Integer,
+            catch_up Text NOT NULL DEFAULT 'skip',
+            created Text
         );
     "#,
     )?;
@@ -58,6 +82,7 @@ fn insert(
             schedule_test::schedule.eq(schedule),
             schedule_test::start_week.eq(start_week),
             schedule_test::assignees.eq(assignees),
+            schedule_test::timezone.eq(timezone),
         ))
         .execute(database.connection())?;
 
@@ -82,6 +107,92 @@ fn it_reads_schedules_from_sql() -> Result<()> {
         r#"{"Monday":["01:23:45.0","12:34:56.0"]}"#,
         202003,
         "[1, 2, 3]",
+        "UTC",
+    )?
+    .try_into()?;
+
+    assert_eq!(expected_schedule, database_schedule);
+
+    Ok(())
+}
+
+#[test]
+fn it_reads_weighted_schedule_assignees_from_sql() -> Result<()> {
+    let expected_schedule = Schedule::new(
+        vec![(Weekday::Monday, vec![time!(08:00:00)])]
+            .into_iter()
+            .collect(),
+        Date::from_iso_week_date(2020, 3, Weekday::Monday)?
+            .midnight()
+            .assume_utc(),
+        vec![1, 2],
+    )
+    .with_weights(vec![(1, 2)].into_iter().collect());
+
+    let database_schedule = insert(
+        r#"{"Monday":["08:00:00.0"]}"#,
+        202003,
+        r#"[{"uid":1,"weight":2},{"uid":2,"weight":1}]"#,
+        "UTC",
+    )?
+    .try_into()?;
+
+    assert_eq!(expected_schedule, database_schedule);
+
+    Ok(())
+}
+
+#[test]
+fn it_reads_schedule_timezones_from_sql() -> Result<()> {
+    let expected_schedule = Schedule::new(
+        vec![(Weekday::Monday, vec![time!(08:00:00)])]
+            .into_iter()
+            .collect(),
+        Date::from_iso_week_date(2020, 3, Weekday::Monday)?
+            .midnight()
+            .assume_utc(),
+        vec![1],
+    )
+    .with_timezone(Timezone::new("Europe/Berlin")?);
+
+    let database_schedule = insert(
+        r#"{"Monday":["08:00:00.0"]}"#,
+        202003,
+        "[1]",
+        "Europe/Berlin",
+    )?
+    .try_into()?;
+
+    assert_eq!(expected_schedule, database_schedule);
+
+    Ok(())
+}
+
+#[test]
+fn it_reads_monthly_schedules_from_sql() -> Result<()> {
+    let expected_schedule = Schedule::from_recurrence(
+        Recurrence::Monthly(
+            vec![
+                (MonthDay::Day(1), vec![time!(09:00:00)]),
+                (
+                    MonthDay::LastWeekday(Weekday::Friday),
+                    vec![time!(18:00:00)],
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        ),
+        Date::from_iso_week_date(2020, 3, Weekday::Monday)?
+            .midnight()
+            .assume_utc(),
+        vec![1, 2],
+    );
+
+    let database_schedule = insert(
+        r#"{"1":["09:00:00.0"],"last Friday":["18:00:00.0"]}"#,
+        202003,
+        "[1, 2]",
+        "UTC",
     )?
     .try_into()?;
 
@@ -92,7 +203,7 @@ fn it_reads_schedules_from_sql() -> Result<()> {
 
 #[test]
 fn it_fails_to_read_invalid_schedule_times_from_sql() -> Result<()> {
-    let database_result = insert(r#"{"mon":["99:99:99"]}"#, 202003, "[1, 2, 3]");
+    let database_result = insert(r#"{"mon":["99:99:99"]}"#, 202003, "[1, 2, 3]", "UTC");
 
     assert!(matches!(
         database_result,
@@ -106,7 +217,7 @@ fn it_fails_to_read_invalid_schedule_times_from_sql() -> Result<()> {
 
 #[test]
 fn it_fails_to_read_invalid_schedule_assignees_from_sql() -> Result<()> {
-    let database_result = insert(r#"{"mon":["12:34:56"]}"#, 202003, "1");
+    let database_result = insert(r#"{"mon":["12:34:56"]}"#, 202003, "1", "UTC");
 
     assert!(matches!(
         database_result,
@@ -4,8 +4,11 @@ mod common;
 mod common_database;
 
 use common::Result;
-use remembear::user::model::{NewUser, UpdatedUser, User};
+use remembear::reminder::model::{Status, UpdatedReminder};
+use remembear::user::model::{Absence, NewAbsence, NewUser, UpdatedUser, User};
 use remembear::user::{provider::Providable, Provider};
+use remembear::Schedule;
+use time::{macros::time, Date, Weekday};
 
 #[test]
 fn it_gets_nothing_without_users() -> Result<()> {
@@ -194,8 +197,8 @@ fn it_removes_existing_users() -> Result<()> {
         name: String::from("Leland"),
     })?;
 
-    provider.remove(1)?;
-    provider.remove(3)?;
+    provider.remove(1, Vec::new())?;
+    provider.remove(3, Vec::new())?;
 
     let expected_users = vec![User {
         uid: 2,
@@ -206,3 +209,73 @@ fn it_removes_existing_users() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn it_keeps_users_when_their_reminders_fail_to_update() -> Result<()> {
+    let database = common_database::new()?;
+    let provider = Provider::new(database);
+
+    provider.add(NewUser {
+        name: String::from("Laura"),
+    })?;
+
+    provider.add_absence(NewAbsence {
+        user_uid: 1,
+        dates: "2026-08-10..2026-08-16".parse()?,
+        defer_turn: false,
+    })?;
+
+    // The reminder no longer exists, so the removal fails
+    let unassigned = vec![UpdatedReminder {
+        uid: 1,
+        name: String::from("Dishes"),
+        schedule: Schedule::new(
+            vec![(Weekday::Monday, vec![time!(21:00:00)])]
+                .into_iter()
+                .collect(),
+            Date::from_iso_week_date(2020, 2, Weekday::Monday)?
+                .midnight()
+                .assume_utc(),
+            vec![2],
+        ),
+        status: Status::Active,
+    }];
+
+    assert!(provider.remove(1, unassigned).is_err());
+    assert_eq!(1, provider.get_all()?.len());
+    assert_eq!(1, provider.get_absences()?.len());
+
+    Ok(())
+}
+
+#[test]
+fn it_gets_absences_of_users() -> Result<()> {
+    let database = common_database::new()?;
+    let provider = Provider::new(database);
+
+    provider.add(NewUser {
+        name: String::from("Laura"),
+    })?;
+
+    let new_absence = provider.add_absence(NewAbsence {
+        user_uid: 1,
+        dates: "2026-08-10..2026-08-16".parse()?,
+        defer_turn: true,
+    })?;
+
+    let expected_absence = Absence {
+        uid: 1,
+        user_uid: 1,
+        dates: "2026-08-10..2026-08-16".parse()?,
+        defer_turn: true,
+    };
+
+    assert_eq!(expected_absence, new_absence);
+    assert_eq!(vec![expected_absence], provider.get_absences()?);
+
+    // Absences are removed along with their user
+    provider.remove(1, Vec::new())?;
+    assert!(provider.get_absences()?.is_empty());
+
+    Ok(())
+}